@@ -0,0 +1,338 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+
+use crate::core::traits::{
+    DataNormalizer, LabelGenerator, ManifestSubmitter, PickupScheduler, RateProvider, ReturnService, ShipmentTracker,
+    ShippingCarrier,
+};
+use crate::errors::DeliveryError;
+use crate::labels::{render_label, render_qr_code, RENDERED_FORMATS};
+use crate::manifest::Manifest;
+use crate::models::{
+    Address, CarrierCode, LabelFormat, LabelOptions, Parcel, Pickup, PickupRequest, PickupStatus, Rate, ReturnMethod,
+    ReturnRequest, ReturnShipment, ShipmentStatus, ShippingLabel, TrackingEvent, TrackingInfo,
+    TrackingSubStatus,
+};
+use crate::utils::address::AddressNormalizer;
+use crate::utils::status_mapping;
+
+/// Préfixe des numéros de suivi générés par le transporteur fictif
+const TRACKING_PREFIX: &str = "MOCK";
+
+/// Durée de validité des retours, en jours
+const RETURN_VALIDITY_DAYS: i64 = 30;
+
+/// Transporteur fictif : tarifs calculés localement, étiquettes générées
+/// par le moteur de rendu intégré, suivi simulé
+#[derive(Debug, Clone)]
+pub struct MockCarrier {
+    code: CarrierCode,
+    available: bool,
+    account: Option<String>,
+    pickups: Arc<Mutex<HashMap<String, Pickup>>>,  // Enlèvements réservés, par numéro de confirmation
+}
+
+impl MockCarrier {
+    /// Crée un transporteur fictif se présentant sous le code donné
+    pub fn new(code: CarrierCode) -> Self {
+        Self {
+            code,
+            available: true,
+            account: None,
+            pickups: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Simule une indisponibilité de l'API
+    pub fn with_available(mut self, available: bool) -> Self {
+        self.available = available;
+        self
+    }
+
+    /// Définit le compte client
+    pub fn with_account(mut self, account: &str) -> Self {
+        self.account = Some(account.to_string());
+        self
+    }
+
+    /// Numéro de suivi déterministe dérivé de l'identifiant du colis
+    fn tracking_number(parcel: &Parcel) -> String {
+        let digits: String = parcel.id.as_u128().to_string().chars().take(12).collect();
+        format!("{}{:0>12}", TRACKING_PREFIX, digits)
+    }
+
+    fn rate(&self, parcel: &Parcel, service: &str, service_code: &str, base: f64, per_kg: f64, days: u32) -> Rate {
+        Rate {
+            id: format!("mock-{}-{}", service_code, parcel.id),
+            carrier: self.code,
+            service: service.to_string(),
+            service_code: service_code.to_string(),
+            price: ((base + per_kg * parcel.weight) * 100.0).round() / 100.0,
+            currency: "EUR".to_string(),
+            estimated_delivery: Some(Utc::now() + Duration::days(days as i64)),
+            delivery_days: Some(days),
+            guaranteed_delivery: days == 1,
+            features: Vec::new(),
+            billable_weight: None,
+            estimated: true,
+        }
+        .with_billable_weight(parcel, crate::utils::weight::public_rules())
+    }
+}
+
+#[async_trait]
+impl RateProvider for MockCarrier {
+    async fn get_rates(&self, parcel: &Parcel) -> Result<Vec<Rate>, DeliveryError> {
+        self.get_rates_blocking(parcel)
+    }
+
+    fn get_rates_blocking(&self, parcel: &Parcel) -> Result<Vec<Rate>, DeliveryError> {
+        Ok(vec![
+            self.rate(parcel, "Standard", "STD", 5.90, 0.80, 3),
+            self.rate(parcel, "Express", "EXP", 12.50, 1.50, 1),
+        ])
+    }
+}
+
+#[async_trait]
+impl LabelGenerator for MockCarrier {
+    async fn generate_label(&self, parcel: &Parcel, rate: &Rate, options: &LabelOptions) -> Result<ShippingLabel, DeliveryError> {
+        self.generate_label_blocking(parcel, rate, options)
+    }
+
+    fn generate_label_blocking(&self, parcel: &Parcel, rate: &Rate, options: &LabelOptions) -> Result<ShippingLabel, DeliveryError> {
+        render_label(parcel, rate, &Self::tracking_number(parcel), options)
+    }
+
+    fn supported_label_formats(&self) -> Vec<LabelFormat> {
+        RENDERED_FORMATS.to_vec()
+    }
+}
+
+#[async_trait]
+impl ShipmentTracker for MockCarrier {
+    async fn track_parcel(&self, tracking_number: &str) -> Result<TrackingInfo, DeliveryError> {
+        self.track_parcel_blocking(tracking_number)
+    }
+
+    fn track_parcel_blocking(&self, tracking_number: &str) -> Result<TrackingInfo, DeliveryError> {
+        if !self.can_track(tracking_number) {
+            return Err(DeliveryError::UnsupportedTrackingNumber(tracking_number.to_string()));
+        }
+
+        let shipped_at = Utc::now() - Duration::days(1);
+
+        Ok(TrackingInfo {
+            tracking_number: tracking_number.to_string(),
+            carrier: self.code,
+            status: ShipmentStatus::InTransit,
+            estimated_delivery: Some(Utc::now() + Duration::days(2)),
+            shipped_at: Some(shipped_at),
+            delivered_at: None,
+            events: vec![
+                TrackingEvent {
+                    timestamp: shipped_at,
+                    status: ShipmentStatus::Pickup,
+                    sub_status: TrackingSubStatus::PickedUp,
+                    location: None,
+                    description: "Colis pris en charge".to_string(),
+                    raw_status: "PCH".to_string(),
+                },
+                TrackingEvent {
+                    timestamp: Utc::now(),
+                    status: ShipmentStatus::InTransit,
+                    sub_status: TrackingSubStatus::InTransit,
+                    location: None,
+                    description: "Colis en cours d'acheminement".to_string(),
+                    raw_status: "ETI".to_string(),
+                },
+            ],
+            signature_name: None,
+        })
+    }
+
+    fn can_track(&self, tracking_number: &str) -> bool {
+        tracking_number.starts_with(TRACKING_PREFIX)
+    }
+}
+
+#[async_trait]
+impl ReturnService for MockCarrier {
+    fn supported_return_methods(&self) -> Vec<ReturnMethod> {
+        vec![ReturnMethod::PrintAtHome, ReturnMethod::QrCode, ReturnMethod::Labelless]
+    }
+
+    async fn create_return(
+        &self,
+        parcel: &Parcel,
+        original_tracking_number: &str,
+        request: &ReturnRequest
+    ) -> Result<ReturnShipment, DeliveryError> {
+        self.create_return_blocking(parcel, original_tracking_number, request)
+    }
+
+    fn create_return_blocking(
+        &self,
+        parcel: &Parcel,
+        original_tracking_number: &str,
+        request: &ReturnRequest
+    ) -> Result<ReturnShipment, DeliveryError> {
+        let tracking_number = Self::tracking_number(parcel);
+        let rma = request.rma.clone().unwrap_or_default();
+        // Code de dépôt : les 8 derniers chiffres du numéro de suivi
+        let code = tracking_number[tracking_number.len() - 8..].to_string();
+
+        let (label, qr_code, return_code) = match request.method {
+            ReturnMethod::PrintAtHome => {
+                let rate = self.rate(parcel, "Retour", "RET", 4.90, 0.50, 3);
+                (Some(render_label(parcel, &rate, &tracking_number, &request.label_options)?), None, None)
+            }
+            ReturnMethod::QrCode => {
+                let qr_code = render_qr_code(&format!("{}|{}|{}", tracking_number, rma, code))?;
+                (None, Some(qr_code), Some(code))
+            }
+            ReturnMethod::Labelless => (None, None, Some(code)),
+        };
+
+        Ok(ReturnShipment {
+            rma,
+            original_tracking_number: original_tracking_number.to_string(),
+            carrier: self.code,
+            method: request.method,
+            tracking_number,
+            label,
+            qr_code,
+            return_code,
+            reason: request.reason.clone(),
+            created_at: Utc::now(),
+            expires_at: Some(Utc::now() + Duration::days(RETURN_VALIDITY_DAYS)),
+        })
+    }
+}
+
+#[async_trait]
+impl PickupScheduler for MockCarrier {
+    async fn schedule_pickup(&self, request: &PickupRequest) -> Result<Pickup, DeliveryError> {
+        self.schedule_pickup_blocking(request)
+    }
+
+    fn schedule_pickup_blocking(&self, request: &PickupRequest) -> Result<Pickup, DeliveryError> {
+        let id = uuid::Uuid::new_v4().simple().to_string().to_uppercase();
+        let pickup = Pickup {
+            confirmation_number: format!("PU{}", &id[..10]),
+            carrier: self.code,
+            status: PickupStatus::Scheduled,
+            request: request.clone(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+
+        let mut pickups = self.pickups.lock().unwrap_or_else(PoisonError::into_inner);
+        pickups.insert(pickup.confirmation_number.clone(), pickup.clone());
+        Ok(pickup)
+    }
+
+    async fn modify_pickup(&self, confirmation_number: &str, request: &PickupRequest) -> Result<Pickup, DeliveryError> {
+        self.modify_pickup_blocking(confirmation_number, request)
+    }
+
+    fn modify_pickup_blocking(&self, confirmation_number: &str, request: &PickupRequest) -> Result<Pickup, DeliveryError> {
+        let mut pickups = self.pickups.lock().unwrap_or_else(PoisonError::into_inner);
+        let pickup = pickups.get_mut(confirmation_number)
+            .filter(|pickup| pickup.status == PickupStatus::Scheduled)
+            .ok_or_else(|| DeliveryError::ApiError(format!("Enlèvement {} introuvable ou annulé", confirmation_number)))?;
+
+        pickup.request = request.clone();
+        pickup.updated_at = Utc::now();
+        Ok(pickup.clone())
+    }
+
+    async fn cancel_pickup(&self, confirmation_number: &str) -> Result<(), DeliveryError> {
+        self.cancel_pickup_blocking(confirmation_number)
+    }
+
+    fn cancel_pickup_blocking(&self, confirmation_number: &str) -> Result<(), DeliveryError> {
+        let mut pickups = self.pickups.lock().unwrap_or_else(PoisonError::into_inner);
+        let pickup = pickups.get_mut(confirmation_number)
+            .filter(|pickup| pickup.status == PickupStatus::Scheduled)
+            .ok_or_else(|| DeliveryError::ApiError(format!("Enlèvement {} introuvable ou annulé", confirmation_number)))?;
+
+        pickup.status = PickupStatus::Cancelled;
+        pickup.updated_at = Utc::now();
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ManifestSubmitter for MockCarrier {
+    async fn submit_manifest(&self, manifest: &Manifest) -> Result<String, DeliveryError> {
+        self.submit_manifest_blocking(manifest)
+    }
+
+    fn submit_manifest_blocking(&self, manifest: &Manifest) -> Result<String, DeliveryError> {
+        if !self.available {
+            return Err(DeliveryError::ConnectionError(format!("Clôture {} impossible: API indisponible", manifest.id)));
+        }
+
+        Ok(format!("CLO-{}", manifest.id))
+    }
+}
+
+impl DataNormalizer for MockCarrier {
+    fn normalize_status_code(&self, carrier_status: &str) -> ShipmentStatus {
+        status_mapping::resolve_status(self.code, carrier_status)
+    }
+
+    fn normalize_address(&self, address: &mut Address) -> Result<(), DeliveryError> {
+        for change in self.address_normalizer().normalize_in_place(address) {
+            log::debug!("Adresse normalisée pour {}: {}", self.code, change);
+        }
+        Ok(())
+    }
+
+    fn address_normalizer(&self) -> AddressNormalizer {
+        AddressNormalizer::for_carrier(self.code)
+    }
+
+    fn validate_address(&self, address: &Address) -> Result<(), DeliveryError> {
+        crate::utils::validation::validate_address(address).into_result().map(|_| ())
+    }
+}
+
+#[async_trait]
+impl ShippingCarrier for MockCarrier {
+    fn carrier_code(&self) -> CarrierCode {
+        self.code
+    }
+
+    fn carrier_name(&self) -> String {
+        format!("{} (fictif)", self.code)
+    }
+
+    async fn is_available(&self) -> bool {
+        self.available
+    }
+
+    fn is_available_blocking(&self) -> bool {
+        self.available
+    }
+
+    fn return_service(&self) -> Option<&dyn ReturnService> {
+        Some(self)
+    }
+
+    fn pickup_scheduler(&self) -> Option<&dyn PickupScheduler> {
+        Some(self)
+    }
+
+    fn manifest_submitter(&self) -> Option<&dyn ManifestSubmitter> {
+        Some(self)
+    }
+
+    fn account_number(&self) -> Option<String> {
+        self.account.clone()
+    }
+}
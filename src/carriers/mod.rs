@@ -17,3 +17,7 @@ pub mod ups;
 /// Module pour DHL
 #[cfg(feature = "dhl")]
 pub mod dhl;
+
+/// Transporteur fictif pour les tests et le développement
+#[cfg(feature = "mock")]
+pub mod mock;
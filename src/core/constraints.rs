@@ -0,0 +1,360 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::{Address, CarrierCode, Parcel};
+
+/// Code machine d'une violation de contrainte transporteur
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConstraintCode {
+    WeightExceeded,
+    LengthExceeded,
+    GirthExceeded,
+    TotalDimensionsExceeded,
+    BelowMinimumDimensions,
+    InsuranceExceeded,
+    OriginNotServed,
+    DestinationNotServed,
+    PoBoxNotAllowed,
+}
+
+impl fmt::Display for ConstraintCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let code = match self {
+            ConstraintCode::WeightExceeded => "weight_exceeded",
+            ConstraintCode::LengthExceeded => "length_exceeded",
+            ConstraintCode::GirthExceeded => "girth_exceeded",
+            ConstraintCode::TotalDimensionsExceeded => "total_dimensions_exceeded",
+            ConstraintCode::BelowMinimumDimensions => "below_minimum_dimensions",
+            ConstraintCode::InsuranceExceeded => "insurance_exceeded",
+            ConstraintCode::OriginNotServed => "origin_not_served",
+            ConstraintCode::DestinationNotServed => "destination_not_served",
+            ConstraintCode::PoBoxNotAllowed => "po_box_not_allowed",
+        };
+        write!(f, "{}", code)
+    }
+}
+
+/// Violation d'une contrainte transporteur par un colis
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConstraintViolation {
+    pub carrier: CarrierCode,
+    pub service_code: Option<String>,
+    pub code: ConstraintCode,
+    /// Champ du colis concerné (ex: `weight`, `recipient.country`)
+    pub field: String,
+    pub limit: Option<f64>,
+    pub actual: Option<f64>,
+    pub message: String,
+}
+
+impl fmt::Display for ConstraintViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} [{}]: {}", self.carrier, self.code, self.message)
+    }
+}
+
+/// Contraintes déclarées par un transporteur pour un service (ou pour tous ses services)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ServiceConstraints {
+    pub carrier: CarrierCode,
+    /// Code de service concerné (None = contraintes communes à tous les services)
+    pub service_code: Option<String>,
+    /// Poids réel maximal en kg
+    pub max_weight: Option<f64>,
+    /// Plus grand côté maximal en cm
+    pub max_length: Option<f64>,
+    /// Longueur + circonférence (2 × (largeur + hauteur)) maximale en cm
+    pub max_length_plus_girth: Option<f64>,
+    /// Somme des trois côtés maximale en cm
+    pub max_total_dimensions: Option<f64>,
+    /// Dimensions minimales (longueur, largeur, hauteur) en cm
+    pub min_dimensions: Option<(f64, f64, f64)>,
+    /// Valeur assurable maximale, dans la devise du colis
+    pub max_insurance_value: Option<f64>,
+    /// Pays d'origine desservis (None = tous)
+    pub origin_countries: Option<Vec<String>>,
+    /// Pays de destination desservis (None = tous)
+    pub destination_countries: Option<Vec<String>>,
+    /// Indique si la livraison en boîte postale est acceptée
+    pub allow_po_box: bool,
+}
+
+impl ServiceConstraints {
+    /// Crée des contraintes vides pour un transporteur
+    pub fn new(carrier: CarrierCode) -> Self {
+        Self {
+            carrier,
+            service_code: None,
+            max_weight: None,
+            max_length: None,
+            max_length_plus_girth: None,
+            max_total_dimensions: None,
+            min_dimensions: None,
+            max_insurance_value: None,
+            origin_countries: None,
+            destination_countries: None,
+            allow_po_box: true,
+        }
+    }
+
+    /// Restreint les contraintes à un service
+    pub fn for_service(mut self, service_code: &str) -> Self {
+        self.service_code = Some(service_code.to_string());
+        self
+    }
+
+    /// Définit le poids réel maximal en kg
+    pub fn with_max_weight(mut self, weight: f64) -> Self {
+        self.max_weight = Some(weight);
+        self
+    }
+
+    /// Définit le plus grand côté maximal en cm
+    pub fn with_max_length(mut self, length: f64) -> Self {
+        self.max_length = Some(length);
+        self
+    }
+
+    /// Définit la longueur + circonférence maximale en cm
+    pub fn with_max_length_plus_girth(mut self, value: f64) -> Self {
+        self.max_length_plus_girth = Some(value);
+        self
+    }
+
+    /// Définit la somme maximale des trois côtés en cm
+    pub fn with_max_total_dimensions(mut self, value: f64) -> Self {
+        self.max_total_dimensions = Some(value);
+        self
+    }
+
+    /// Définit les dimensions minimales en cm
+    pub fn with_min_dimensions(mut self, length: f64, width: f64, height: f64) -> Self {
+        self.min_dimensions = Some((length, width, height));
+        self
+    }
+
+    /// Définit la valeur assurable maximale
+    pub fn with_max_insurance(mut self, value: f64) -> Self {
+        self.max_insurance_value = Some(value);
+        self
+    }
+
+    /// Restreint les pays d'origine desservis
+    pub fn with_origin_countries(mut self, countries: &[&str]) -> Self {
+        self.origin_countries = Some(countries.iter().map(|c| c.to_string()).collect());
+        self
+    }
+
+    /// Restreint les pays de destination desservis
+    pub fn with_destination_countries(mut self, countries: &[&str]) -> Self {
+        self.destination_countries = Some(countries.iter().map(|c| c.to_string()).collect());
+        self
+    }
+
+    /// Interdit la livraison en boîte postale
+    pub fn without_po_box(mut self) -> Self {
+        self.allow_po_box = false;
+        self
+    }
+
+    /// Vérifie un colis et retourne toutes les violations constatées
+    pub fn check(&self, parcel: &Parcel) -> Vec<ConstraintViolation> {
+        let mut violations = Vec::new();
+        let mut violation = |code, field: &str, limit: Option<f64>, actual: Option<f64>, message: String| {
+            violations.push(ConstraintViolation {
+                carrier: self.carrier,
+                service_code: self.service_code.clone(),
+                code,
+                field: field.to_string(),
+                limit,
+                actual,
+                message,
+            });
+        };
+
+        let mut sides = [parcel.length, parcel.width, parcel.height];
+        sides.sort_by(|a, b| b.total_cmp(a));
+        let [length, width, height] = sides;
+
+        // Limite de manutention : porte sur le poids réel, pas sur le poids volumétrique
+        if let Some(max) = self.max_weight && parcel.weight > max {
+            violation(
+                ConstraintCode::WeightExceeded, "weight", Some(max), Some(parcel.weight),
+                format!("Le poids réel ({:.2} kg) dépasse le maximum de {} kg", parcel.weight, max),
+            );
+        }
+
+        if let Some(max) = self.max_length && length > max {
+            violation(
+                ConstraintCode::LengthExceeded, "length", Some(max), Some(length),
+                format!("Le plus grand côté ({} cm) dépasse le maximum de {} cm", length, max),
+            );
+        }
+
+        if let Some(max) = self.max_length_plus_girth {
+            let value = length + 2.0 * (width + height);
+            if value > max {
+                violation(
+                    ConstraintCode::GirthExceeded, "dimensions", Some(max), Some(value),
+                    format!("La longueur + circonférence ({} cm) dépasse le maximum de {} cm", value, max),
+                );
+            }
+        }
+
+        if let Some(max) = self.max_total_dimensions {
+            let value = length + width + height;
+            if value > max {
+                violation(
+                    ConstraintCode::TotalDimensionsExceeded, "dimensions", Some(max), Some(value),
+                    format!("La somme des côtés ({} cm) dépasse le maximum de {} cm", value, max),
+                );
+            }
+        }
+
+        if let Some((min_length, min_width, min_height)) = self.min_dimensions
+            && (length < min_length || width < min_width || height < min_height) {
+            violation(
+                ConstraintCode::BelowMinimumDimensions, "dimensions", None, None,
+                format!("Les dimensions minimales sont {} × {} × {} cm", min_length, min_width, min_height),
+            );
+        }
+
+        if let (Some(max), Some(value)) = (self.max_insurance_value, parcel.insurance_value) && value > max {
+            violation(
+                ConstraintCode::InsuranceExceeded, "insurance_value", Some(max), Some(value),
+                format!("La valeur assurée ({}) dépasse le maximum de {}", value, max),
+            );
+        }
+
+        if !country_allowed(&self.origin_countries, &parcel.sender.country) {
+            violation(
+                ConstraintCode::OriginNotServed, "sender.country", None, None,
+                format!("Le pays d'origine {} n'est pas desservi", parcel.sender.country),
+            );
+        }
+
+        if !country_allowed(&self.destination_countries, &parcel.recipient.country) {
+            violation(
+                ConstraintCode::DestinationNotServed, "recipient.country", None, None,
+                format!("Le pays de destination {} n'est pas desservi", parcel.recipient.country),
+            );
+        }
+
+        if !self.allow_po_box && is_po_box(&parcel.recipient) {
+            violation(
+                ConstraintCode::PoBoxNotAllowed, "recipient.street1", None, None,
+                "La livraison en boîte postale n'est pas acceptée".to_string(),
+            );
+        }
+
+        violations
+    }
+}
+
+/// Vérifie qu'un pays fait partie de la liste autorisée (None = tous les pays)
+fn country_allowed(allowed: &Option<Vec<String>>, country: &str) -> bool {
+    match allowed {
+        Some(countries) => countries.iter().any(|c| c.eq_ignore_ascii_case(country.trim())),
+        None => true,
+    }
+}
+
+/// Détecte une adresse de boîte postale (BP, PO Box, Postfach...)
+pub fn is_po_box(address: &Address) -> bool {
+    const MARKERS: [&str; 8] = [
+        "PO BOX", "POBOX", "POST OFFICE BOX", "BP ", "BOITE POSTALE",
+        "POSTFACH", "APARTADO", "CASELLA POSTALE",
+    ];
+
+    let lines = std::iter::once(&address.street1).chain(address.street2.as_ref());
+
+    lines.into_iter().any(|line| {
+        let normalized: String = line.to_uppercase().replace(['.', ','], "").replace("BOÎTE", "BOITE");
+        let normalized = format!("{} ", normalized.trim());
+        MARKERS.iter().any(|marker| normalized.starts_with(marker) || normalized.contains(&format!(" {}", marker)))
+    })
+}
+
+/// Contraintes publiques par défaut des transporteurs supportés
+pub fn default_constraints(carrier: CarrierCode) -> Vec<ServiceConstraints> {
+    match carrier {
+        CarrierCode::Colissimo => vec![
+            ServiceConstraints::new(carrier)
+                .with_max_weight(30.0)
+                .with_max_length(100.0)
+                .with_max_total_dimensions(150.0)
+                .with_min_dimensions(16.0, 11.0, 1.0)
+                .with_max_insurance(5000.0)
+                .with_origin_countries(&["FR", "MC", "AD"]),
+        ],
+        CarrierCode::Chronopost => vec![
+            ServiceConstraints::new(carrier)
+                .with_max_weight(30.0)
+                .with_max_length(150.0)
+                .with_max_length_plus_girth(300.0)
+                .with_max_insurance(5000.0)
+                .with_origin_countries(&["FR", "MC", "AD"])
+                .without_po_box(),
+        ],
+        CarrierCode::FedEx => vec![
+            ServiceConstraints::new(carrier)
+                .with_max_weight(68.0)
+                .with_max_length(274.0)
+                .with_max_length_plus_girth(330.0)
+                .with_max_insurance(50000.0)
+                .without_po_box(),
+        ],
+        CarrierCode::UPS => vec![
+            ServiceConstraints::new(carrier)
+                .with_max_weight(70.0)
+                .with_max_length(274.0)
+                .with_max_length_plus_girth(400.0)
+                .with_max_insurance(50000.0)
+                .without_po_box(),
+        ],
+        CarrierCode::DHL => vec![
+            ServiceConstraints::new(carrier)
+                .with_max_weight(70.0)
+                .with_max_length(120.0)
+                .with_max_insurance(25000.0)
+                .without_po_box(),
+        ],
+    }
+}
+
+/// Vérifie un colis contre les contraintes d'un transporteur
+///
+/// Les contraintes communes s'appliquent toujours. Si un service est précisé, ses contraintes
+/// propres s'appliquent aussi ; sinon le colis doit satisfaire au moins un des services déclarés.
+pub fn check_parcel(constraints: &[ServiceConstraints], parcel: &Parcel, service_code: Option<&str>) -> Vec<ConstraintViolation> {
+    let (common, services): (Vec<_>, Vec<_>) = constraints.iter()
+        .partition(|constraint| constraint.service_code.is_none());
+
+    let mut violations: Vec<ConstraintViolation> = common.iter()
+        .flat_map(|constraint| constraint.check(parcel))
+        .collect();
+
+    match service_code {
+        Some(code) => {
+            violations.extend(
+                services.iter()
+                    .filter(|constraint| constraint.service_code.as_deref() == Some(code))
+                    .flat_map(|constraint| constraint.check(parcel))
+            );
+        }
+        None if !services.is_empty() => {
+            let per_service: Vec<Vec<ConstraintViolation>> = services.iter()
+                .map(|constraint| constraint.check(parcel))
+                .collect();
+
+            if per_service.iter().all(|service_violations| !service_violations.is_empty()) {
+                violations.extend(per_service.into_iter().flatten());
+            }
+        }
+        None => {}
+    }
+
+    violations
+}
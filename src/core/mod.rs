@@ -1,15 +1,43 @@
 pub mod traits;
+pub mod constraints;
 
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use uuid::Uuid;
+
 use crate::errors::DeliveryError;
-use crate::models::{CarrierCode, Parcel, Rate, ShippingLabel, TrackingInfo};
-use traits::ShippingCarrier;
+use crate::eta::{DeliveryEstimate, EtaEngine};
+use crate::i18n::{Catalog, Locale, Localize};
+use crate::lifecycle::LifecycleState;
+use crate::manifest::{Manifest, ManifestCollector, ManifestEntry};
+use crate::store::{MemoryStore, ShipmentQuery, ShipmentRecord, ShipmentStore};
+use constraints::ConstraintViolation;
+use crate::models::{
+    Address, AttemptOutcome, CarrierCode, LabelBundle, LabelOptions, Parcel, Pickup, PickupRequest, Rate, ReturnRequest, ReturnShipment,
+    ShippingLabel, TrackingAttempt, TrackingInfo, TrackingLookup,
+};
+use crate::utils::address::NormalizedAddress;
+use crate::utils::tracking::{detect_carriers, normalize_tracking_number};
+use crate::utils::validation::{validate_pickup_request, validate_rate_weight};
+use crate::utils::weight::VolumetricRules;
+use crate::verification::{AddressVerification, AddressVerifier};
+use traits::{PickupScheduler, ReturnService, ShippingCarrier};
+
+/// Nombre d'essais d'enregistrement d'un envoi modifié en même temps par une autre opération
+const MAX_WRITE_ATTEMPTS: usize = 3;
 
 /// Le gestionnaire principal pour interagir avec différents transporteurs
 pub struct ShippingManager {
     carriers: HashMap<CarrierCode, Arc<dyn ShippingCarrier>>,
+    carrier_priority: Vec<CarrierCode>,  // Ordre d'interrogation des transporteurs
+    manifests: ManifestCollector,  // Envois en attente de clôture
+    store: Arc<dyn ShipmentStore>,
+    eta: EtaEngine,  // Estimation des dates de livraison
+    volumetric_rules: VolumetricRules,  // Diviseurs volumétriques du contrat (publics par défaut)
+    locale: Locale,  // Langue des libellés et messages
+    catalog: Option<Catalog>,  // Traductions propres au gestionnaire (sinon les traductions actives)
+    address_verifier: Option<Arc<dyn AddressVerifier>>,  // Vérification des adresses (référentiel postal)
 }
 
 impl ShippingManager {
@@ -17,6 +45,100 @@ impl ShippingManager {
     pub fn new() -> Self {
         Self {
             carriers: HashMap::new(),
+            carrier_priority: Vec::new(),
+            manifests: ManifestCollector::new(),
+            store: Arc::new(MemoryStore::new()),
+            eta: EtaEngine::new(),
+            volumetric_rules: VolumetricRules::default(),
+            locale: Locale::default(),
+            catalog: None,
+            address_verifier: None,
+        }
+    }
+
+    /// Remplace le stockage des envois (en mémoire par défaut)
+    ///
+    /// Les envois étiquetés et pas encore remis sur un bordereau y sont relus pour
+    /// la prochaine clôture.
+    pub fn set_store(&mut self, store: Box<dyn ShipmentStore>) -> &mut Self {
+        self.store = Arc::from(store);
+        if let Err(e) = self.reload_manifests() {
+            log::error!("Relecture des envois en attente de clôture impossible: {}", e);
+        }
+        self
+    }
+
+    /// Relit dans le stockage les envois en attente de clôture
+    ///
+    /// Les envois étiquetés, hors retours, sont remis en attente pour leur
+    /// transporteur et leur compte ; renvoie le nombre d'envois en attente.
+    pub fn reload_manifests(&self) -> Result<usize, DeliveryError> {
+        let labelled = self.store.find_shipments(&ShipmentQuery::new().with_state(LifecycleState::Labelled))?;
+
+        let mut entries = Vec::new();
+        for record in labelled.into_iter().filter(|record| record.return_of.is_none()) {
+            let label_created_at = self.store.load_label(&record.tracking_number)?
+                .map_or(record.updated_at, |label| label.created_at);
+            entries.push(ManifestEntry::from_record(&record, label_created_at));
+        }
+
+        let count = entries.len();
+        self.manifests.reload(entries);
+        Ok(count)
+    }
+
+    /// Stockage des envois, étiquettes et relevés de suivi
+    pub fn store(&self) -> &dyn ShipmentStore {
+        self.store.as_ref()
+    }
+
+    /// Remplace le moteur d'estimation des dates de livraison
+    pub fn set_eta_engine(&mut self, engine: EtaEngine) -> &mut Self {
+        self.eta = engine;
+        self
+    }
+
+    /// Moteur d'estimation des dates de livraison
+    pub fn eta_engine(&self) -> &EtaEngine {
+        &self.eta
+    }
+
+    /// Remplace les diviseurs volumétriques, pour un contrat aux diviseurs négociés
+    ///
+    /// Ils servent à vérifier le poids facturable des tarifs avant la génération
+    /// d'une étiquette : ce doivent être ceux du `TariffRateProvider` utilisé.
+    pub fn set_volumetric_rules(&mut self, rules: VolumetricRules) -> &mut Self {
+        self.volumetric_rules = rules;
+        self
+    }
+
+    /// Diviseurs volumétriques du contrat
+    pub fn volumetric_rules(&self) -> &VolumetricRules {
+        &self.volumetric_rules
+    }
+
+    /// Change la langue des libellés et messages (le français par défaut)
+    pub fn set_locale(&mut self, locale: Locale) -> &mut Self {
+        self.locale = locale;
+        self
+    }
+
+    /// Langue des libellés et messages
+    pub fn locale(&self) -> Locale {
+        self.locale
+    }
+
+    /// Utilise des traductions propres à ce gestionnaire plutôt que les traductions actives
+    pub fn set_catalog(&mut self, catalog: Catalog) -> &mut Self {
+        self.catalog = Some(catalog);
+        self
+    }
+
+    /// Texte d'un statut, d'une erreur ou d'un rapport de validation dans la langue du gestionnaire
+    pub fn localize(&self, value: &impl Localize) -> String {
+        match &self.catalog {
+            Some(catalog) => value.localize_with(catalog, self.locale),
+            None => value.localize(self.locale),
         }
     }
 
@@ -24,29 +146,129 @@ impl ShippingManager {
     pub fn add_carrier(&mut self, carrier: Box<dyn ShippingCarrier>) -> &mut Self {
         let code = carrier.carrier_code();
         self.carriers.insert(code, Arc::from(carrier));
+        if !self.carrier_priority.contains(&code) {
+            self.carrier_priority.push(code);
+        }
         self
     }
 
+    /// Fixe l'ordre de priorité des transporteurs (par défaut, l'ordre d'ajout)
+    ///
+    /// Les transporteurs non cités suivent, dans leur ordre actuel. Cet ordre
+    /// départage les transporteurs interrogés pour le suivi d'un numéro ambigu.
+    pub fn set_carrier_priority(&mut self, priority: &[CarrierCode]) -> &mut Self {
+        let mut order: Vec<CarrierCode> = Vec::new();
+        for code in priority.iter().chain(self.carrier_priority.iter()) {
+            if !order.contains(code) {
+                order.push(*code);
+            }
+        }
+        self.carrier_priority = order;
+        self
+    }
+
+    /// Transporteurs enregistrés, par ordre de priorité
+    fn ordered_carriers(&self) -> Vec<CarrierCode> {
+        self.carrier_priority.iter().copied().filter(|code| self.carriers.contains_key(code)).collect()
+    }
+
+    /// Rang d'un transporteur dans l'ordre de priorité
+    fn priority_of(&self, code: CarrierCode) -> usize {
+        self.carrier_priority.iter().position(|candidate| *candidate == code).unwrap_or(usize::MAX)
+    }
+
     /// Obtient un transporteur par son code
     pub fn get_carrier(&self, code: &CarrierCode) -> Option<Arc<dyn ShippingCarrier>> {
         self.carriers.get(code).cloned()
     }
 
-    /// Liste tous les transporteurs disponibles
+    /// Liste tous les transporteurs disponibles, par ordre de priorité
     pub fn list_carriers(&self) -> Vec<(CarrierCode, String)> {
-        self.carriers
-            .iter()
-            .map(|(code, carrier)| (*code, carrier.carrier_name()))
+        self.ordered_carriers()
+            .into_iter()
+            .map(|code| (code, self.carriers[&code].carrier_name()))
+            .collect()
+    }
+
+    /// Vérifie un colis contre les contraintes déclarées par un transporteur
+    /// et retourne toutes les violations constatées
+    pub fn check_constraints(
+        &self,
+        carrier_code: &CarrierCode,
+        parcel: &Parcel,
+        service_code: Option<&str>
+    ) -> Result<Vec<ConstraintViolation>, DeliveryError> {
+        let carrier = self.get_carrier(carrier_code)
+            .ok_or_else(|| DeliveryError::UnknownCarrier(format!("{:?}", carrier_code)))?;
+
+        Ok(constraints::check_parcel(&carrier.constraints(), parcel, service_code))
+    }
+
+    /// Normalise une adresse selon les règles d'un transporteur et retourne
+    /// les modifications apportées
+    pub fn normalize_address(&self, carrier_code: &CarrierCode, address: &Address) -> Result<NormalizedAddress, DeliveryError> {
+        let carrier = self.get_carrier(carrier_code)
+            .ok_or_else(|| DeliveryError::UnknownCarrier(format!("{:?}", carrier_code)))?;
+
+        Ok(carrier.address_normalizer().normalize(address))
+    }
+
+    /// Définit le service de vérification des adresses
+    pub fn set_address_verifier(&mut self, verifier: Box<dyn AddressVerifier>) -> &mut Self {
+        self.address_verifier = Some(Arc::from(verifier));
+        self
+    }
+
+    /// Vérifie la cohérence d'une adresse (code postal et localité) et propose des corrections
+    ///
+    /// Retourne `VerificationStatus::Unsupported` si le service ne couvre pas le pays.
+    pub async fn verify_address(&self, address: &Address) -> Result<AddressVerification, DeliveryError> {
+        let verifier = self.address_verifier()?;
+        if !verifier.supports(&address.country) {
+            return Ok(AddressVerification::unsupported());
+        }
+        verifier.verify(address).await
+    }
+
+    /// Version synchrone de verify_address
+    pub fn verify_address_blocking(&self, address: &Address) -> Result<AddressVerification, DeliveryError> {
+        let verifier = self.address_verifier()?;
+        if !verifier.supports(&address.country) {
+            return Ok(AddressVerification::unsupported());
+        }
+        verifier.verify_blocking(address)
+    }
+
+    fn address_verifier(&self) -> Result<&Arc<dyn AddressVerifier>, DeliveryError> {
+        self.address_verifier.as_ref().ok_or_else(|| {
+            DeliveryError::UnsupportedService("Aucun service de vérification d'adresse configuré".to_string())
+        })
+    }
+
+    /// Liste les transporteurs auxquels le colis peut être confié, par ordre de priorité
+    pub fn eligible_carriers(&self, parcel: &Parcel) -> Vec<CarrierCode> {
+        self.ordered_carriers()
+            .into_iter()
+            .filter(|code| {
+                self.carriers.get(code)
+                    .is_some_and(|carrier| constraints::check_parcel(&carrier.constraints(), parcel, None).is_empty())
+            })
             .collect()
     }
 
     /// Obtient les tarifs de tous les transporteurs pour un colis donné
+    ///
+    /// Les transporteurs dont les contraintes excluent le colis ne sont pas interrogés
+    /// et retournent `DeliveryError::IneligibleParcel`.
     pub async fn get_all_rates(&self, parcel: &Parcel) -> HashMap<CarrierCode, Result<Vec<Rate>, DeliveryError>> {
         let mut results = HashMap::new();
 
         for (code, carrier) in &self.carriers {
-            let result = carrier.get_rates(parcel).await;
-            results.insert(*code, result);
+            let result = match preflight(carrier.as_ref(), parcel, None) {
+                Ok(()) => carrier.get_rates(parcel).await,
+                Err(e) => Err(e),
+            };
+            results.insert(*code, result.map(|rates| self.with_delivery_dates(rates, parcel)));
         }
 
         results
@@ -57,7 +279,8 @@ impl ShippingManager {
         let carrier = self.get_carrier(carrier_code)
             .ok_or_else(|| DeliveryError::UnknownCarrier(format!("{:?}", carrier_code)))?;
 
-        carrier.get_rates(parcel).await
+        preflight(carrier.as_ref(), parcel, None)?;
+        Ok(self.with_delivery_dates(carrier.get_rates(parcel).await?, parcel))
     }
 
     /// Version synchrone de get_rates
@@ -65,20 +288,45 @@ impl ShippingManager {
         let carrier = self.get_carrier(carrier_code)
             .ok_or_else(|| DeliveryError::UnknownCarrier(format!("{:?}", carrier_code)))?;
 
-        carrier.get_rates_blocking(parcel)
+        preflight(carrier.as_ref(), parcel, None)?;
+        Ok(self.with_delivery_dates(carrier.get_rates_blocking(parcel)?, parcel))
+    }
+
+    /// Renseigne la date de livraison estimée des tarifs qui n'en ont pas
+    fn with_delivery_dates(&self, mut rates: Vec<Rate>, parcel: &Parcel) -> Vec<Rate> {
+        let now = chrono::Utc::now();
+        for rate in &mut rates {
+            self.eta.fill_rate(rate, parcel, now);
+        }
+        rates
     }
 
     /// Génère une étiquette d'expédition pour un colis avec un tarif sélectionné
+    ///
+    /// L'envoi et son étiquette sont enregistrés dans le stockage
+    /// et ajoutés au prochain bordereau du transporteur.
+    ///
+    /// Un tarif dont le poids facturable est inférieur à celui du colis (tarif
+    /// obtenu avant une modification des dimensions, par exemple) est refusé
+    /// avec `DeliveryError::Validation`. Un colis dont l'envoi est déjà livré,
+    /// retourné ou annulé est refusé avec `DeliveryError::InvalidTransition`,
+    /// avant tout appel au transporteur.
     pub async fn generate_label(
         &self,
         carrier_code: &CarrierCode,
         parcel: &Parcel,
-        rate: &Rate
+        rate: &Rate,
+        options: &LabelOptions
     ) -> Result<ShippingLabel, DeliveryError> {
         let carrier = self.get_carrier(carrier_code)
             .ok_or_else(|| DeliveryError::UnknownCarrier(format!("{:?}", carrier_code)))?;
 
-        carrier.generate_label(parcel, rate).await
+        preflight(carrier.as_ref(), parcel, Some(&rate.service_code))?;
+        validate_rate_weight(parcel, rate, &self.volumetric_rules).into_result()?;
+        self.check_relabel(*carrier_code, parcel)?;
+        let label = negotiate_label_format(carrier.generate_label(parcel, rate, options).await?, options)?;
+        self.record_label(carrier.as_ref(), &label, parcel, rate);
+        Ok(label)
     }
 
     /// Version synchrone de generate_label
@@ -86,37 +334,568 @@ impl ShippingManager {
         &self,
         carrier_code: &CarrierCode,
         parcel: &Parcel,
-        rate: &Rate
+        rate: &Rate,
+        options: &LabelOptions
     ) -> Result<ShippingLabel, DeliveryError> {
         let carrier = self.get_carrier(carrier_code)
             .ok_or_else(|| DeliveryError::UnknownCarrier(format!("{:?}", carrier_code)))?;
 
-        carrier.generate_label_blocking(parcel, rate)
+        preflight(carrier.as_ref(), parcel, Some(&rate.service_code))?;
+        validate_rate_weight(parcel, rate, &self.volumetric_rules).into_result()?;
+        self.check_relabel(*carrier_code, parcel)?;
+        let label = negotiate_label_format(carrier.generate_label_blocking(parcel, rate, options)?, options)?;
+        self.record_label(carrier.as_ref(), &label, parcel, rate);
+        Ok(label)
     }
 
     /// Suit un colis à partir de son numéro de suivi
     /// Essaie de détecter automatiquement le transporteur approprié
+    ///
+    /// Les transporteurs sont interrogés dans l'ordre de `lookup_tracking` ; le
+    /// relevé est enregistré dans le stockage et met à jour le statut de l'envoi.
     pub async fn track_parcel(&self, tracking_number: &str) -> Result<TrackingInfo, DeliveryError> {
-        // Cherche le transporteur capable de suivre ce numéro
-        for carrier in self.carriers.values() {
-            if carrier.can_track(tracking_number) {
-                return carrier.track_parcel(tracking_number).await;
+        self.lookup_tracking(tracking_number, None).await.into_result()
+    }
+
+    /// Version synchrone de track_parcel
+    pub fn track_parcel_blocking(&self, tracking_number: &str) -> Result<TrackingInfo, DeliveryError> {
+        self.lookup_tracking_blocking(tracking_number, None).into_result()
+    }
+
+    /// Suit un colis en indiquant les transporteurs interrogés
+    ///
+    /// Le transporteur suggéré est interrogé en premier, puis ceux détectés d'après
+    /// le format du numéro (par probabilité, puis par priorité), puis ceux qui déclarent
+    /// savoir le suivre (par priorité). On passe au suivant quand le numéro est inconnu
+    /// du transporteur ; toute autre erreur interrompt la recherche.
+    pub async fn lookup_tracking(&self, tracking_number: &str, carrier_hint: Option<CarrierCode>) -> TrackingLookup {
+        let mut lookup = new_lookup(tracking_number);
+
+        match self.tracking_carriers(&lookup.tracking_number, carrier_hint) {
+            Ok(carriers) => {
+                for carrier in carriers {
+                    let result = carrier.track_parcel(&lookup.tracking_number).await;
+                    if self.settle_attempt(&mut lookup, carrier.carrier_code(), result) {
+                        break;
+                    }
+                }
             }
+            Err(e) => lookup.result = Err(e),
         }
 
-        Err(DeliveryError::UnsupportedTrackingNumber(tracking_number.to_string()))
+        lookup
     }
 
-    /// Version synchrone de track_parcel
-    pub fn track_parcel_blocking(&self, tracking_number: &str) -> Result<TrackingInfo, DeliveryError> {
-        // Cherche le transporteur capable de suivre ce numéro
-        for carrier in self.carriers.values() {
-            if carrier.can_track(tracking_number) {
-                return carrier.track_parcel_blocking(tracking_number);
+    /// Version synchrone de lookup_tracking
+    pub fn lookup_tracking_blocking(&self, tracking_number: &str, carrier_hint: Option<CarrierCode>) -> TrackingLookup {
+        let mut lookup = new_lookup(tracking_number);
+
+        match self.tracking_carriers(&lookup.tracking_number, carrier_hint) {
+            Ok(carriers) => {
+                for carrier in carriers {
+                    let result = carrier.track_parcel_blocking(&lookup.tracking_number);
+                    if self.settle_attempt(&mut lookup, carrier.carrier_code(), result) {
+                        break;
+                    }
+                }
+            }
+            Err(e) => lookup.result = Err(e),
+        }
+
+        lookup
+    }
+
+    /// Prend en compte la réponse d'un transporteur ; retourne `true` si la recherche est terminée
+    ///
+    /// Si aucun transporteur ne connaît le numéro, l'erreur du premier interrogé est conservée.
+    fn settle_attempt(
+        &self,
+        lookup: &mut TrackingLookup,
+        carrier: CarrierCode,
+        result: Result<TrackingInfo, DeliveryError>
+    ) -> bool {
+        let (outcome, done) = match result {
+            Ok(info) => {
+                self.record_tracking(&info);
+                lookup.result = Ok(info);
+                (AttemptOutcome::Found, true)
+            }
+            Err(e) if e.is_not_found() => {
+                if lookup.attempts.is_empty() {
+                    lookup.result = Err(e);
+                }
+                (AttemptOutcome::NotFound, false)
+            }
+            Err(e) => {
+                let outcome = AttemptOutcome::Failed(e.to_string());
+                lookup.result = Err(e);
+                (outcome, true)
+            }
+        };
+
+        lookup.attempts.push(TrackingAttempt { carrier, outcome });
+        done
+    }
+
+    /// Transporteurs à interroger pour un numéro de suivi, dans l'ordre
+    fn tracking_carriers(
+        &self,
+        tracking_number: &str,
+        carrier_hint: Option<CarrierCode>
+    ) -> Result<Vec<Arc<dyn ShippingCarrier>>, DeliveryError> {
+        let mut codes: Vec<CarrierCode> = Vec::new();
+
+        if let Some(hint) = carrier_hint {
+            if !self.carriers.contains_key(&hint) {
+                return Err(DeliveryError::UnknownCarrier(format!("{:?}", hint)));
+            }
+            codes.push(hint);
+        }
+
+        let mut candidates = detect_carriers(tracking_number);
+        candidates.sort_by(|a, b| {
+            b.confidence.total_cmp(&a.confidence).then_with(|| self.priority_of(a.carrier).cmp(&self.priority_of(b.carrier)))
+        });
+        for candidate in candidates {
+            if self.carriers.contains_key(&candidate.carrier) && !codes.contains(&candidate.carrier) {
+                codes.push(candidate.carrier);
+            }
+        }
+
+        for code in self.ordered_carriers() {
+            if !codes.contains(&code) && self.carriers[&code].can_track(tracking_number) {
+                codes.push(code);
+            }
+        }
+
+        Ok(codes.iter().filter_map(|code| self.get_carrier(code)).collect())
+    }
+
+    /// Crée un retour pour un envoi
+    ///
+    /// L'expéditeur et le destinataire du colis d'origine sont inversés ; un numéro
+    /// d'autorisation de retour est généré si la demande n'en fournit pas.
+    pub async fn create_return(
+        &self,
+        carrier_code: &CarrierCode,
+        original: &Parcel,
+        original_tracking_number: &str,
+        request: &ReturnRequest
+    ) -> Result<ReturnShipment, DeliveryError> {
+        let carrier = self.get_carrier(carrier_code)
+            .ok_or_else(|| DeliveryError::UnknownCarrier(format!("{:?}", carrier_code)))?;
+        let service = return_service(carrier.as_ref(), request)?;
+
+        let parcel = original.for_return();
+        preflight(carrier.as_ref(), &parcel, None)?;
+        let request = with_rma(request);
+        let shipment = service.create_return(&parcel, original_tracking_number, &request).await?;
+        self.register_return(shipment, &parcel, &request)
+    }
+
+    /// Version synchrone de create_return
+    pub fn create_return_blocking(
+        &self,
+        carrier_code: &CarrierCode,
+        original: &Parcel,
+        original_tracking_number: &str,
+        request: &ReturnRequest
+    ) -> Result<ReturnShipment, DeliveryError> {
+        let carrier = self.get_carrier(carrier_code)
+            .ok_or_else(|| DeliveryError::UnknownCarrier(format!("{:?}", carrier_code)))?;
+        let service = return_service(carrier.as_ref(), request)?;
+
+        let parcel = original.for_return();
+        preflight(carrier.as_ref(), &parcel, None)?;
+        let request = with_rma(request);
+        let shipment = service.create_return_blocking(&parcel, original_tracking_number, &request)?;
+        self.register_return(shipment, &parcel, &request)
+    }
+
+    /// Génère l'étiquette d'envoi et pré-génère le retour associé
+    pub async fn generate_label_with_return(
+        &self,
+        carrier_code: &CarrierCode,
+        parcel: &Parcel,
+        rate: &Rate,
+        options: &LabelOptions,
+        request: &ReturnRequest
+    ) -> Result<LabelBundle, DeliveryError> {
+        let outbound = self.generate_label(carrier_code, parcel, rate, options).await?;
+        let return_shipment = self.create_return(carrier_code, parcel, &outbound.tracking_number, request).await?;
+
+        Ok(LabelBundle { outbound, return_shipment })
+    }
+
+    /// Version synchrone de generate_label_with_return
+    pub fn generate_label_with_return_blocking(
+        &self,
+        carrier_code: &CarrierCode,
+        parcel: &Parcel,
+        rate: &Rate,
+        options: &LabelOptions,
+        request: &ReturnRequest
+    ) -> Result<LabelBundle, DeliveryError> {
+        let outbound = self.generate_label_blocking(carrier_code, parcel, rate, options)?;
+        let return_shipment = self.create_return_blocking(carrier_code, parcel, &outbound.tracking_number, request)?;
+
+        Ok(LabelBundle { outbound, return_shipment })
+    }
+
+    /// Liste les retours rattachés à un envoi, tels qu'enregistrés dans le stockage
+    pub fn returns_for(&self, original_tracking_number: &str) -> Result<Vec<ReturnShipment>, DeliveryError> {
+        self.find_returns(&ShipmentQuery::new().with_return_of(original_tracking_number))
+    }
+
+    /// Retrouve un retour par son numéro d'autorisation
+    pub fn find_return(&self, rma: &str) -> Result<Option<ReturnShipment>, DeliveryError> {
+        Ok(self.find_returns(&ShipmentQuery::new().with_rma(rma))?.into_iter().next())
+    }
+
+    /// Suit les retours rattachés à un envoi, par numéro de suivi du retour
+    pub async fn track_returns(&self, original_tracking_number: &str) -> Result<HashMap<String, Result<TrackingInfo, DeliveryError>>, DeliveryError> {
+        let mut results = HashMap::new();
+
+        for shipment in self.returns_for(original_tracking_number)? {
+            let result = self.track_parcel(&shipment.tracking_number).await;
+            results.insert(shipment.tracking_number, result);
+        }
+
+        Ok(results)
+    }
+
+    /// Version synchrone de track_returns
+    pub fn track_returns_blocking(&self, original_tracking_number: &str) -> Result<HashMap<String, Result<TrackingInfo, DeliveryError>>, DeliveryError> {
+        Ok(self.returns_for(original_tracking_number)?
+            .into_iter()
+            .map(|shipment| {
+                let result = self.track_parcel_blocking(&shipment.tracking_number);
+                (shipment.tracking_number, result)
+            })
+            .collect())
+    }
+
+    /// Retours enregistrés correspondant à une recherche, avec leur étiquette
+    fn find_returns(&self, query: &ShipmentQuery) -> Result<Vec<ReturnShipment>, DeliveryError> {
+        self.store.find_shipments(query)?
+            .into_iter()
+            .filter_map(|record| record.return_shipment)
+            .map(|mut shipment| {
+                shipment.label = self.store.load_label(&shipment.tracking_number)?;
+                Ok(shipment)
+            })
+            .collect()
+    }
+
+    /// Enregistre le retour, rattaché à son envoi d'origine, étiquette dans un format accepté
+    fn register_return(
+        &self,
+        mut shipment: ReturnShipment,
+        parcel: &Parcel,
+        request: &ReturnRequest
+    ) -> Result<ReturnShipment, DeliveryError> {
+        if let Some(label) = shipment.label.take() {
+            shipment.label = Some(negotiate_label_format(label, &request.label_options)?);
+        }
+
+        let mut record = ShipmentRecord::new(parcel, shipment.carrier, &shipment.tracking_number)
+            .with_return(&shipment);
+        persist(record.lifecycle.transition(LifecycleState::Labelled, "Retour créé"));
+        persist(self.store.save_shipment(&record));
+        if let Some(label) = &shipment.label {
+            persist(self.store.save_label(label));
+        }
+
+        Ok(shipment)
+    }
+
+    /// Réserve un enlèvement auprès d'un transporteur
+    ///
+    /// La demande est validée avant tout appel à l'API du transporteur, jour
+    /// d'enlèvement compris d'après le calendrier du moteur d'estimation.
+    pub async fn schedule_pickup(&self, carrier_code: &CarrierCode, request: &PickupRequest) -> Result<Pickup, DeliveryError> {
+        let carrier = self.get_carrier(carrier_code)
+            .ok_or_else(|| DeliveryError::UnknownCarrier(format!("{:?}", carrier_code)))?;
+        let scheduler = pickup_scheduler(carrier.as_ref())?;
+
+        validate_pickup_request(request, self.eta.calendar(), *carrier_code).into_result()?;
+        scheduler.schedule_pickup(request).await
+    }
+
+    /// Version synchrone de schedule_pickup
+    pub fn schedule_pickup_blocking(&self, carrier_code: &CarrierCode, request: &PickupRequest) -> Result<Pickup, DeliveryError> {
+        let carrier = self.get_carrier(carrier_code)
+            .ok_or_else(|| DeliveryError::UnknownCarrier(format!("{:?}", carrier_code)))?;
+        let scheduler = pickup_scheduler(carrier.as_ref())?;
+
+        validate_pickup_request(request, self.eta.calendar(), *carrier_code).into_result()?;
+        scheduler.schedule_pickup_blocking(request)
+    }
+
+    /// Modifie un enlèvement réservé (créneau, nombre de colis, envois concernés...)
+    pub async fn modify_pickup(
+        &self,
+        carrier_code: &CarrierCode,
+        confirmation_number: &str,
+        request: &PickupRequest
+    ) -> Result<Pickup, DeliveryError> {
+        let carrier = self.get_carrier(carrier_code)
+            .ok_or_else(|| DeliveryError::UnknownCarrier(format!("{:?}", carrier_code)))?;
+        let scheduler = pickup_scheduler(carrier.as_ref())?;
+
+        validate_pickup_request(request, self.eta.calendar(), *carrier_code).into_result()?;
+        scheduler.modify_pickup(confirmation_number, request).await
+    }
+
+    /// Version synchrone de modify_pickup
+    pub fn modify_pickup_blocking(
+        &self,
+        carrier_code: &CarrierCode,
+        confirmation_number: &str,
+        request: &PickupRequest
+    ) -> Result<Pickup, DeliveryError> {
+        let carrier = self.get_carrier(carrier_code)
+            .ok_or_else(|| DeliveryError::UnknownCarrier(format!("{:?}", carrier_code)))?;
+        let scheduler = pickup_scheduler(carrier.as_ref())?;
+
+        validate_pickup_request(request, self.eta.calendar(), *carrier_code).into_result()?;
+        scheduler.modify_pickup_blocking(confirmation_number, request)
+    }
+
+    /// Annule un enlèvement réservé
+    pub async fn cancel_pickup(&self, carrier_code: &CarrierCode, confirmation_number: &str) -> Result<(), DeliveryError> {
+        let carrier = self.get_carrier(carrier_code)
+            .ok_or_else(|| DeliveryError::UnknownCarrier(format!("{:?}", carrier_code)))?;
+
+        pickup_scheduler(carrier.as_ref())?.cancel_pickup(confirmation_number).await
+    }
+
+    /// Version synchrone de cancel_pickup
+    pub fn cancel_pickup_blocking(&self, carrier_code: &CarrierCode, confirmation_number: &str) -> Result<(), DeliveryError> {
+        let carrier = self.get_carrier(carrier_code)
+            .ok_or_else(|| DeliveryError::UnknownCarrier(format!("{:?}", carrier_code)))?;
+
+        pickup_scheduler(carrier.as_ref())?.cancel_pickup_blocking(confirmation_number)
+    }
+
+    /// Envois en attente de clôture
+    pub fn manifests(&self) -> &ManifestCollector {
+        &self.manifests
+    }
+
+    /// Clôture la journée d'un transporteur et retourne le bordereau de remise
+    ///
+    /// Le bordereau est transmis au transporteur s'il propose la clôture par API ;
+    /// en cas d'échec, les envois restent en attente de la prochaine clôture et le
+    /// numéro du bordereau n'est pas consommé.
+    pub async fn close_manifest(&self, carrier_code: &CarrierCode) -> Result<Manifest, DeliveryError> {
+        let carrier = self.get_carrier(carrier_code)
+            .ok_or_else(|| DeliveryError::UnknownCarrier(format!("{:?}", carrier_code)))?;
+        let mut manifest = self.manifests.close(*carrier_code, carrier.account_number().as_deref())?;
+
+        if let Some(submitter) = carrier.manifest_submitter() {
+            match submitter.submit_manifest(&manifest).await {
+                Ok(confirmation) => manifest.confirmation_number = Some(confirmation),
+                Err(e) => {
+                    self.manifests.restore(manifest);
+                    return Err(e);
+                }
+            }
+        }
+
+        self.manifests.confirm(&manifest);
+        self.record_manifest(&manifest);
+        Ok(manifest)
+    }
+
+    /// Version synchrone de close_manifest
+    pub fn close_manifest_blocking(&self, carrier_code: &CarrierCode) -> Result<Manifest, DeliveryError> {
+        let carrier = self.get_carrier(carrier_code)
+            .ok_or_else(|| DeliveryError::UnknownCarrier(format!("{:?}", carrier_code)))?;
+        let mut manifest = self.manifests.close(*carrier_code, carrier.account_number().as_deref())?;
+
+        if let Some(submitter) = carrier.manifest_submitter() {
+            match submitter.submit_manifest_blocking(&manifest) {
+                Ok(confirmation) => manifest.confirmation_number = Some(confirmation),
+                Err(e) => {
+                    self.manifests.restore(manifest);
+                    return Err(e);
+                }
+            }
+        }
+
+        self.manifests.confirm(&manifest);
+        self.record_manifest(&manifest);
+        Ok(manifest)
+    }
+
+    /// Estime la date de livraison d'un envoi enregistré
+    ///
+    /// L'estimation part du délai du tarif retenu et s'affine avec le dernier relevé de suivi.
+    pub fn estimate_delivery(&self, tracking_number: &str) -> Result<Option<DeliveryEstimate>, DeliveryError> {
+        let record = self.store.load_shipment(tracking_number)?
+            .ok_or_else(|| DeliveryError::ShipmentNotFound(tracking_number.to_string()))?;
+        let now = chrono::Utc::now();
+        let (origin, destination) = (&record.parcel.sender.country, &record.parcel.recipient.country);
+
+        let estimate = match self.store.latest_tracking(tracking_number)? {
+            Some(info) => {
+                let delivery_days = record.rate.as_ref().and_then(|rate| rate.delivery_days);
+                self.eta.estimate_tracking(&info, delivery_days, origin, destination, now)
+            }
+            None => record.rate.as_ref().and_then(|rate| self.eta.estimate_rate(rate, &record.parcel, record.created_at)),
+        };
+
+        Ok(estimate)
+    }
+
+    /// Annule un envoi qui n'a pas encore été confié au transporteur
+    ///
+    /// L'envoi est retiré du prochain bordereau ; l'étiquette n'est pas annulée
+    /// auprès du transporteur.
+    pub fn cancel_shipment(&self, tracking_number: &str, reason: &str) -> Result<ShipmentRecord, DeliveryError> {
+        let record = self.update_shipment(tracking_number, |current| {
+            let Some(mut record) = current else { return Ok(None) };
+            record.lifecycle.transition(LifecycleState::Cancelled, reason)?;
+            record.updated_at = chrono::Utc::now();
+            Ok(Some(record))
+        })?.ok_or_else(|| DeliveryError::ShipmentNotFound(tracking_number.to_string()))?;
+        self.manifests.remove(tracking_number);
+
+        Ok(record)
+    }
+
+    /// Refuse de regénérer l'étiquette d'un colis dont l'envoi est livré, retourné ou annulé
+    fn check_relabel(&self, carrier_code: CarrierCode, parcel: &Parcel) -> Result<(), DeliveryError> {
+        let query = ShipmentQuery::new().with_parcel_id(parcel.id).with_carrier(carrier_code);
+        let terminal = self.store.find_shipments(&query)?.into_iter()
+            .find(|record| record.return_of.is_none() && record.lifecycle.state.is_terminal());
+
+        match terminal {
+            Some(record) => Err(DeliveryError::InvalidTransition(format!(
+                "Envoi {} ({}): étiquette non regénérée", record.tracking_number, record.lifecycle.state
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Enregistre une étiquette générée : stockage de l'envoi et ligne du prochain bordereau
+    ///
+    /// Une étiquette regénérée pour un envoi déjà enregistré complète son cycle de
+    /// vie sans le faire revenir en arrière ; un envoi livré, retourné ou annulé
+    /// n'est pas modifié.
+    fn record_label(&self, carrier: &dyn ShippingCarrier, label: &ShippingLabel, parcel: &Parcel, rate: &Rate) {
+        let mut pending = false;
+        let updated = self.update_shipment(&label.tracking_number, |current| {
+            let mut record = current.unwrap_or_else(|| ShipmentRecord::new(parcel, label.carrier, &label.tracking_number));
+
+            if record.lifecycle.state.is_terminal() {
+                log::warn!(
+                    "Étiquette regénérée pour l'envoi {} ({}): envoi inchangé",
+                    label.tracking_number, record.lifecycle.state
+                );
+                return Ok(None);
             }
+
+            // Colis, tarif et compte ne changent plus une fois l'envoi remis au transporteur
+            pending = record.lifecycle.state.can_transition_to(LifecycleState::Manifested);
+            if pending {
+                record.parcel = parcel.clone();
+                record.rate = Some(rate.clone());
+                record.account = carrier.account_number();
+            }
+
+            let transitions = [
+                (LifecycleState::Rated, format!("Tarif {} retenu", rate.service_code)),
+                (LifecycleState::Labelled, "Étiquette générée".to_string()),
+            ];
+            for (state, action) in transitions {
+                if record.lifecycle.state.can_transition_to(state) {
+                    record.lifecycle.transition(state, &action)?;
+                }
+            }
+
+            record.updated_at = chrono::Utc::now();
+            Ok(Some(record))
+        });
+
+        if matches!(updated, Ok(None)) {
+            return;
+        }
+        persist(updated.map(|_| ()));
+        persist(self.store.save_label(label));
+
+        if pending {
+            let entry = ManifestEntry::from_label(label)
+                .with_parcel(parcel, rate)
+                .with_account(carrier.account_number().as_deref());
+            self.manifests.record(entry);
+        }
+    }
+
+    /// Enregistre un relevé de suivi et reporte son statut sur l'envoi
+    fn record_tracking(&self, info: &TrackingInfo) {
+        persist(self.store.save_tracking(info));
+
+        // Les événements anciens ou contradictoires sont écartés par le cycle de vie
+        let updated = self.update_shipment(&info.tracking_number, |current| {
+            let Some(mut record) = current else { return Ok(None) };
+            let audited = record.lifecycle.audit.len();
+            record.lifecycle.apply_tracking(info);
+
+            if record.lifecycle.audit.len() == audited {
+                return Ok(None);
+            }
+            record.status = record.lifecycle.status().unwrap_or(record.status);
+            record.updated_at = chrono::Utc::now();
+            Ok(Some(record))
+        });
+        persist(updated.map(|_| ()));
+    }
+
+    /// Marque les envois d'un bordereau clôturé comme remis au transporteur
+    fn record_manifest(&self, manifest: &Manifest) {
+        let action = format!("Bordereau {}", manifest.id);
+
+        for entry in &manifest.entries {
+            let updated = self.update_shipment(&entry.tracking_number, |current| {
+                let Some(mut record) = current else { return Ok(None) };
+                if !record.lifecycle.state.can_transition_to(LifecycleState::Manifested) {
+                    return Ok(None);
+                }
+                record.lifecycle.transition(LifecycleState::Manifested, &action)?;
+                record.updated_at = chrono::Utc::now();
+                Ok(Some(record))
+            });
+            persist(updated.map(|_| ()));
         }
+    }
+
+    /// Charge un envoi, le modifie et l'enregistre
+    ///
+    /// `change` reçoit l'envoi enregistré et renvoie la version à enregistrer, ou
+    /// `None` s'il n'y a rien à écrire. Si une autre opération a enregistré l'envoi
+    /// entre-temps (étiquette et suivi reçus en même temps), le stockage refuse la
+    /// version périmée : l'envoi est rechargé et la modification appliquée de nouveau.
+    fn update_shipment(
+        &self,
+        tracking_number: &str,
+        mut change: impl FnMut(Option<ShipmentRecord>) -> Result<Option<ShipmentRecord>, DeliveryError>
+    ) -> Result<Option<ShipmentRecord>, DeliveryError> {
+        let mut attempt = 1;
+        loop {
+            let Some(record) = change(self.store.load_shipment(tracking_number)?)? else {
+                return Ok(None);
+            };
 
-        Err(DeliveryError::UnsupportedTrackingNumber(tracking_number.to_string()))
+            match self.store.save_shipment(&record) {
+                Err(DeliveryError::InvalidTransition(reason)) if attempt < MAX_WRITE_ATTEMPTS => {
+                    log::debug!("Envoi {} rechargé ({}): {}", tracking_number, attempt, reason);
+                    attempt += 1;
+                }
+                result => return result.map(|()| Some(record)),
+            }
+        }
     }
 
     /// Vérifie si un transporteur spécifique est disponible
@@ -128,8 +907,86 @@ impl ShippingManager {
     }
 }
 
+/// Vérifie les contraintes du transporteur avant tout appel à son API
+fn preflight(carrier: &dyn ShippingCarrier, parcel: &Parcel, service_code: Option<&str>) -> Result<(), DeliveryError> {
+    let violations = constraints::check_parcel(&carrier.constraints(), parcel, service_code);
+
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(DeliveryError::IneligibleParcel(violations))
+    }
+}
+
 impl Default for ShippingManager {
     fn default() -> Self {
         Self::new()
     }
 }
+
+/// Service de retour du transporteur, s'il propose le mode demandé
+fn return_service<'a>(carrier: &'a dyn ShippingCarrier, request: &ReturnRequest) -> Result<&'a dyn ReturnService, DeliveryError> {
+    let service = carrier.return_service()
+        .ok_or_else(|| DeliveryError::UnsupportedService(format!("{} ne propose pas de retours", carrier.carrier_name())))?;
+
+    if !service.supported_return_methods().contains(&request.method) {
+        return Err(DeliveryError::UnsupportedService(format!(
+            "Retour {:?} non proposé par {}", request.method, carrier.carrier_name()
+        )));
+    }
+
+    Ok(service)
+}
+
+/// Journalise un échec d'enregistrement sans interrompre l'opération :
+/// l'étiquette ou le relevé a déjà été obtenu auprès du transporteur
+fn persist(result: Result<(), DeliveryError>) {
+    if let Err(e) = result {
+        log::error!("Enregistrement dans le stockage des envois impossible: {}", e);
+    }
+}
+
+/// Service de réservation d'enlèvements du transporteur
+fn pickup_scheduler(carrier: &dyn ShippingCarrier) -> Result<&dyn PickupScheduler, DeliveryError> {
+    carrier.pickup_scheduler()
+        .ok_or_else(|| DeliveryError::UnsupportedService(format!(
+            "{} ne propose pas la réservation d'enlèvements", carrier.carrier_name()
+        )))
+}
+
+/// Complète la demande avec un numéro d'autorisation de retour généré au besoin
+fn with_rma(request: &ReturnRequest) -> ReturnRequest {
+    let mut request = request.clone();
+    if request.rma.is_none() {
+        let id = Uuid::new_v4().simple().to_string().to_uppercase();
+        request.rma = Some(format!("RMA-{}", &id[..10]));
+    }
+    request
+}
+
+/// Garantit que l'étiquette est dans un des formats acceptés, en la convertissant au besoin
+fn negotiate_label_format(label: ShippingLabel, options: &LabelOptions) -> Result<ShippingLabel, DeliveryError> {
+    if options.formats.is_empty() || options.formats.contains(&label.label_format) {
+        return Ok(label);
+    }
+
+    let mut last_error = None;
+    for format in &options.formats {
+        match label.convert_to(*format) {
+            Ok(converted) => return Ok(converted),
+            Err(e) => last_error = Some(e),
+        }
+    }
+
+    Err(last_error.unwrap_or(DeliveryError::UnsupportedService(format!("{:?}", label.label_format))))
+}
+
+/// Recherche de suivi pas encore aboutie
+fn new_lookup(tracking_number: &str) -> TrackingLookup {
+    let tracking_number = normalize_tracking_number(tracking_number);
+    TrackingLookup {
+        result: Err(DeliveryError::UnsupportedTrackingNumber(tracking_number.clone())),
+        tracking_number,
+        attempts: Vec::new(),
+    }
+}
@@ -1,5 +1,8 @@
 use async_trait::async_trait;
-use crate::models::{Parcel, Rate, ShippingLabel, TrackingInfo};
+use crate::models::{
+    LabelFormat, LabelOptions, Parcel, Pickup, PickupRequest, Rate, ReturnMethod, ReturnRequest, ReturnShipment,
+    ShipmentStatus, ShippingLabel, TrackingInfo,
+};
 use crate::errors::DeliveryError;
 
 /// Trait pour l'obtention des tarifs d'envoi
@@ -16,10 +19,15 @@ pub trait RateProvider: Send + Sync {
 #[async_trait]
 pub trait LabelGenerator: Send + Sync {
     /// Génère une étiquette d'expédition pour un colis avec un tarif sélectionné
-    async fn generate_label(&self, parcel: &Parcel, rate: &Rate) -> Result<ShippingLabel, DeliveryError>;
+    async fn generate_label(&self, parcel: &Parcel, rate: &Rate, options: &LabelOptions) -> Result<ShippingLabel, DeliveryError>;
 
     /// Version synchrone (bloquante) de generate_label
-    fn generate_label_blocking(&self, parcel: &Parcel, rate: &Rate) -> Result<ShippingLabel, DeliveryError>;
+    fn generate_label_blocking(&self, parcel: &Parcel, rate: &Rate, options: &LabelOptions) -> Result<ShippingLabel, DeliveryError>;
+
+    /// Formats d'étiquette produits nativement par le transporteur
+    fn supported_label_formats(&self) -> Vec<LabelFormat> {
+        vec![LabelFormat::PDF]
+    }
 }
 
 /// Trait pour le suivi de l'acheminement des colis
@@ -37,17 +45,85 @@ pub trait ShipmentTracker: Send + Sync {
 
 /// Trait pour la normalisation des données hétérogènes entre transporteurs
 pub trait DataNormalizer: Send + Sync {
-    /// Convertit un code d'état spécifique au transporteur en un format standardisé
-    fn normalize_status_code(&self, carrier_status: &str) -> String;
+    /// Convertit un code d'état spécifique au transporteur en statut normalisé
+    ///
+    /// Les implémentations s'appuient en général sur les tables de `utils::status_mapping`,
+    /// qui recensent les codes inconnus.
+    fn normalize_status_code(&self, carrier_status: &str) -> ShipmentStatus;
 
     /// Normalise une adresse selon les standards du transporteur
+    ///
+    /// Les implémentations s'appuient en général sur `address_normalizer`.
     fn normalize_address(&self, address: &mut crate::models::Address) -> Result<(), DeliveryError>;
 
+    /// Règles de normalisation des adresses du transporteur (casse, ASCII, longueur des lignes)
+    fn address_normalizer(&self) -> crate::utils::address::AddressNormalizer {
+        crate::utils::address::AddressNormalizer::new()
+    }
+
     /// Vérifie si une adresse est valide pour ce transporteur
     fn validate_address(&self, address: &crate::models::Address) -> Result<(), DeliveryError>;
 }
 
+/// Trait pour les services de retour proposés par un transporteur
+#[async_trait]
+pub trait ReturnService: Send + Sync {
+    /// Modes de retour proposés
+    fn supported_return_methods(&self) -> Vec<ReturnMethod>;
+
+    /// Crée un retour pour un colis dont l'expéditeur et le destinataire sont déjà inversés
+    ///
+    /// Le numéro d'autorisation de retour de la demande est toujours renseigné.
+    async fn create_return(
+        &self,
+        parcel: &Parcel,
+        original_tracking_number: &str,
+        request: &ReturnRequest
+    ) -> Result<ReturnShipment, DeliveryError>;
+
+    /// Version synchrone (bloquante) de create_return
+    fn create_return_blocking(
+        &self,
+        parcel: &Parcel,
+        original_tracking_number: &str,
+        request: &ReturnRequest
+    ) -> Result<ReturnShipment, DeliveryError>;
+}
+
+/// Trait pour la réservation d'enlèvements auprès d'un transporteur
+#[async_trait]
+pub trait PickupScheduler: Send + Sync {
+    /// Réserve un enlèvement et retourne sa confirmation
+    async fn schedule_pickup(&self, request: &PickupRequest) -> Result<Pickup, DeliveryError>;
+
+    /// Version synchrone (bloquante) de schedule_pickup
+    fn schedule_pickup_blocking(&self, request: &PickupRequest) -> Result<Pickup, DeliveryError>;
+
+    /// Modifie un enlèvement réservé
+    async fn modify_pickup(&self, confirmation_number: &str, request: &PickupRequest) -> Result<Pickup, DeliveryError>;
+
+    /// Version synchrone (bloquante) de modify_pickup
+    fn modify_pickup_blocking(&self, confirmation_number: &str, request: &PickupRequest) -> Result<Pickup, DeliveryError>;
+
+    /// Annule un enlèvement réservé
+    async fn cancel_pickup(&self, confirmation_number: &str) -> Result<(), DeliveryError>;
+
+    /// Version synchrone (bloquante) de cancel_pickup
+    fn cancel_pickup_blocking(&self, confirmation_number: &str) -> Result<(), DeliveryError>;
+}
+
+/// Trait pour la clôture de fin de journée auprès d'un transporteur
+#[async_trait]
+pub trait ManifestSubmitter: Send + Sync {
+    /// Transmet le bordereau au transporteur et retourne la référence de clôture
+    async fn submit_manifest(&self, manifest: &crate::manifest::Manifest) -> Result<String, DeliveryError>;
+
+    /// Version synchrone (bloquante) de submit_manifest
+    fn submit_manifest_blocking(&self, manifest: &crate::manifest::Manifest) -> Result<String, DeliveryError>;
+}
+
 /// Trait combiné pour un transporteur complet
+#[async_trait]
 pub trait ShippingCarrier: RateProvider + LabelGenerator + ShipmentTracker + DataNormalizer {
     /// Obtient le code du transporteur
     fn carrier_code(&self) -> crate::models::CarrierCode;
@@ -60,4 +136,29 @@ pub trait ShippingCarrier: RateProvider + LabelGenerator + ShipmentTracker + Dat
 
     /// Version synchrone (bloquante) de is_available
     fn is_available_blocking(&self) -> bool;
+
+    /// Contraintes de colis déclarées par le transporteur pour ses services
+    fn constraints(&self) -> Vec<crate::core::constraints::ServiceConstraints> {
+        crate::core::constraints::default_constraints(self.carrier_code())
+    }
+
+    /// Services de retour du transporteur, s'il en propose
+    fn return_service(&self) -> Option<&dyn ReturnService> {
+        None
+    }
+
+    /// Réservation d'enlèvements, si le transporteur la propose
+    fn pickup_scheduler(&self) -> Option<&dyn PickupScheduler> {
+        None
+    }
+
+    /// Clôture des bordereaux par API, si le transporteur la propose
+    fn manifest_submitter(&self) -> Option<&dyn ManifestSubmitter> {
+        None
+    }
+
+    /// Compte client utilisé auprès du transporteur
+    fn account_number(&self) -> Option<String> {
+        None
+    }
 }
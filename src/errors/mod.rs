@@ -1,5 +1,8 @@
 use thiserror::Error;
 
+use crate::core::constraints::ConstraintViolation;
+use crate::utils::validation::ValidationReport;
+
 /// Les erreurs possibles lors de l'utilisation de la bibliothèque
 #[derive(Error, Debug)]
 pub enum DeliveryError {
@@ -15,12 +18,24 @@ pub enum DeliveryError {
     #[error("Format de numéro de suivi non supporté: {0}")]
     UnsupportedTrackingNumber(String),
 
+    #[error("Envoi introuvable chez le transporteur: {0}")]
+    TrackingNotFound(String),
+
+    #[error("Envoi inconnu: {0}")]
+    ShipmentNotFound(String),
+
     #[error("Colis invalide: {0}")]
     InvalidParcel(String),
 
     #[error("Adresse invalide: {0}")]
     InvalidAddress(String),
 
+    #[error("Validation échouée: {0}")]
+    Validation(ValidationReport),
+
+    #[error("Colis non éligible: {}", format_violations(.0))]
+    IneligibleParcel(Vec<ConstraintViolation>),
+
     #[error("Taux non disponible")]
     RateUnavailable,
 
@@ -33,6 +48,18 @@ pub enum DeliveryError {
     #[error("Erreur de connexion: {0}")]
     ConnectionError(String),
 
+    #[error("Erreur d'impression: {0}")]
+    PrintError(String),
+
+    #[error("Erreur de bordereau: {0}")]
+    ManifestError(String),
+
+    #[error("Transition de statut invalide: {0}")]
+    InvalidTransition(String),
+
+    #[error("Erreur de notification: {0}")]
+    NotificationError(String),
+
     #[error("Erreur de sérialisation: {0}")]
     SerializationError(String),
 
@@ -55,5 +82,21 @@ pub enum DeliveryError {
     Unknown(String),
 }
 
+impl DeliveryError {
+    /// Indique que le transporteur ne connaît pas le numéro de suivi,
+    /// auquel cas un autre transporteur peut être interrogé
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, DeliveryError::UnsupportedTrackingNumber(_) | DeliveryError::TrackingNotFound(_))
+    }
+}
+
+/// Concatène les messages de violations de contraintes
+fn format_violations(violations: &[ConstraintViolation]) -> String {
+    violations.iter()
+        .map(|violation| violation.to_string())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
 /// Résultat spécifique à la bibliothèque
 pub type DeliveryResult<T> = Result<T, DeliveryError>;
@@ -0,0 +1,323 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+use crate::models::{CarrierCode, Parcel, Rate, ShipmentStatus, TrackingEvent, TrackingInfo, TrackingSubStatus};
+use crate::utils::calendar::{BusinessCalendar, next_day};
+
+/// Heure limite de dépôt par défaut : un colis remis plus tard part le jour ouvré suivant
+const DEFAULT_CUTOFF: (u32, u32) = (17, 0);
+
+/// Heure de fin de tournée, utilisée pour dater une livraison estimée
+const END_OF_DELIVERY: (u32, u32) = (18, 0);
+
+/// Durée de conservation en point de retrait, en jours ouvrés
+const PICKUP_HOLD_DAYS: u32 = 5;
+
+/// Origine d'une estimation de livraison
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EstimateBasis {
+    TransitTime,  // Délai d'acheminement du service
+    CarrierEstimate,  // Date annoncée par le transporteur
+    TrackingHistory,  // Délai ajusté d'après les événements de suivi
+    Delivered,  // Livraison constatée
+}
+
+/// Date de livraison estimée, avec sa fourchette
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeliveryEstimate {
+    pub earliest: NaiveDate,
+    pub expected: NaiveDate,
+    pub latest: NaiveDate,
+    pub confidence: f64,  // Probabilité estimée d'une livraison dans la fourchette (0 à 1)
+    pub basis: EstimateBasis,
+}
+
+impl DeliveryEstimate {
+    /// Indique si une date tombe dans la fourchette
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.earliest <= date && date <= self.latest
+    }
+}
+
+/// Moteur d'estimation des dates de livraison
+///
+/// Les dates sont calculées en jours opérés par le transporteur (du lundi au
+/// vendredi, plus le samedi pour les transporteurs qui livrent ce jour-là), hors
+/// jours fériés du pays de destination, à partir de la date de départ déterminée
+/// par l'heure limite de dépôt et le calendrier du pays d'origine.
+#[derive(Debug, Clone)]
+pub struct EtaEngine {
+    timezone: Tz,  // Fuseau des dates calculées, heure d'été comprise (heures limites : fuseau du pays d'origine)
+    default_cutoff: NaiveTime,
+    cutoffs: HashMap<CarrierCode, NaiveTime>,
+    saturday_delivery: Vec<CarrierCode>,
+    calendar: BusinessCalendar,  // Jours fériés et jours non opérés des transporteurs
+}
+
+impl Default for EtaEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EtaEngine {
+    /// Crée un moteur à l'heure de Paris (heure d'été comprise), avec une heure limite de 17h
+    pub fn new() -> Self {
+        Self {
+            timezone: chrono_tz::Europe::Paris,
+            default_cutoff: NaiveTime::from_hms_opt(DEFAULT_CUTOFF.0, DEFAULT_CUTOFF.1, 0).unwrap_or_default(),
+            cutoffs: HashMap::new(),
+            saturday_delivery: Vec::new(),
+            calendar: BusinessCalendar::new(),
+        }
+    }
+
+    /// Définit le fuseau utilisé pour les dates calculées
+    pub fn with_timezone(mut self, timezone: Tz) -> Self {
+        self.timezone = timezone;
+        self
+    }
+
+    /// Fuseau utilisé pour les dates calculées
+    pub fn timezone(&self) -> Tz {
+        self.timezone
+    }
+
+    /// Définit l'heure limite de dépôt par défaut
+    pub fn with_default_cutoff(mut self, cutoff: NaiveTime) -> Self {
+        self.default_cutoff = cutoff;
+        self
+    }
+
+    /// Définit l'heure limite de dépôt d'un transporteur
+    pub fn with_cutoff(mut self, carrier: CarrierCode, cutoff: NaiveTime) -> Self {
+        self.cutoffs.insert(carrier, cutoff);
+        self
+    }
+
+    /// Indique qu'un transporteur livre le samedi
+    pub fn with_saturday_delivery(mut self, carrier: CarrierCode) -> Self {
+        if !self.saturday_delivery.contains(&carrier) {
+            self.saturday_delivery.push(carrier);
+        }
+        self
+    }
+
+    /// Remplace le calendrier des jours fériés et des jours non opérés
+    pub fn with_calendar(mut self, calendar: BusinessCalendar) -> Self {
+        self.calendar = calendar;
+        self
+    }
+
+    /// Calendrier des jours fériés et des jours non opérés
+    pub fn calendar(&self) -> &BusinessCalendar {
+        &self.calendar
+    }
+
+    /// Date locale d'un instant
+    pub fn local_date(&self, at: DateTime<Utc>) -> NaiveDate {
+        at.with_timezone(&self.timezone).date_naive()
+    }
+
+    /// Indique si un transporteur livre à cette date
+    pub fn is_delivery_day(&self, carrier: CarrierCode, country: &str, date: NaiveDate) -> bool {
+        self.calendar.is_operating_day(carrier, country, date)
+            || (date.weekday() == Weekday::Sat
+                && self.saturday_delivery.contains(&carrier)
+                && !self.calendar.is_holiday(country, date)
+                && !self.calendar.is_closed(carrier, date))
+    }
+
+    /// Date de départ d'un colis remis à un instant donné
+    ///
+    /// L'heure limite s'entend à l'heure locale du pays d'origine. Après l'heure
+    /// limite, ou un jour non opéré, le colis part le jour opéré suivant.
+    pub fn ship_date(&self, carrier: CarrierCode, origin_country: &str, at: DateTime<Utc>) -> NaiveDate {
+        let date = self.calendar.local_date(origin_country, at);
+        let time = at.with_timezone(&self.calendar.timezone(origin_country)).time();
+        let cutoff = self.cutoffs.get(&carrier).copied().unwrap_or(self.default_cutoff);
+
+        if time <= cutoff {
+            self.calendar.next_ship_date(carrier, origin_country, date)
+        } else {
+            self.calendar.next_ship_date(carrier, origin_country, date + Duration::days(1))
+        }
+    }
+
+    /// Ajoute un nombre de jours de livraison à une date
+    pub fn add_delivery_days(&self, carrier: CarrierCode, country: &str, from: NaiveDate, days: u32) -> NaiveDate {
+        (0..days).fold(from, |date, _| next_day(date, |date| self.is_delivery_day(carrier, country, date)))
+    }
+
+    /// Instant de fin de tournée d'une date, pour renseigner `Rate::estimated_delivery`
+    pub fn end_of_delivery(&self, date: NaiveDate) -> DateTime<Utc> {
+        let time = NaiveTime::from_hms_opt(END_OF_DELIVERY.0, END_OF_DELIVERY.1, 0).unwrap_or_default();
+        // Une heure locale inexistante ou ambiguë (changement d'heure) retient le premier instant possible
+        self.timezone.from_local_datetime(&date.and_time(time))
+            .earliest()
+            .map(|local| local.with_timezone(&Utc))
+            .unwrap_or_else(|| date.and_time(time).and_utc())
+    }
+
+    /// Estimation d'après le délai d'acheminement d'un service
+    ///
+    /// La fourchette s'élargit d'un jour en national et de deux à l'international,
+    /// sauf pour une livraison garantie.
+    pub fn estimate_transit(
+        &self,
+        carrier: CarrierCode,
+        origin_country: &str,
+        destination_country: &str,
+        shipped_at: DateTime<Utc>,
+        delivery_days: u32,
+        guaranteed: bool
+    ) -> DeliveryEstimate {
+        let ship_date = self.ship_date(carrier, origin_country, shipped_at);
+        let expected = self.add_delivery_days(carrier, destination_country, ship_date, delivery_days);
+        let domestic = origin_country.eq_ignore_ascii_case(destination_country);
+
+        let (earliest, latest, confidence) = if guaranteed {
+            (expected, expected, 0.95)
+        } else {
+            let earliest = self.add_delivery_days(carrier, destination_country, ship_date, delivery_days.saturating_sub(1).max(1));
+            let slack = if domestic { 1 } else { 2 };
+            let latest = self.add_delivery_days(carrier, destination_country, expected, slack);
+            (earliest.min(expected), latest, if domestic { 0.85 } else { 0.7 })
+        };
+
+        DeliveryEstimate { earliest, expected, latest, confidence, basis: EstimateBasis::TransitTime }
+    }
+
+    /// Estimation d'après la date annoncée par le transporteur, à un jour de livraison près
+    fn estimate_carrier(&self, carrier: CarrierCode, destination_country: &str, announced: DateTime<Utc>) -> DeliveryEstimate {
+        let expected = self.local_date(announced);
+
+        DeliveryEstimate {
+            earliest: expected,
+            expected,
+            latest: self.add_delivery_days(carrier, destination_country, expected, 1),
+            confidence: 0.7,
+            basis: EstimateBasis::CarrierEstimate,
+        }
+    }
+
+    /// Estimation pour un tarif, si son délai ou sa date de livraison est connu
+    pub fn estimate_rate(&self, rate: &Rate, parcel: &Parcel, shipped_at: DateTime<Utc>) -> Option<DeliveryEstimate> {
+        match (rate.delivery_days, rate.estimated_delivery) {
+            (Some(days), _) => Some(self.estimate_transit(
+                rate.carrier, &parcel.sender.country, &parcel.recipient.country, shipped_at, days, rate.guaranteed_delivery,
+            )),
+            (None, Some(announced)) => Some(self.estimate_carrier(rate.carrier, &parcel.recipient.country, announced)),
+            (None, None) => None,
+        }
+    }
+
+    /// Renseigne la date de livraison d'un tarif qui n'en a pas
+    pub fn fill_rate(&self, rate: &mut Rate, parcel: &Parcel, shipped_at: DateTime<Utc>) {
+        if rate.estimated_delivery.is_none()
+            && let Some(estimate) = self.estimate_rate(rate, parcel, shipped_at) {
+            rate.estimated_delivery = Some(self.end_of_delivery(estimate.expected));
+        }
+    }
+
+    /// Estimation affinée par l'historique de suivi
+    ///
+    /// Une livraison constatée ou une mise en livraison fixe la date ; un incident
+    /// élargit la fourchette ; un colis en retard sur l'estimation est reporté au
+    /// prochain jour de livraison avec une confiance réduite.
+    pub fn estimate_tracking(
+        &self,
+        info: &TrackingInfo,
+        delivery_days: Option<u32>,
+        origin_country: &str,
+        destination_country: &str,
+        now: DateTime<Utc>
+    ) -> Option<DeliveryEstimate> {
+        let carrier = info.carrier;
+        let latest_event = info.latest_event();
+
+        if info.status == ShipmentStatus::Delivered || latest_event.is_some_and(|event| event.status == ShipmentStatus::Delivered) {
+            let delivered_at = info.delivered_at.or(latest_event.map(|event| event.timestamp)).unwrap_or(now);
+            let date = self.local_date(delivered_at);
+            return Some(DeliveryEstimate { earliest: date, expected: date, latest: date, confidence: 1.0, basis: EstimateBasis::Delivered });
+        }
+
+        if let Some(event) = latest_event {
+            let date = self.local_date(event.timestamp);
+
+            match sub_status(event) {
+                TrackingSubStatus::OutForDelivery => {
+                    let latest = self.add_delivery_days(carrier, destination_country, date, 1);
+                    return Some(DeliveryEstimate { earliest: date, expected: date, latest, confidence: 0.9, basis: EstimateBasis::TrackingHistory });
+                }
+                TrackingSubStatus::AvailableForPickup => {
+                    let expected = self.add_delivery_days(carrier, destination_country, date, 1);
+                    let latest = self.add_delivery_days(carrier, destination_country, date, PICKUP_HOLD_DAYS);
+                    return Some(DeliveryEstimate { earliest: date, expected, latest, confidence: 0.5, basis: EstimateBasis::TrackingHistory });
+                }
+                _ => {}
+            }
+        }
+
+        // Départ : date annoncée, sinon premier événement de prise en charge ou de transit
+        let shipped_at = info.shipped_at.or_else(|| {
+            info.events.iter()
+                .filter(|event| matches!(event.status, ShipmentStatus::Pickup | ShipmentStatus::InTransit))
+                .map(|event| event.timestamp)
+                .min()
+        });
+
+        let mut estimate = match (delivery_days, info.estimated_delivery) {
+            (Some(days), announced) => {
+                let mut estimate = self.estimate_transit(carrier, origin_country, destination_country, shipped_at.unwrap_or(now), days, false);
+                if let Some(announced) = announced {
+                    // La date du transporteur prime, la fourchette couvre les deux estimations
+                    let announced = self.local_date(announced);
+                    estimate.earliest = estimate.earliest.min(announced);
+                    estimate.latest = estimate.latest.max(announced);
+                    estimate.expected = announced;
+                }
+                estimate
+            }
+            (None, Some(announced)) => self.estimate_carrier(carrier, destination_country, announced),
+            (None, None) => return None,
+        };
+        if shipped_at.is_some() {
+            estimate.basis = EstimateBasis::TrackingHistory;
+        }
+
+        if latest_event.is_some_and(|event| event.status == ShipmentStatus::Exception) {
+            estimate.latest = self.add_delivery_days(carrier, destination_country, estimate.latest, 2);
+            estimate.confidence = (estimate.confidence - 0.3).max(0.2);
+            estimate.basis = EstimateBasis::TrackingHistory;
+        }
+
+        // Colis en retard : report au prochain jour de livraison
+        let today = self.local_date(now);
+        if estimate.expected < today {
+            let expected = if self.is_delivery_day(carrier, destination_country, today) {
+                today
+            } else {
+                self.add_delivery_days(carrier, destination_country, today, 1)
+            };
+            estimate.earliest = expected;
+            estimate.expected = expected;
+            estimate.latest = estimate.latest.max(self.add_delivery_days(carrier, destination_country, expected, 1));
+            estimate.confidence *= 0.7;
+            estimate.basis = EstimateBasis::TrackingHistory;
+        }
+
+        Some(estimate)
+    }
+}
+
+/// Sous-statut d'un événement, déduit du statut pour les événements qui n'en ont pas
+fn sub_status(event: &TrackingEvent) -> TrackingSubStatus {
+    match (event.sub_status, event.status) {
+        (TrackingSubStatus::Unknown, ShipmentStatus::OutForDelivery) => TrackingSubStatus::OutForDelivery,
+        (sub_status, _) => sub_status,
+    }
+}
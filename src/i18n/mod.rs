@@ -0,0 +1,307 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::constraints::ConstraintViolation;
+use crate::errors::DeliveryError;
+use crate::lifecycle::LifecycleState;
+use crate::models::{ShipmentStatus, TrackingEvent, TrackingSubStatus};
+use crate::utils::catalog::{ActiveCatalog, CatalogTables, YamlCatalog};
+use crate::utils::validation::{ValidationIssue, ValidationReport};
+
+/// Langue des libellés et messages
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    Fr,
+    En,
+    De,
+    Es,
+    It,
+}
+
+impl Locale {
+    /// Toutes les langues fournies par la bibliothèque
+    pub const ALL: [Locale; 5] = [Locale::Fr, Locale::En, Locale::De, Locale::Es, Locale::It];
+
+    /// Code ISO 639-1 de la langue
+    pub fn code(self) -> &'static str {
+        match self {
+            Locale::Fr => "fr",
+            Locale::En => "en",
+            Locale::De => "de",
+            Locale::Es => "es",
+            Locale::It => "it",
+        }
+    }
+
+    /// Langue correspondant à une étiquette (`fr`, `de-CH`, `en_GB`, `it-IT.UTF-8`…)
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let language = tag.trim().split(['-', '_', '.']).next()?.to_lowercase();
+        Self::ALL.into_iter().find(|locale| locale.code() == language)
+    }
+
+    /// Format des dates (chrono) usuel dans la langue
+    pub fn date_format(self) -> &'static str {
+        match self {
+            Locale::De => "%d.%m.%Y",
+            _ => "%d/%m/%Y",
+        }
+    }
+
+    /// Langue usuelle d'un pays (code ISO 3166-1), l'anglais à défaut
+    pub fn for_country(country: &str) -> Self {
+        match country.trim().to_uppercase().as_str() {
+            "FR" | "BE" | "LU" | "MC" => Locale::Fr,
+            "DE" | "AT" | "LI" => Locale::De,
+            "ES" | "AD" => Locale::Es,
+            "IT" | "SM" | "VA" => Locale::It,
+            _ => Locale::En,
+        }
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+/// Catalogue de traductions
+///
+/// Document YAML : une entrée par langue puis par section,
+/// `en: { status: { Delivered: Delivered }, validation: { ... } }`.
+/// Une clé absente dans la langue demandée est cherchée dans la langue de repli
+/// (l'anglais par défaut), puis en français.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Catalog {
+    messages: CatalogTables<Locale, String>,  // Par langue, clé `section.clé`
+    fallback: Locale,
+}
+
+impl Default for Catalog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl YamlCatalog for Catalog {
+    const NAME: &'static str = "Traductions";
+    const EMBEDDED: &'static str = include_str!("messages.yaml");
+
+    fn from_yaml(yaml: &str) -> Result<Self, DeliveryError> {
+        Ok(Self { messages: CatalogTables::from_sectioned_yaml(yaml, Self::NAME)?, ..Self::new() })
+    }
+
+    fn with_overrides(mut self, overrides: Catalog) -> Self {
+        self.messages.extend(overrides.messages);
+        self
+    }
+}
+
+impl Catalog {
+    /// Crée un catalogue vide
+    pub fn new() -> Self {
+        Self {
+            messages: CatalogTables::default(),
+            fallback: Locale::En,
+        }
+    }
+
+    /// Ajoute ou remplace une traduction (clé `section.clé`, ex: `status.Delivered`)
+    pub fn with_message(mut self, locale: Locale, key: &str, text: &str) -> Self {
+        self.messages.insert(locale, key.to_string(), text.to_string());
+        self
+    }
+
+    /// Change la langue de repli (l'anglais par défaut)
+    pub fn with_fallback(mut self, fallback: Locale) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    /// Texte d'une clé dans une langue, sans repli
+    pub fn get(&self, locale: Locale, key: &str) -> Option<&str> {
+        self.messages.get(&locale, key).map(String::as_str)
+    }
+
+    /// Texte d'une clé avec ses paramètres `{nom}` remplacés
+    ///
+    /// Les langues sont essayées dans l'ordre demandée, repli, français ; une
+    /// traduction dont un paramètre n'est pas fourni est ignorée.
+    pub fn translate(&self, locale: Locale, key: &str, params: &BTreeMap<String, String>) -> Option<String> {
+        self.messages.find_localized(locale, self.fallback, |_, messages| {
+            messages.get(key).and_then(|template| render(template, params))
+        })
+    }
+
+    /// Clés présentes en français mais absentes d'une langue, triées
+    pub fn missing_keys(&self, locale: Locale) -> Vec<String> {
+        let mut missing: Vec<String> = self.messages.group(&Locale::Fr)
+            .map(|messages| messages.keys().filter(|key| self.get(locale, key).is_none()).cloned().collect())
+            .unwrap_or_default();
+
+        missing.sort();
+        missing
+    }
+}
+
+/// Remplace les paramètres `{nom}` d'un texte ; `None` si l'un d'eux n'est pas fourni
+fn render(template: &str, params: &BTreeMap<String, String>) -> Option<String> {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        let end = start + rest[start..].find('}')?;
+        output.push_str(&rest[..start]);
+        output.push_str(params.get(&rest[start + 1..end])?);
+        rest = &rest[end + 1..];
+    }
+
+    output.push_str(rest);
+    Some(output)
+}
+
+/// Traductions utilisées par défaut (par défaut, les traductions intégrées)
+static ACTIVE_CATALOG: ActiveCatalog<Catalog> = ActiveCatalog::new();
+
+/// Remplace les traductions utilisées par défaut
+///
+/// Pour ne surcharger que quelques textes :
+/// `set_catalog(Catalog::embedded().with_overrides_file("traductions.yaml")?)`.
+pub fn set_catalog(catalog: Catalog) {
+    ACTIVE_CATALOG.set(catalog);
+}
+
+/// Copie des traductions utilisées par défaut
+pub fn catalog() -> Catalog {
+    ACTIVE_CATALOG.get()
+}
+
+/// Valeur affichable dans plusieurs langues
+///
+/// Sans traduction disponible, le texte d'origine (en français) est retourné.
+pub trait Localize {
+    /// Texte dans une langue d'après un catalogue donné
+    fn localize_with(&self, catalog: &Catalog, locale: Locale) -> String;
+
+    /// Texte dans une langue d'après les traductions actives
+    fn localize(&self, locale: Locale) -> String {
+        self.localize_with(&ACTIVE_CATALOG.read(), locale)
+    }
+}
+
+/// Libellé d'une valeur d'énumération, rangée par nom de variante dans une section
+fn label(catalog: &Catalog, locale: Locale, section: &str, variant: impl fmt::Debug, default: impl fmt::Display) -> String {
+    catalog.translate(locale, &format!("{}.{:?}", section, variant), &BTreeMap::new())
+        .unwrap_or_else(|| default.to_string())
+}
+
+impl Localize for ShipmentStatus {
+    fn localize_with(&self, catalog: &Catalog, locale: Locale) -> String {
+        label(catalog, locale, "status", self, self)
+    }
+}
+
+impl Localize for TrackingSubStatus {
+    fn localize_with(&self, catalog: &Catalog, locale: Locale) -> String {
+        label(catalog, locale, "sub_status", self, self)
+    }
+}
+
+impl Localize for LifecycleState {
+    fn localize_with(&self, catalog: &Catalog, locale: Locale) -> String {
+        label(catalog, locale, "lifecycle", self, self)
+    }
+}
+
+/// Description d'un événement : libellé de son sous-statut, ou texte du transporteur
+/// si le code n'est pas reconnu
+impl Localize for TrackingEvent {
+    fn localize_with(&self, catalog: &Catalog, locale: Locale) -> String {
+        match self.sub_status {
+            TrackingSubStatus::Unknown => self.description.clone(),
+            sub_status => sub_status.localize_with(catalog, locale),
+        }
+    }
+}
+
+/// Message d'un problème de validation, recherché par `validation.champ.code` puis `validation.code`
+///
+/// Le champ retenu est le dernier segment du chemin (`recipient.postal_code` → `postal_code`).
+fn validation_message(issue: &ValidationIssue, catalog: &Catalog, locale: Locale) -> String {
+    let leaf = issue.field.rsplit('.').next().unwrap_or_default();
+    let mut params = issue.params.clone();
+    params.entry("field".to_string()).or_insert_with(|| issue.field.clone());
+
+    catalog.translate(locale, &format!("validation.{}.{}", leaf, issue.code), &params)
+        .or_else(|| catalog.translate(locale, &format!("validation.{}", issue.code), &params))
+        .unwrap_or_else(|| issue.message.clone())
+}
+
+impl Localize for ValidationIssue {
+    fn localize_with(&self, catalog: &Catalog, locale: Locale) -> String {
+        format!("{}: {}", self.field, validation_message(self, catalog, locale))
+    }
+}
+
+impl Localize for ValidationReport {
+    fn localize_with(&self, catalog: &Catalog, locale: Locale) -> String {
+        self.issues.iter()
+            .map(|issue| issue.localize_with(catalog, locale))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+impl Localize for ConstraintViolation {
+    fn localize_with(&self, catalog: &Catalog, locale: Locale) -> String {
+        let message = validation_message(&ValidationIssue::from(self), catalog, locale);
+        format!("{} [{}]: {}", self.carrier, self.code, message)
+    }
+}
+
+/// Message d'erreur ; le détail fourni par le transporteur ou l'appelant n'est pas traduit
+impl Localize for DeliveryError {
+    fn localize_with(&self, catalog: &Catalog, locale: Locale) -> String {
+        let (key, detail) = match self {
+            DeliveryError::ApiError(detail) => ("api_error", Some(detail.clone())),
+            DeliveryError::UnknownCarrier(detail) => ("unknown_carrier", Some(detail.clone())),
+            DeliveryError::UnsupportedService(detail) => ("unsupported_service", Some(detail.clone())),
+            DeliveryError::UnsupportedTrackingNumber(detail) => ("unsupported_tracking_number", Some(detail.clone())),
+            DeliveryError::TrackingNotFound(detail) => ("tracking_not_found", Some(detail.clone())),
+            DeliveryError::ShipmentNotFound(detail) => ("shipment_not_found", Some(detail.clone())),
+            DeliveryError::InvalidParcel(detail) => ("invalid_parcel", Some(detail.clone())),
+            DeliveryError::InvalidAddress(detail) => ("invalid_address", Some(detail.clone())),
+            DeliveryError::Validation(report) => ("validation", Some(report.localize_with(catalog, locale))),
+            DeliveryError::IneligibleParcel(violations) => {
+                let detail = violations.iter()
+                    .map(|violation| violation.localize_with(catalog, locale))
+                    .collect::<Vec<_>>()
+                    .join("; ");
+                ("ineligible_parcel", Some(detail))
+            }
+            DeliveryError::RateUnavailable => ("rate_unavailable", None),
+            DeliveryError::AuthenticationError => ("authentication", None),
+            DeliveryError::LabelGenerationError(detail) => ("label_generation", Some(detail.clone())),
+            DeliveryError::ConnectionError(detail) => ("connection", Some(detail.clone())),
+            DeliveryError::PrintError(detail) => ("print", Some(detail.clone())),
+            DeliveryError::ManifestError(detail) => ("manifest", Some(detail.clone())),
+            DeliveryError::InvalidTransition(detail) => ("invalid_transition", Some(detail.clone())),
+            DeliveryError::NotificationError(detail) => ("notification", Some(detail.clone())),
+            DeliveryError::SerializationError(detail) => ("serialization", Some(detail.clone())),
+            DeliveryError::OperationCancelled(detail) => ("operation_cancelled", Some(detail.clone())),
+            DeliveryError::InternalError(detail) => ("internal", Some(detail.clone())),
+            DeliveryError::HttpError(e) => ("http", Some(e.to_string())),
+            DeliveryError::IoError(e) => ("io", Some(e.to_string())),
+            DeliveryError::JsonError(e) => ("json", Some(e.to_string())),
+            DeliveryError::Unknown(detail) => ("unknown", Some(detail.clone())),
+        };
+
+        let params: BTreeMap<String, String> = detail.into_iter().map(|detail| ("detail".to_string(), detail)).collect();
+        catalog.translate(locale, &format!("error.{}", key), &params)
+            .unwrap_or_else(|| self.to_string())
+    }
+}
@@ -0,0 +1,116 @@
+use crate::errors::DeliveryError;
+
+/// Largeurs des barres et espaces de chaque symbole Code 128 (valeurs 0 à 106)
+const PATTERNS: [&str; 107] = [
+    "212222", "222122", "222221", "121223", "121322", "131222", "122213", "122312", "132212", "221213",
+    "221312", "231212", "112232", "122132", "122231", "113222", "123122", "123221", "223211", "221132",
+    "221231", "213212", "223112", "312131", "311222", "321122", "321221", "312212", "322112", "322211",
+    "212123", "212321", "232121", "111323", "131123", "131321", "112313", "132113", "132311", "211313",
+    "231113", "231311", "112133", "112331", "132131", "113123", "113321", "133121", "313121", "211331",
+    "231131", "213113", "213311", "213131", "311123", "311321", "331121", "312113", "312311", "332111",
+    "314111", "221411", "431111", "111224", "111422", "121124", "121421", "141122", "141221", "112214",
+    "112412", "122114", "122411", "142112", "142211", "241211", "221114", "413111", "241112", "134111",
+    "111242", "121142", "121241", "114212", "124112", "124211", "411212", "421112", "421211", "212141",
+    "214121", "412121", "111143", "111341", "131141", "114113", "114311", "411113", "411311", "113141",
+    "114131", "311141", "411131", "211412", "211214", "211232", "2331112",
+];
+
+const CODE_B: usize = 100;
+const CODE_C: usize = 99;
+const START_B: usize = 104;
+const START_C: usize = 105;
+const STOP: usize = 106;
+
+/// Jeu de caractères Code 128 utilisé pour une portion des données
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CodeSet {
+    B,
+    C,
+}
+
+/// Compte les chiffres consécutifs à partir d'une position
+fn digit_run(bytes: &[u8], start: usize) -> usize {
+    bytes[start..].iter().take_while(|b| b.is_ascii_digit()).count()
+}
+
+/// Encode des données en valeurs de symboles Code 128 (départ, données, clé de contrôle, arrêt)
+///
+/// Les suites d'au moins 4 chiffres sont compactées en jeu C, le reste est encodé en jeu B.
+pub fn encode_values(data: &str) -> Result<Vec<usize>, DeliveryError> {
+    if data.is_empty() {
+        return Err(DeliveryError::LabelGenerationError("Code 128: données vides".to_string()));
+    }
+
+    if let Some(c) = data.chars().find(|c| !(' '..='~').contains(c)) {
+        return Err(DeliveryError::LabelGenerationError(format!(
+            "Code 128: caractère non supporté '{}'", c
+        )));
+    }
+
+    let bytes = data.as_bytes();
+    let mut values = Vec::with_capacity(bytes.len() + 4);
+
+    let leading_digits = digit_run(bytes, 0);
+    let mut set = if leading_digits >= 4 || leading_digits == bytes.len() && leading_digits.is_multiple_of(2) {
+        values.push(START_C);
+        CodeSet::C
+    } else {
+        values.push(START_B);
+        CodeSet::B
+    };
+
+    let mut i = 0;
+    while i < bytes.len() {
+        let run = digit_run(bytes, i);
+
+        match set {
+            CodeSet::C if run >= 2 => {
+                values.push(((bytes[i] - b'0') * 10 + (bytes[i + 1] - b'0')) as usize);
+                i += 2;
+            }
+            CodeSet::C => {
+                values.push(CODE_B);
+                set = CodeSet::B;
+            }
+            // En fin de données, 4 chiffres suffisent à rentabiliser le changement de jeu
+            CodeSet::B if run >= 6 || (run >= 4 && i + run == bytes.len()) => {
+                // Un chiffre impair est encodé en jeu B avant de basculer en jeu C
+                if run % 2 == 1 {
+                    values.push((bytes[i] - b' ') as usize);
+                    i += 1;
+                }
+                values.push(CODE_C);
+                set = CodeSet::C;
+            }
+            CodeSet::B => {
+                values.push((bytes[i] - b' ') as usize);
+                i += 1;
+            }
+        }
+    }
+
+    let checksum = values.iter()
+        .enumerate()
+        .map(|(position, value)| position.max(1) * value)
+        .sum::<usize>() % 103;
+
+    values.push(checksum);
+    values.push(STOP);
+
+    Ok(values)
+}
+
+/// Encode des données en modules (true = barre) sans zone de silence
+pub fn encode(data: &str) -> Result<Vec<bool>, DeliveryError> {
+    let values = encode_values(data)?;
+    let mut modules = Vec::with_capacity(values.len() * 11 + 2);
+
+    for value in values {
+        for (index, width) in PATTERNS[value].bytes().enumerate() {
+            let is_bar = index % 2 == 0;
+            modules.extend(std::iter::repeat_n(is_bar, (width - b'0') as usize));
+        }
+    }
+
+    Ok(modules)
+}
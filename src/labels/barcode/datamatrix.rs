@@ -0,0 +1,344 @@
+use crate::errors::DeliveryError;
+
+/// Caractéristiques d'un symbole DataMatrix ECC 200 carré
+struct SymbolSize {
+    /// Taille du symbole en modules, motifs de repérage compris
+    size: usize,
+    /// Taille d'une région de données en modules
+    region: usize,
+    data_codewords: usize,
+    ecc_codewords: usize,
+    /// Nombre de blocs Reed-Solomon entrelacés
+    blocks: usize,
+}
+
+const fn symbol(size: usize, region: usize, data_codewords: usize, ecc_codewords: usize, blocks: usize) -> SymbolSize {
+    SymbolSize { size, region, data_codewords, ecc_codewords, blocks }
+}
+
+/// Tailles carrées ECC 200 supportées (jusqu'à 132 × 132)
+const SYMBOL_SIZES: [SymbolSize; 23] = [
+    symbol(10, 8, 3, 5, 1),
+    symbol(12, 10, 5, 7, 1),
+    symbol(14, 12, 8, 10, 1),
+    symbol(16, 14, 12, 12, 1),
+    symbol(18, 16, 18, 14, 1),
+    symbol(20, 18, 22, 18, 1),
+    symbol(22, 20, 30, 20, 1),
+    symbol(24, 22, 36, 24, 1),
+    symbol(26, 24, 44, 28, 1),
+    symbol(32, 14, 62, 36, 1),
+    symbol(36, 16, 86, 42, 1),
+    symbol(40, 18, 114, 48, 1),
+    symbol(44, 20, 144, 56, 1),
+    symbol(48, 22, 174, 68, 1),
+    symbol(52, 24, 204, 84, 2),
+    symbol(64, 14, 280, 112, 2),
+    symbol(72, 16, 368, 144, 4),
+    symbol(80, 18, 456, 192, 4),
+    symbol(88, 20, 576, 224, 4),
+    symbol(96, 22, 696, 272, 4),
+    symbol(104, 24, 816, 336, 6),
+    symbol(120, 18, 1050, 408, 6),
+    symbol(132, 20, 1304, 496, 8),
+];
+
+/// Symbole DataMatrix encodé
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataMatrix {
+    /// Taille du symbole en modules
+    pub size: usize,
+    /// Modules ligne par ligne, du haut vers le bas (true = module foncé)
+    pub modules: Vec<bool>,
+}
+
+impl DataMatrix {
+    /// Indique si le module à la ligne et à la colonne données est foncé
+    pub fn is_dark(&self, row: usize, col: usize) -> bool {
+        self.modules[row * self.size + col]
+    }
+}
+
+/// Encode des données en mode ASCII (paires de chiffres compactées, Upper Shift au-delà de 127)
+fn encode_ascii(data: &[u8]) -> Vec<u8> {
+    let mut codewords = Vec::with_capacity(data.len());
+    let mut i = 0;
+
+    while i < data.len() {
+        let byte = data[i];
+
+        if byte.is_ascii_digit() && data.get(i + 1).is_some_and(|next| next.is_ascii_digit()) {
+            codewords.push(130 + (byte - b'0') * 10 + (data[i + 1] - b'0'));
+            i += 2;
+            continue;
+        }
+
+        if byte > 127 {
+            codewords.push(235);
+            codewords.push(byte - 127);
+        } else {
+            codewords.push(byte + 1);
+        }
+        i += 1;
+    }
+
+    codewords
+}
+
+/// Tables logarithmiques de GF(256) avec le polynôme primitif x⁸ + x⁵ + x³ + x² + 1
+fn galois_tables() -> ([u8; 256], [u8; 256]) {
+    let mut exp = [0u8; 256];
+    let mut log = [0u8; 256];
+    let mut value: u16 = 1;
+
+    for (power, slot) in exp.iter_mut().enumerate().take(255) {
+        *slot = value as u8;
+        log[value as usize] = power as u8;
+        value <<= 1;
+        if value & 0x100 != 0 {
+            value ^= 0x12D;
+        }
+    }
+
+    (exp, log)
+}
+
+/// Calcule les mots de correction Reed-Solomon d'un bloc
+fn reed_solomon(data: &[u8], ecc_len: usize) -> Vec<u8> {
+    let (exp, log) = galois_tables();
+    let multiply = |a: u8, b: u8| -> u8 {
+        if a == 0 || b == 0 {
+            0
+        } else {
+            exp[(log[a as usize] as usize + log[b as usize] as usize) % 255]
+        }
+    };
+
+    // Polynôme générateur (x - α¹)(x - α²)...(x - αⁿ)
+    let mut generator = vec![1u8];
+    for i in 1..=ecc_len {
+        let mut next = vec![0u8; generator.len() + 1];
+        for (j, coefficient) in generator.iter().enumerate() {
+            next[j] ^= *coefficient;
+            next[j + 1] ^= multiply(*coefficient, exp[i % 255]);
+        }
+        generator = next;
+    }
+
+    let mut remainder = vec![0u8; ecc_len];
+    for byte in data {
+        let factor = byte ^ remainder[0];
+        remainder.rotate_left(1);
+        remainder[ecc_len - 1] = 0;
+        for (slot, coefficient) in remainder.iter_mut().zip(&generator[1..]) {
+            *slot ^= multiply(factor, *coefficient);
+        }
+    }
+
+    remainder
+}
+
+/// Place les mots de code dans la matrice de données (ISO/IEC 16022, annexe F)
+struct Placement {
+    rows: usize,
+    cols: usize,
+    /// Pour chaque module : None si non attribué, sinon (mot de code, bit) ou valeur fixe
+    cells: Vec<Option<Cell>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Cell {
+    Bit { codeword: usize, bit: u8 },
+    Fixed(bool),
+}
+
+impl Placement {
+    fn new(rows: usize, cols: usize) -> Self {
+        Self { rows, cols, cells: vec![None; rows * cols] }
+    }
+
+    fn is_free(&self, row: isize, col: isize) -> bool {
+        self.cells[row as usize * self.cols + col as usize].is_none()
+    }
+
+    fn module(&mut self, mut row: isize, mut col: isize, codeword: usize, bit: u8) {
+        let (rows, cols) = (self.rows as isize, self.cols as isize);
+
+        if row < 0 {
+            row += rows;
+            col += 4 - ((rows + 4) % 8);
+        }
+        if col < 0 {
+            col += cols;
+            row += 4 - ((cols + 4) % 8);
+        }
+
+        self.cells[row as usize * self.cols + col as usize] = Some(Cell::Bit { codeword, bit });
+    }
+
+    fn utah(&mut self, row: isize, col: isize, codeword: usize) {
+        let offsets = [(-2, -2), (-2, -1), (-1, -2), (-1, -1), (-1, 0), (0, -2), (0, -1), (0, 0)];
+        for (bit, (dr, dc)) in offsets.into_iter().enumerate() {
+            self.module(row + dr, col + dc, codeword, bit as u8 + 1);
+        }
+    }
+
+    fn corner(&mut self, positions: [(isize, isize); 8], codeword: usize) {
+        for (bit, (row, col)) in positions.into_iter().enumerate() {
+            self.module(row, col, codeword, bit as u8 + 1);
+        }
+    }
+
+    fn fill(&mut self) {
+        let (rows, cols) = (self.rows as isize, self.cols as isize);
+        let mut codeword = 0;
+        let mut row: isize = 4;
+        let mut col: isize = 0;
+
+        loop {
+            if row == rows && col == 0 {
+                self.corner([(rows - 1, 0), (rows - 1, 1), (rows - 1, 2), (0, cols - 2),
+                             (0, cols - 1), (1, cols - 1), (2, cols - 1), (3, cols - 1)], codeword);
+                codeword += 1;
+            }
+            if row == rows - 2 && col == 0 && cols % 4 != 0 {
+                self.corner([(rows - 3, 0), (rows - 2, 0), (rows - 1, 0), (0, cols - 4),
+                             (0, cols - 3), (0, cols - 2), (0, cols - 1), (1, cols - 1)], codeword);
+                codeword += 1;
+            }
+            if row == rows - 2 && col == 0 && cols % 8 == 4 {
+                self.corner([(rows - 3, 0), (rows - 2, 0), (rows - 1, 0), (0, cols - 2),
+                             (0, cols - 1), (1, cols - 1), (2, cols - 1), (3, cols - 1)], codeword);
+                codeword += 1;
+            }
+            if row == rows + 4 && col == 2 && cols % 8 == 0 {
+                self.corner([(rows - 1, 0), (rows - 1, cols - 1), (0, cols - 3), (0, cols - 2),
+                             (0, cols - 1), (1, cols - 3), (1, cols - 2), (1, cols - 1)], codeword);
+                codeword += 1;
+            }
+
+            // Diagonale montante
+            loop {
+                if row < rows && col >= 0 && self.is_free(row, col) {
+                    self.utah(row, col, codeword);
+                    codeword += 1;
+                }
+                row -= 2;
+                col += 2;
+                if row < 0 || col >= cols {
+                    break;
+                }
+            }
+            row += 1;
+            col += 3;
+
+            // Diagonale descendante
+            loop {
+                if row >= 0 && col < cols && self.is_free(row, col) {
+                    self.utah(row, col, codeword);
+                    codeword += 1;
+                }
+                row += 2;
+                col -= 2;
+                if row >= rows || col < 0 {
+                    break;
+                }
+            }
+            row += 3;
+            col += 1;
+
+            if row >= rows && col >= cols {
+                break;
+            }
+        }
+
+        // Coin inférieur droit non couvert : motif fixe
+        let last = self.rows * self.cols - 1;
+        if self.cells[last].is_none() {
+            self.cells[last] = Some(Cell::Fixed(true));
+            self.cells[last - self.cols - 1] = Some(Cell::Fixed(true));
+            self.cells[last - 1] = Some(Cell::Fixed(false));
+            self.cells[last - self.cols] = Some(Cell::Fixed(false));
+        }
+    }
+}
+
+/// Encode des données en symbole DataMatrix ECC 200 carré, de la plus petite taille possible
+pub fn encode(data: &str) -> Result<DataMatrix, DeliveryError> {
+    // Les caractères hors Latin-1 ne sont pas représentables sans ECI
+    let bytes: Vec<u8> = data.chars()
+        .map(|c| u8::try_from(u32::from(c)).unwrap_or(b'?'))
+        .collect();
+    let mut codewords = encode_ascii(&bytes);
+
+    let symbol = SYMBOL_SIZES.iter()
+        .find(|symbol| symbol.data_codewords >= codewords.len())
+        .ok_or_else(|| DeliveryError::LabelGenerationError(format!(
+            "DataMatrix: données trop longues ({} mots de code)", codewords.len()
+        )))?;
+
+    // Bourrage : 129 puis valeurs pseudo-aléatoires (algorithme 253 états)
+    if codewords.len() < symbol.data_codewords {
+        codewords.push(129);
+    }
+    while codewords.len() < symbol.data_codewords {
+        let position = codewords.len() + 1;
+        let mut pad = 129 + ((149 * position) % 253) + 1;
+        if pad > 254 {
+            pad -= 254;
+        }
+        codewords.push(pad as u8);
+    }
+
+    // Correction d'erreurs par blocs entrelacés
+    let ecc_per_block = symbol.ecc_codewords / symbol.blocks;
+    let mut ecc = vec![0u8; symbol.ecc_codewords];
+    for block in 0..symbol.blocks {
+        let block_data: Vec<u8> = codewords.iter().skip(block).step_by(symbol.blocks).copied().collect();
+        for (i, value) in reed_solomon(&block_data, ecc_per_block).into_iter().enumerate() {
+            ecc[block + i * symbol.blocks] = value;
+        }
+    }
+    codewords.extend(ecc);
+
+    // Placement dans la matrice de données (sans motifs de repérage)
+    let regions = symbol.size / (symbol.region + 2);
+    let matrix_size = regions * symbol.region;
+    let mut placement = Placement::new(matrix_size, matrix_size);
+    placement.fill();
+
+    let data_bit = |row: usize, col: usize| -> bool {
+        match placement.cells[row * matrix_size + col] {
+            Some(Cell::Bit { codeword, bit }) => codewords[codeword] & (1 << (8 - bit)) != 0,
+            Some(Cell::Fixed(dark)) => dark,
+            None => false,
+        }
+    };
+
+    // Assemblage du symbole avec les motifs de repérage de chaque région
+    let size = symbol.size;
+    let cell = symbol.region + 2;
+    let mut modules = vec![false; size * size];
+
+    for row in 0..size {
+        for col in 0..size {
+            let (region_row, inner_row) = (row / cell, row % cell);
+            let (region_col, inner_col) = (col / cell, col % cell);
+
+            modules[row * size + col] = if inner_row == cell - 1 || inner_col == 0 {
+                // Bordures pleines (bas et gauche)
+                true
+            } else if inner_row == 0 {
+                // Bordure supérieure alternée
+                inner_col % 2 == 0
+            } else if inner_col == cell - 1 {
+                // Bordure droite alternée
+                inner_row % 2 == 1
+            } else {
+                data_bit(region_row * symbol.region + inner_row - 1, region_col * symbol.region + inner_col - 1)
+            };
+        }
+    }
+
+    Ok(DataMatrix { size, modules })
+}
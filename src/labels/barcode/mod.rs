@@ -0,0 +1,8 @@
+/// Symbologie Code 128 (numéros de suivi)
+pub mod code128;
+
+/// Symbologie DataMatrix ECC 200 (données de tri)
+pub mod datamatrix;
+
+/// Symbologie QR Code
+pub mod qr;
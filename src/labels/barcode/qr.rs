@@ -0,0 +1,30 @@
+use qrcode::{Color, EcLevel, QrCode};
+
+use crate::errors::DeliveryError;
+
+/// Symbole QR Code encodé
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QrMatrix {
+    /// Taille du symbole en modules
+    pub size: usize,
+    /// Modules ligne par ligne, du haut vers le bas (true = module foncé)
+    pub modules: Vec<bool>,
+}
+
+impl QrMatrix {
+    /// Indique si le module à la ligne et à la colonne données est foncé
+    pub fn is_dark(&self, row: usize, col: usize) -> bool {
+        self.modules[row * self.size + col]
+    }
+}
+
+/// Encode des données en QR Code (niveau de correction M)
+pub fn encode(data: &str) -> Result<QrMatrix, DeliveryError> {
+    let code = QrCode::with_error_correction_level(data.as_bytes(), EcLevel::M)
+        .map_err(|e| DeliveryError::LabelGenerationError(format!("QR Code: {}", e)))?;
+
+    Ok(QrMatrix {
+        size: code.width(),
+        modules: code.to_colors().into_iter().map(|color| color == Color::Dark).collect(),
+    })
+}
@@ -0,0 +1,428 @@
+use lopdf::{dictionary, Dictionary, Document, Object, ObjectId, Stream};
+
+use crate::errors::DeliveryError;
+use crate::labels::layout::MM_PER_POINT;
+use crate::models::{LabelFormat, ShippingLabel};
+
+/// Dimensions d'une feuille A4 en points
+const A4_POINTS: (f64, f64) = (210.0 / MM_PER_POINT, 297.0 / MM_PER_POINT);
+
+/// Marge autour de chaque étiquette d'une planche, en points (5 mm)
+const CELL_MARGIN: f64 = 5.0 / MM_PER_POINT;
+
+/// Longueur des traits de coupe, en points (4 mm)
+const CUT_MARK_LENGTH: f64 = 4.0 / MM_PER_POINT;
+
+/// Disposition des étiquettes dans le document fusionné
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SheetLayout {
+    /// Une étiquette par page, à sa taille réelle
+    OnePerPage,
+    /// Deux étiquettes par feuille A4 (haut et bas)
+    TwoPerA4,
+    /// Quatre étiquettes par feuille A4 (2 × 2)
+    FourPerA4,
+}
+
+impl SheetLayout {
+    /// Nombre de colonnes et de lignes d'une planche A4
+    fn grid(&self) -> Option<(usize, usize)> {
+        match self {
+            SheetLayout::OnePerPage => None,
+            SheetLayout::TwoPerA4 => Some((1, 2)),
+            SheetLayout::FourPerA4 => Some((2, 2)),
+        }
+    }
+}
+
+/// Matrice de transformation PDF [a b c d e f]
+type Matrix = [f64; 6];
+
+/// Compose deux transformations : `first` puis `second`
+fn then(first: Matrix, second: Matrix) -> Matrix {
+    [
+        first[0] * second[0] + first[1] * second[2],
+        first[0] * second[1] + first[1] * second[3],
+        first[2] * second[0] + first[3] * second[2],
+        first[2] * second[1] + first[3] * second[3],
+        first[4] * second[0] + first[5] * second[2] + second[4],
+        first[4] * second[1] + first[5] * second[3] + second[5],
+    ]
+}
+
+/// Cellule d'une page (x, y, largeur, hauteur) en points, origine en bas à gauche
+type Cell = (f64, f64, f64, f64);
+
+/// Page d'étiquette importée dans le document fusionné
+#[derive(Clone, Copy)]
+struct LabelPage {
+    /// XObject dessinant l'étiquette
+    xobject: ObjectId,
+    /// Transformation ramenant l'étiquette dans le rectangle [0, largeur] × [0, hauteur]
+    base: Matrix,
+    /// Largeur en points
+    width: f64,
+    /// Hauteur en points
+    height: f64,
+}
+
+/// Lot d'étiquettes à fusionner en un seul travail d'impression
+#[derive(Debug, Clone)]
+pub struct LabelBatch {
+    labels: Vec<ShippingLabel>,
+    sheet: SheetLayout,
+    cut_marks: bool,
+}
+
+impl LabelBatch {
+    /// Crée un lot à partir d'étiquettes, dans l'ordre fourni
+    pub fn new(labels: impl IntoIterator<Item = ShippingLabel>) -> Self {
+        Self {
+            labels: labels.into_iter().collect(),
+            sheet: SheetLayout::OnePerPage,
+            cut_marks: false,
+        }
+    }
+
+    /// Ajoute une étiquette au lot
+    pub fn push(&mut self, label: ShippingLabel) {
+        self.labels.push(label);
+    }
+
+    /// Définit la disposition des étiquettes dans le PDF
+    pub fn with_sheet(mut self, sheet: SheetLayout) -> Self {
+        self.sheet = sheet;
+        self
+    }
+
+    /// Active les traits de coupe entre les étiquettes d'une planche A4
+    pub fn with_cut_marks(mut self, cut_marks: bool) -> Self {
+        self.cut_marks = cut_marks;
+        self
+    }
+
+    /// Trie les étiquettes selon une clé fournie par l'appelant (tri stable)
+    pub fn order_by<K: Ord>(mut self, key: impl FnMut(&ShippingLabel) -> K) -> Self {
+        self.labels.sort_by_key(key);
+        self
+    }
+
+    /// Étiquettes du lot, dans l'ordre d'impression
+    pub fn labels(&self) -> &[ShippingLabel] {
+        &self.labels
+    }
+
+    /// Nombre d'étiquettes du lot
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    /// Indique si le lot est vide
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// Concatène les étiquettes ZPL en un seul travail d'impression
+    pub fn to_zpl(&self) -> Result<Vec<u8>, DeliveryError> {
+        self.ensure_not_empty()?;
+
+        let mut job = Vec::new();
+        for label in &self.labels {
+            if label.label_format != LabelFormat::ZPL {
+                return Err(DeliveryError::LabelGenerationError(format!(
+                    "Étiquette {} au format {:?} : fusion ZPL impossible", label.tracking_number, label.label_format
+                )));
+            }
+
+            job.extend_from_slice(&label.label_data);
+            if !job.ends_with(b"\n") {
+                job.push(b'\n');
+            }
+        }
+
+        Ok(job)
+    }
+
+    /// Fusionne les étiquettes PDF, PNG (et ZPL, converties) en un seul document PDF
+    pub fn to_pdf(&self) -> Result<Vec<u8>, DeliveryError> {
+        self.ensure_not_empty()?;
+
+        let mut document = Document::with_version("1.5");
+        let mut pages = Vec::new();
+
+        for label in &self.labels {
+            match label.label_format {
+                LabelFormat::PDF => pages.extend(import_pdf(&mut document, &label.label_data)?),
+                LabelFormat::PNG => {
+                    // Une image ne porte qu'un exemplaire : répétée selon le nombre demandé
+                    let page = import_png(&mut document, label)?;
+                    pages.extend(std::iter::repeat_n(page, label.copies.max(1) as usize));
+                }
+                LabelFormat::ZPL => {
+                    let converted = label.convert_to(LabelFormat::PDF)?;
+                    pages.extend(import_pdf(&mut document, &converted.label_data)?);
+                }
+            }
+        }
+
+        let pages_id = document.new_object_id();
+        let kids = match self.sheet.grid() {
+            None => pages.iter()
+                .map(|page| add_sheet(&mut document, pages_id, (page.width, page.height), &[(page, full_cell(page))], None))
+                .collect::<Result<Vec<_>, _>>()?,
+            Some((columns, rows)) => {
+                let cells = sheet_cells(columns, rows);
+                let marks = self.cut_marks.then(|| cut_marks(columns, rows));
+
+                pages.chunks(columns * rows)
+                    .map(|chunk| {
+                        let placed: Vec<_> = chunk.iter().zip(&cells).map(|(page, cell)| (page, *cell)).collect();
+                        add_sheet(&mut document, pages_id, A4_POINTS, &placed, marks.as_deref())
+                    })
+                    .collect::<Result<Vec<_>, _>>()?
+            }
+        };
+
+        let count = kids.len() as i64;
+        document.objects.insert(pages_id, Object::Dictionary(dictionary! {
+            "Type" => "Pages",
+            "Kids" => kids.into_iter().map(Object::Reference).collect::<Vec<_>>(),
+            "Count" => count,
+        }));
+
+        let catalog_id = document.add_object(dictionary! {
+            "Type" => "Catalog",
+            "Pages" => pages_id,
+        });
+        document.trailer.set("Root", catalog_id);
+        document.prune_objects();
+        document.compress();
+
+        let mut output = Vec::new();
+        document.save_to(&mut output)
+            .map_err(|e| DeliveryError::LabelGenerationError(format!("Fusion PDF impossible: {}", e)))?;
+        Ok(output)
+    }
+
+    fn ensure_not_empty(&self) -> Result<(), DeliveryError> {
+        if self.labels.is_empty() {
+            return Err(DeliveryError::LabelGenerationError("Lot d'étiquettes vide".to_string()));
+        }
+        Ok(())
+    }
+}
+
+fn pdf_error(e: lopdf::Error) -> DeliveryError {
+    DeliveryError::LabelGenerationError(format!("Fusion PDF impossible: {}", e))
+}
+
+/// Cellule couvrant toute la page d'une étiquette
+fn full_cell(page: &LabelPage) -> Cell {
+    (0.0, 0.0, page.width, page.height)
+}
+
+/// Cellules (x, y, largeur, hauteur) d'une planche A4, de haut en bas et de gauche à droite
+fn sheet_cells(columns: usize, rows: usize) -> Vec<Cell> {
+    let (width, height) = (A4_POINTS.0 / columns as f64, A4_POINTS.1 / rows as f64);
+
+    (0..rows)
+        .flat_map(|row| (0..columns).map(move |column| (column, row)))
+        .map(|(column, row)| {
+            let y = A4_POINTS.1 - (row + 1) as f64 * height;
+            (
+                column as f64 * width + CELL_MARGIN,
+                y + CELL_MARGIN,
+                width - 2.0 * CELL_MARGIN,
+                height - 2.0 * CELL_MARGIN,
+            )
+        })
+        .collect()
+}
+
+/// Traits de coupe aux limites des cellules, sur les bords de la feuille et aux intersections
+fn cut_marks(columns: usize, rows: usize) -> String {
+    let (width, height) = A4_POINTS;
+    let mut marks = String::from("0 G 0.5 w\n");
+    let mut line = |x1: f64, y1: f64, x2: f64, y2: f64| {
+        marks.push_str(&format!("{:.2} {:.2} m {:.2} {:.2} l S\n", x1, y1, x2, y2));
+    };
+
+    let xs: Vec<f64> = (1..columns).map(|column| column as f64 * width / columns as f64).collect();
+    let ys: Vec<f64> = (1..rows).map(|row| row as f64 * height / rows as f64).collect();
+
+    for x in &xs {
+        line(*x, 0.0, *x, CUT_MARK_LENGTH);
+        line(*x, height - CUT_MARK_LENGTH, *x, height);
+    }
+    for y in &ys {
+        line(0.0, *y, CUT_MARK_LENGTH, *y);
+        line(width - CUT_MARK_LENGTH, *y, width, *y);
+    }
+    for x in &xs {
+        for y in &ys {
+            line(x - CUT_MARK_LENGTH / 2.0, *y, x + CUT_MARK_LENGTH / 2.0, *y);
+            line(*x, y - CUT_MARK_LENGTH / 2.0, *x, y + CUT_MARK_LENGTH / 2.0);
+        }
+    }
+
+    marks
+}
+
+/// Ajoute une page portant des étiquettes placées dans leurs cellules
+fn add_sheet(
+    document: &mut Document,
+    pages_id: ObjectId,
+    size: (f64, f64),
+    placed: &[(&LabelPage, Cell)],
+    marks: Option<&str>,
+) -> Result<ObjectId, DeliveryError> {
+    let mut content = String::new();
+    let mut xobjects = Dictionary::new();
+
+    for (index, (page, cell)) in placed.iter().enumerate() {
+        let name = format!("L{}", index);
+        let m = then(page.base, fit_in_cell(page, *cell));
+        content.push_str(&format!(
+            "q {:.4} {:.4} {:.4} {:.4} {:.4} {:.4} cm /{} Do Q\n",
+            m[0], m[1], m[2], m[3], m[4], m[5], name
+        ));
+        xobjects.set(name, page.xobject);
+    }
+
+    if let Some(marks) = marks {
+        content.push_str(marks);
+    }
+
+    let content_id = document.add_object(Stream::new(Dictionary::new(), content.into_bytes()));
+
+    Ok(document.add_object(dictionary! {
+        "Type" => "Page",
+        "Parent" => pages_id,
+        "MediaBox" => vec![Object::Integer(0), Object::Integer(0), Object::Real(size.0 as f32), Object::Real(size.1 as f32)],
+        "Contents" => content_id,
+        "Resources" => dictionary! { "XObject" => xobjects },
+    }))
+}
+
+/// Place une étiquette au centre d'une cellule, sans l'agrandir,
+/// en la tournant d'un quart de tour si elle y gagne en taille
+fn fit_in_cell(page: &LabelPage, cell: Cell) -> Matrix {
+    let (x, y, width, height) = cell;
+    let upright = (width / page.width).min(height / page.height).min(1.0);
+    let turned = (width / page.height).min(height / page.width).min(1.0);
+
+    if turned > upright + 1e-6 {
+        // Rotation horaire : l'étiquette occupe [0, hauteur] × [0, largeur]
+        let (w, h) = (page.height * turned, page.width * turned);
+        let rotate = [0.0, -turned, turned, 0.0, 0.0, page.width * turned];
+        then(rotate, [1.0, 0.0, 0.0, 1.0, x + (width - w) / 2.0, y + (height - h) / 2.0])
+    } else {
+        let (w, h) = (page.width * upright, page.height * upright);
+        [upright, 0.0, 0.0, upright, x + (width - w) / 2.0, y + (height - h) / 2.0]
+    }
+}
+
+/// Valeur héritée d'un nœud de l'arbre des pages (Resources, MediaBox, Rotate)
+fn inherited<'a>(source: &'a Document, page_id: ObjectId, key: &[u8]) -> Option<&'a Object> {
+    let mut node = source.get_dictionary(page_id).ok()?;
+
+    loop {
+        if let Ok(value) = node.get(key) {
+            return source.dereference(value).ok().map(|(_, object)| object);
+        }
+        let parent = node.get(b"Parent").and_then(Object::as_reference).ok()?;
+        node = source.get_dictionary(parent).ok()?;
+    }
+}
+
+/// Importe les pages d'un PDF sous forme de XObjects de formulaire
+fn import_pdf(document: &mut Document, data: &[u8]) -> Result<Vec<LabelPage>, DeliveryError> {
+    let mut source = Document::load_mem(data).map_err(pdf_error)?;
+    source.renumber_objects_with(document.max_id + 1);
+    document.max_id = source.objects.keys().map(|(id, _)| *id).max().unwrap_or(0).max(document.max_id);
+
+    let mut pages = Vec::new();
+    for page_id in source.get_pages().into_values() {
+        let media_box: Vec<f64> = inherited(&source, page_id, b"MediaBox")
+            .and_then(|object| object.as_array().ok())
+            .map(|values| values.iter().filter_map(|value| value.as_float().ok()).map(f64::from).collect())
+            .filter(|values: &Vec<f64>| values.len() == 4)
+            .unwrap_or_else(|| vec![0.0, 0.0, A4_POINTS.0, A4_POINTS.1]);
+        let resources = inherited(&source, page_id, b"Resources").cloned().unwrap_or(Object::Dictionary(Dictionary::new()));
+        let rotation = inherited(&source, page_id, b"Rotate").and_then(|object| object.as_i64().ok()).unwrap_or(0).rem_euclid(360);
+        let content = source.get_page_content(page_id).map_err(pdf_error)?;
+
+        let (x0, y0, x1, y1) = (media_box[0], media_box[1], media_box[2], media_box[3]);
+        let (width, height) = (x1 - x0, y1 - y0);
+
+        let form = Stream::new(dictionary! {
+            "Type" => "XObject",
+            "Subtype" => "Form",
+            "BBox" => media_box.iter().map(|value| Object::Real(*value as f32)).collect::<Vec<_>>(),
+            "Resources" => resources,
+        }, content);
+        let xobject = document.add_object(form);
+
+        // Ramène la MediaBox à l'origine puis applique la rotation d'affichage de la page (/Rotate, sens horaire)
+        let origin = [1.0, 0.0, 0.0, 1.0, -x0, -y0];
+        let (base, width, height) = match rotation {
+            90 => (then(origin, [0.0, -1.0, 1.0, 0.0, 0.0, width]), height, width),
+            180 => (then(origin, [-1.0, 0.0, 0.0, -1.0, width, height]), width, height),
+            270 => (then(origin, [0.0, 1.0, -1.0, 0.0, height, 0.0]), height, width),
+            _ => (origin, width, height),
+        };
+
+        pages.push(LabelPage { xobject, base, width, height });
+    }
+
+    // Les objets de la source non référencés (catalogue, arbre des pages) seront élagués
+    document.objects.extend(source.objects);
+    Ok(pages)
+}
+
+/// Importe une image PNG sous forme de XObject image, à sa taille physique
+fn import_png(document: &mut Document, label: &ShippingLabel) -> Result<LabelPage, DeliveryError> {
+    let to_error = |e: png::DecodingError| DeliveryError::LabelGenerationError(format!("Lecture PNG impossible: {}", e));
+
+    let mut decoder = png::Decoder::new(label.label_data.as_slice());
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().map_err(to_error)?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let frame = reader.next_frame(&mut buffer).map_err(to_error)?;
+    let pixels = &buffer[..frame.buffer_size()];
+
+    // Transparence composée sur fond blanc
+    let over_white = |value: u8, alpha: u8| 255 - ((255 - value as u16) * alpha as u16 / 255) as u8;
+    let (color_space, samples): (&str, Vec<u8>) = match frame.color_type {
+        png::ColorType::Grayscale => ("DeviceGray", pixels.to_vec()),
+        png::ColorType::GrayscaleAlpha => ("DeviceGray", pixels.chunks(2).map(|p| over_white(p[0], p[1])).collect()),
+        png::ColorType::Rgb => ("DeviceRGB", pixels.to_vec()),
+        png::ColorType::Rgba => ("DeviceRGB", pixels.chunks(4).flat_map(|p| [0, 1, 2].map(|i| over_white(p[i], p[3]))).collect()),
+        png::ColorType::Indexed => return Err(DeliveryError::LabelGenerationError("PNG indexé non supporté".to_string())),
+    };
+
+    // Résolution : en-tête pHYs, sinon celle annoncée par l'étiquette, sinon 203 dpi
+    let dpi = reader.info().pixel_dims
+        .filter(|dims| dims.unit == png::Unit::Meter && dims.xppu > 0)
+        .map(|dims| dims.xppu as f64 * 0.0254)
+        .or_else(|| label.dimensions.and_then(|dimensions| dimensions.dpi).map(f64::from))
+        .unwrap_or(crate::labels::DEFAULT_DPI as f64);
+    let width = frame.width as f64 * 72.0 / dpi;
+    let height = frame.height as f64 * 72.0 / dpi;
+
+    let image = Stream::new(dictionary! {
+        "Type" => "XObject",
+        "Subtype" => "Image",
+        "Width" => frame.width as i64,
+        "Height" => frame.height as i64,
+        "ColorSpace" => color_space,
+        "BitsPerComponent" => 8,
+    }, samples);
+
+    Ok(LabelPage {
+        xobject: document.add_object(image),
+        base: [width, 0.0, 0.0, height, 0.0, 0.0],
+        width,
+        height,
+    })
+}
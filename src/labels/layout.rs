@@ -0,0 +1,265 @@
+use pdf_canvas::{BuiltinFont, FontSource};
+
+use crate::errors::DeliveryError;
+use crate::labels::barcode::{code128, datamatrix, qr};
+use crate::models::{LabelRotation, LabelSize, Parcel, Rate};
+use crate::utils::formatting::format_address;
+
+/// Nombre de mm dans un point typographique
+pub const MM_PER_POINT: f64 = 25.4 / 72.0;
+
+/// Marge intérieure de l'étiquette en mm
+const MARGIN: f64 = 4.0;
+
+/// Élément graphique d'une étiquette (coordonnées en mm depuis le coin supérieur gauche)
+#[derive(Debug, Clone, PartialEq)]
+pub enum LabelElement {
+    /// Texte dont (x, y) est le coin supérieur gauche, taille en points
+    Text { x: f64, y: f64, size: f64, bold: bool, text: String },
+    /// Cadre d'épaisseur donnée (plein si l'épaisseur couvre la largeur ou la hauteur)
+    Rectangle { x: f64, y: f64, width: f64, height: f64, thickness: f64 },
+    /// Code-barres Code 128, largeur d'un module en mm
+    Code128 { x: f64, y: f64, module_width: f64, height: f64, data: String },
+    /// Code-barres DataMatrix, taille d'un module en mm
+    DataMatrix { x: f64, y: f64, module_size: f64, data: String },
+    /// Code-barres QR Code, taille d'un module en mm
+    QrCode { x: f64, y: f64, module_size: f64, data: String },
+}
+
+/// Mise en page d'une étiquette, indépendante du format de sortie
+#[derive(Debug, Clone, PartialEq)]
+pub struct LabelLayout {
+    /// Largeur en mm
+    pub width: f64,
+    /// Hauteur en mm
+    pub height: f64,
+    pub elements: Vec<LabelElement>,
+    /// Rotation appliquée au rendu
+    pub rotation: LabelRotation,
+}
+
+impl LabelLayout {
+    /// Crée une mise en page vide
+    pub fn new(width: f64, height: f64) -> Self {
+        Self { width, height, elements: Vec::new(), rotation: LabelRotation::None }
+    }
+
+    /// Définit la rotation appliquée au rendu
+    pub fn with_rotation(mut self, rotation: LabelRotation) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Dimensions du rendu en mm (largeur, hauteur), rotation comprise
+    pub fn output_dimensions(&self) -> (f64, f64) {
+        if self.rotation.is_quarter_turn() {
+            (self.height, self.width)
+        } else {
+            (self.width, self.height)
+        }
+    }
+
+    /// Recopie les éléments d'une autre mise en page, décalés à la position donnée
+    pub fn place(&mut self, other: &LabelLayout, x: f64, y: f64) {
+        self.elements.extend(other.elements.iter().cloned().map(|mut element| {
+            let (element_x, element_y) = element.origin_mut();
+            *element_x += x;
+            *element_y += y;
+            element
+        }));
+    }
+
+    /// Ajoute un texte
+    pub fn text(&mut self, x: f64, y: f64, size: f64, bold: bool, text: &str) {
+        self.elements.push(LabelElement::Text { x, y, size, bold, text: text.to_string() });
+    }
+
+    /// Ajoute un cadre
+    pub fn rectangle(&mut self, x: f64, y: f64, width: f64, height: f64, thickness: f64) {
+        self.elements.push(LabelElement::Rectangle { x, y, width, height, thickness });
+    }
+
+    /// Ajoute un trait horizontal sur toute la largeur utile
+    pub fn separator(&mut self, y: f64) {
+        self.rectangle(MARGIN / 2.0, y, self.width - MARGIN, 0.4, 0.4);
+    }
+
+    /// Ajoute un code-barres Code 128
+    pub fn code128(&mut self, x: f64, y: f64, module_width: f64, height: f64, data: &str) {
+        self.elements.push(LabelElement::Code128 { x, y, module_width, height, data: data.to_string() });
+    }
+
+    /// Ajoute un code-barres DataMatrix
+    pub fn datamatrix(&mut self, x: f64, y: f64, module_size: f64, data: &str) {
+        self.elements.push(LabelElement::DataMatrix { x, y, module_size, data: data.to_string() });
+    }
+
+    /// Ajoute un code-barres QR Code
+    pub fn qrcode(&mut self, x: f64, y: f64, module_size: f64, data: &str) {
+        self.elements.push(LabelElement::QrCode { x, y, module_size, data: data.to_string() });
+    }
+}
+
+impl LabelElement {
+    /// Coin supérieur gauche de l'élément
+    pub(crate) fn origin_mut(&mut self) -> (&mut f64, &mut f64) {
+        match self {
+            LabelElement::Text { x, y, .. }
+            | LabelElement::Rectangle { x, y, .. }
+            | LabelElement::Code128 { x, y, .. }
+            | LabelElement::DataMatrix { x, y, .. }
+            | LabelElement::QrCode { x, y, .. } => (x, y),
+        }
+    }
+
+    /// Modules d'un code-barres matriciel : taille du symbole et modules ligne par ligne
+    pub(crate) fn matrix_modules(&self) -> Result<Option<(usize, Vec<bool>)>, DeliveryError> {
+        match self {
+            LabelElement::DataMatrix { data, .. } => {
+                let matrix = datamatrix::encode(data)?;
+                Ok(Some((matrix.size, matrix.modules)))
+            }
+            LabelElement::QrCode { data, .. } => {
+                let matrix = qr::encode(data)?;
+                Ok(Some((matrix.size, matrix.modules)))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Police PDF correspondant à un texte d'étiquette
+pub(crate) fn font_for(bold: bool) -> BuiltinFont {
+    if bold { BuiltinFont::Helvetica_Bold } else { BuiltinFont::Helvetica }
+}
+
+/// Largeur approximative d'un texte en mm
+pub fn text_width(text: &str, size: f64, bold: bool) -> f64 {
+    font_for(bold).get_width(size as f32, text) as f64 * MM_PER_POINT
+}
+
+/// Tronque un texte pour qu'il tienne dans la largeur donnée
+pub(crate) fn fit_text(text: &str, size: f64, bold: bool, max_width: f64) -> String {
+    let mut fitted = text.trim().to_string();
+    while !fitted.is_empty() && text_width(&fitted, size, bold) > max_width {
+        fitted.pop();
+    }
+    fitted
+}
+
+/// Ajoute un bloc d'adresse et retourne l'ordonnée sous le bloc
+fn address_block(layout: &mut LabelLayout, y: f64, title: &str, address: &str, size: f64, bold: bool, max_lines: usize) -> f64 {
+    let max_width = layout.width - 2.0 * MARGIN;
+    layout.text(MARGIN, y, 7.0, true, title);
+
+    let mut line_y = y + 4.0;
+    let line_height = size * MM_PER_POINT * 1.25;
+
+    for line in address.lines().filter(|line| !line.trim().is_empty()).take(max_lines) {
+        layout.text(MARGIN, line_y, size, bold, &fit_text(line, size, bold, max_width));
+        line_y += line_height;
+    }
+
+    line_y
+}
+
+/// Calcule la largeur de module Code 128 pour occuper la largeur disponible,
+/// arrondie au point d'impression à 203 dpi (0,125 mm)
+fn code128_module_width(data: &str, available: f64) -> Result<f64, DeliveryError> {
+    // 10 modules de zone de silence de chaque côté
+    let modules = code128::encode(data)?.len() as f64 + 20.0;
+    let width = ((available / modules) / 0.125).floor() * 0.125;
+    Ok(width.clamp(0.25, 0.5))
+}
+
+/// Marge de la feuille A4 autour de l'étiquette en mm
+const A4_MARGIN: f64 = 10.0;
+
+/// Construit la mise en page standard d'une étiquette d'expédition
+///
+/// En A4, une étiquette 10 × 15 cm est placée dans le coin supérieur gauche de la feuille.
+pub fn shipping_label_layout(
+    parcel: &Parcel,
+    rate: &Rate,
+    tracking_number: &str,
+    size: LabelSize,
+) -> Result<LabelLayout, DeliveryError> {
+    if size == LabelSize::A4 {
+        let label = shipping_label_layout(parcel, rate, tracking_number, LabelSize::Cm10x15)?;
+        let (width, height) = size.dimensions_mm();
+        let mut page = LabelLayout::new(width, height);
+        page.place(&label, A4_MARGIN, A4_MARGIN);
+        return Ok(page);
+    }
+
+    let (width, height) = size.dimensions_mm();
+    let mut layout = LabelLayout::new(width, height);
+    let right = width - MARGIN;
+
+    layout.rectangle(1.0, 1.0, width - 2.0, height - 2.0, 0.5);
+
+    // En-tête : transporteur, service, poids
+    layout.text(MARGIN, 4.0, 16.0, true, &rate.carrier.to_string().to_uppercase());
+    layout.text(MARGIN, 11.5, 9.0, false, &fit_text(&rate.service, 9.0, false, width / 2.0));
+
+    let weight = format!("{:.2} kg", parcel.weight);
+    layout.text(right - text_width(&weight, 12.0, true), 4.0, 12.0, true, &weight);
+
+    if let Some(billable) = rate.billable_weight.filter(|billable| *billable > parcel.weight) {
+        let billable = format!("Facturé {:.2} kg", billable);
+        layout.text(right - text_width(&billable, 7.0, false), 10.0, 7.0, false, &billable);
+    }
+
+    let date = chrono::Utc::now().format("%d/%m/%Y").to_string();
+    layout.text(right - text_width(&date, 7.0, false), 14.0, 7.0, false, &date);
+    layout.separator(19.0);
+
+    // Expéditeur puis destinataire
+    let sender_end = address_block(&mut layout, 21.0, "EXPÉDITEUR", &format_address(&parcel.sender), 7.5, false, 6);
+    let separator_y = sender_end.max(48.0) + 1.0;
+    layout.separator(separator_y);
+
+    address_block(&mut layout, separator_y + 2.0, "DESTINATAIRE", &format_address(&parcel.recipient), 11.0, true, 7);
+    layout.separator(height * 0.60);
+
+    // Bloc service et DataMatrix
+    let block_y = height * 0.60 + 2.0;
+    let matrix_data = format!(
+        "{}|{}|{}|{}",
+        tracking_number, parcel.recipient.postal_code, parcel.recipient.country, rate.service_code
+    );
+    let matrix_side = 24.0;
+    let matrix = datamatrix::encode(&matrix_data)?;
+    let module_size = matrix_side / matrix.size as f64;
+    layout.datamatrix(right - matrix_side, block_y, module_size, &matrix_data);
+
+    let service_width = width - 3.0 * MARGIN - matrix_side;
+    layout.text(MARGIN, block_y, 22.0, true, &fit_text(&rate.service_code, 22.0, true, service_width));
+
+    let mut info_y = block_y + 11.0;
+    let details = [
+        parcel.reference.as_ref().map(|reference| format!("Réf: {}", reference)),
+        parcel.description.as_ref().map(|description| description.to_string()),
+        parcel.is_return.then(|| "RETOUR".to_string()),
+    ];
+    for detail in details.into_iter().flatten() {
+        layout.text(MARGIN, info_y, 8.0, false, &fit_text(&detail, 8.0, false, service_width));
+        info_y += 4.0;
+    }
+
+    let barcode_y = block_y + matrix_side + 3.0;
+    layout.separator(barcode_y - 1.5);
+
+    // Code-barres du numéro de suivi
+    let available = width - 2.0 * MARGIN;
+    let module_width = code128_module_width(tracking_number, available)?;
+    let barcode_width = (code128::encode(tracking_number)?.len() as f64) * module_width;
+    let barcode_height = (height - barcode_y - 12.0).clamp(10.0, 25.0);
+    layout.code128((width - barcode_width) / 2.0, barcode_y + 1.5, module_width, barcode_height, tracking_number);
+
+    let text_y = barcode_y + barcode_height + 3.0;
+    let tracking_width = text_width(tracking_number, 11.0, true);
+    layout.text((width - tracking_width) / 2.0, text_y, 11.0, true, tracking_number);
+
+    Ok(layout)
+}
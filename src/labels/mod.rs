@@ -0,0 +1,101 @@
+/// Encodeurs de codes-barres
+pub mod barcode;
+
+/// Fusion d'étiquettes en lots d'impression
+pub mod batch;
+
+/// Mise en page des étiquettes
+pub mod layout;
+
+/// Rendu PDF des étiquettes
+pub mod pdf;
+
+/// Rendu PNG monochrome des étiquettes
+pub mod raster;
+
+/// Génération et interprétation du ZPL II (imprimantes Zebra)
+pub mod zpl;
+
+mod font;
+
+use chrono::Utc;
+
+use crate::errors::DeliveryError;
+use crate::models::{LabelDimensions, LabelFormat, LabelOptions, Parcel, Rate, ShippingLabel};
+
+/// Résolution par défaut des imprimantes thermiques (8 points/mm)
+pub const DEFAULT_DPI: u32 = 203;
+
+/// Taille d'un module des QR Codes autonomes, en mm (4 points à 203 dpi)
+const QR_MODULE_MM: f64 = 0.5;
+
+/// Formats que le moteur de rendu intégré sait produire
+pub const RENDERED_FORMATS: [LabelFormat; 3] = [LabelFormat::PDF, LabelFormat::ZPL, LabelFormat::PNG];
+
+/// Produit les données d'une mise en page dans le format demandé
+///
+/// Les exemplaires sont répétés en pages PDF ou demandés par ^PQ en ZPL ;
+/// une image PNG ne contient qu'un exemplaire.
+pub fn render_layout(
+    layout: &layout::LabelLayout,
+    format: LabelFormat,
+    dpi: u32,
+    copies: u32,
+) -> Result<Vec<u8>, DeliveryError> {
+    match format {
+        LabelFormat::PDF => pdf::render_pdf_pages(&vec![layout.clone(); copies.max(1) as usize]),
+        LabelFormat::PNG => raster::render_png(layout, dpi),
+        LabelFormat::ZPL => Ok(zpl::with_copies(&zpl::to_zpl(layout, dpi)?, copies).into_bytes()),
+    }
+}
+
+/// Dimensions réelles d'une mise en page rendue dans un format
+pub fn layout_dimensions(layout: &layout::LabelLayout, format: LabelFormat, dpi: u32) -> LabelDimensions {
+    let (width_mm, height_mm) = layout.output_dimensions();
+
+    LabelDimensions {
+        width_mm,
+        height_mm,
+        dpi: (format != LabelFormat::PDF).then_some(dpi),
+        rotation: layout.rotation,
+    }
+}
+
+/// Génère une étiquette imprimable pour un colis, un tarif et un numéro de suivi
+///
+/// Utile aux transporteurs dont l'API ne renvoie que des données brutes.
+/// Le format retenu est le premier format préféré des options.
+pub fn render_label(
+    parcel: &Parcel,
+    rate: &Rate,
+    tracking_number: &str,
+    options: &LabelOptions,
+) -> Result<ShippingLabel, DeliveryError> {
+    let format = options.negotiate(&RENDERED_FORMATS)?;
+    let dpi = options.dpi.value();
+    let layout = layout::shipping_label_layout(parcel, rate, tracking_number, options.size)?
+        .with_rotation(options.rotation);
+
+    Ok(ShippingLabel {
+        carrier: rate.carrier,
+        tracking_number: tracking_number.to_string(),
+        label_format: format,
+        label_data: render_layout(&layout, format, dpi, options.copies)?,
+        created_at: Utc::now(),
+        expires_at: None,
+        dimensions: Some(layout_dimensions(&layout, format, dpi)),
+        copies: options.copies.max(1),
+    })
+}
+
+/// Produit l'image PNG d'un QR Code seul, marge de 4 modules comprise
+///
+/// Sert aux QR Codes de dépôt en point relais, affichés sur le téléphone du client.
+pub fn render_qr_code(data: &str) -> Result<Vec<u8>, DeliveryError> {
+    let quiet_zone = 4.0 * QR_MODULE_MM;
+    let side = barcode::qr::encode(data)?.size as f64 * QR_MODULE_MM + 2.0 * quiet_zone;
+
+    let mut layout = layout::LabelLayout::new(side, side);
+    layout.qrcode(quiet_zone, quiet_zone, QR_MODULE_MM, data);
+    raster::render_png(&layout, DEFAULT_DPI)
+}
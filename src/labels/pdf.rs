@@ -0,0 +1,114 @@
+use std::io;
+
+use pdf_canvas::graphicsstate::Matrix;
+use pdf_canvas::{Canvas, Pdf};
+
+use crate::errors::DeliveryError;
+use crate::labels::barcode::code128;
+use crate::labels::layout::{font_for, LabelElement, LabelLayout, MM_PER_POINT};
+use crate::models::LabelRotation;
+
+/// Convertit des mm en points PDF
+fn pt(mm: f64) -> f32 {
+    (mm / MM_PER_POINT) as f32
+}
+
+/// Dessine un élément sur une page dont la hauteur est donnée en mm
+fn draw_element(canvas: &mut Canvas, element: &LabelElement, page_height: f64) -> io::Result<()> {
+    // Le PDF place l'origine en bas à gauche
+    let y_pt = |y: f64| pt(page_height - y);
+
+    match element {
+        LabelElement::Text { x, y, size, bold, text } => {
+            // Ligne de base à environ 80 % de la taille du corps
+            let baseline = y + size * MM_PER_POINT * 0.8;
+            canvas.left_text(pt(*x), y_pt(baseline), font_for(*bold), *size as f32, text)
+        }
+        LabelElement::Rectangle { x, y, width, height, thickness } => {
+            if *thickness * 2.0 >= width.min(*height) {
+                canvas.rectangle(pt(*x), y_pt(y + height), pt(*width), pt(*height))?;
+                canvas.fill()
+            } else {
+                canvas.set_line_width(pt(*thickness))?;
+                let half = thickness / 2.0;
+                canvas.rectangle(pt(x + half), y_pt(y + height - half), pt(width - thickness), pt(height - thickness))?;
+                canvas.stroke()
+            }
+        }
+        LabelElement::Code128 { x, y, module_width, height, data } => {
+            let modules = code128::encode(data).map_err(|e| io::Error::other(e.to_string()))?;
+            let mut index = 0;
+            while index < modules.len() {
+                let run = modules[index..].iter().take_while(|dark| **dark == modules[index]).count();
+                if modules[index] {
+                    let bar_x = x + index as f64 * module_width;
+                    canvas.rectangle(pt(bar_x), y_pt(y + height), pt(run as f64 * module_width), pt(*height))?;
+                }
+                index += run;
+            }
+            canvas.fill()
+        }
+        LabelElement::DataMatrix { x, y, module_size, .. } | LabelElement::QrCode { x, y, module_size, .. } => {
+            let (size, modules) = element.matrix_modules()
+                .map_err(|e| io::Error::other(e.to_string()))?
+                .unwrap_or_default();
+            for (index, _) in modules.iter().enumerate().filter(|(_, dark)| **dark) {
+                let module_x = x + (index % size) as f64 * module_size;
+                let module_y = y + (index / size + 1) as f64 * module_size;
+                canvas.rectangle(pt(module_x), y_pt(module_y), pt(*module_size), pt(*module_size))?;
+            }
+            canvas.fill()
+        }
+    }
+}
+
+/// Applique la rotation de la mise en page au repère de la page
+fn rotate_page(canvas: &mut Canvas, layout: &LabelLayout) -> io::Result<()> {
+    let (width, height) = (pt(layout.width), pt(layout.height));
+
+    // Rotation horaire autour de l'origine, puis translation pour revenir dans la page
+    let (dx, dy, angle) = match layout.rotation {
+        LabelRotation::None => return Ok(()),
+        LabelRotation::Rotate90 => (0.0, width, -90.0),
+        LabelRotation::Rotate180 => (width, height, 180.0),
+        LabelRotation::Rotate270 => (height, 0.0, 90.0),
+    };
+
+    canvas.concat(Matrix::translate(dx, dy))?;
+    canvas.concat(Matrix::rotate_deg(angle))
+}
+
+/// Produit un document PDF d'une page par mise en page
+pub fn render_pdf_pages(layouts: &[LabelLayout]) -> Result<Vec<u8>, DeliveryError> {
+    // pdf-canvas n'écrit que dans un fichier : passage par un fichier temporaire
+    let path = std::env::temp_dir().join(format!("zyou_label_{}.pdf", uuid::Uuid::new_v4()));
+    let path_str = path.to_string_lossy().to_string();
+
+    let result = (|| -> io::Result<Vec<u8>> {
+        let mut document = Pdf::create(&path_str)?;
+        document.set_producer("zyou_delivery");
+
+        for layout in layouts {
+            let (page_width, page_height) = layout.output_dimensions();
+            document.render_page(pt(page_width), pt(page_height), |canvas| {
+                rotate_page(canvas, layout)?;
+                for element in &layout.elements {
+                    draw_element(canvas, element, layout.height)?;
+                }
+                Ok(())
+            })?;
+        }
+
+        document.finish()?;
+        std::fs::read(&path)
+    })();
+
+    let _ = std::fs::remove_file(&path);
+
+    result.map_err(|e| DeliveryError::LabelGenerationError(format!("Rendu PDF impossible: {}", e)))
+}
+
+/// Produit un document PDF d'une page à partir d'une mise en page
+pub fn render_pdf(layout: &LabelLayout) -> Result<Vec<u8>, DeliveryError> {
+    render_pdf_pages(std::slice::from_ref(layout))
+}
@@ -0,0 +1,178 @@
+use crate::errors::DeliveryError;
+use crate::labels::barcode::code128;
+use crate::labels::font::{glyph, GLYPH_HEIGHT, GLYPH_WIDTH};
+use crate::labels::layout::{LabelElement, LabelLayout, MM_PER_POINT};
+use crate::models::LabelRotation;
+
+/// Image monochrome d'une étiquette, un booléen par point d'impression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bitmap {
+    pub width: usize,
+    pub height: usize,
+    /// Points ligne par ligne (true = noir)
+    pub pixels: Vec<bool>,
+}
+
+impl Bitmap {
+    /// Crée une image blanche
+    pub fn new(width: usize, height: usize) -> Self {
+        Self { width, height, pixels: vec![false; width * height] }
+    }
+
+    /// Noircit un rectangle exprimé en points (bornes arrondies, découpé aux limites de l'image)
+    pub fn fill_rect(&mut self, x: f64, y: f64, width: f64, height: f64) {
+        let clamp = |value: f64, max: usize| (value.round().max(0.0) as usize).min(max);
+        let (x0, x1) = (clamp(x, self.width), clamp(x + width, self.width));
+        let (y0, y1) = (clamp(y, self.height), clamp(y + height, self.height));
+
+        for row in y0..y1 {
+            self.pixels[row * self.width + x0..row * self.width + x1].fill(true);
+        }
+    }
+
+    /// Indique si le point donné est noir
+    pub fn is_dark(&self, x: usize, y: usize) -> bool {
+        self.pixels[y * self.width + x]
+    }
+
+    /// Retourne l'image tournée dans le sens horaire
+    pub fn rotated(&self, rotation: LabelRotation) -> Bitmap {
+        let (width, height) = if rotation.is_quarter_turn() {
+            (self.height, self.width)
+        } else {
+            (self.width, self.height)
+        };
+        let mut rotated = Bitmap::new(width, height);
+
+        for y in 0..self.height {
+            for x in (0..self.width).filter(|x| self.is_dark(*x, y)) {
+                let (new_x, new_y) = match rotation {
+                    LabelRotation::None => (x, y),
+                    LabelRotation::Rotate90 => (self.height - 1 - y, x),
+                    LabelRotation::Rotate180 => (self.width - 1 - x, self.height - 1 - y),
+                    LabelRotation::Rotate270 => (y, self.width - 1 - x),
+                };
+                rotated.pixels[new_y * width + new_x] = true;
+            }
+        }
+
+        rotated
+    }
+}
+
+/// Dessine un texte avec la police bitmap, (x, y) étant le coin supérieur gauche en points
+fn draw_text(bitmap: &mut Bitmap, x: f64, y: f64, height: f64, bold: bool, text: &str) {
+    // Les glyphes occupent environ 70 % du corps, chasse proche de l'Helvetica
+    let scale_y = height * 0.7 / GLYPH_HEIGHT as f64;
+    let scale_x = scale_y * 0.9;
+    let advance = (GLYPH_WIDTH + 1) as f64 * scale_x;
+    let top = y + height * 0.1;
+
+    for (index, c) in text.chars().enumerate() {
+        let left = x + index as f64 * advance;
+
+        for (col, bits) in glyph(c).iter().enumerate() {
+            for row in (0..GLYPH_HEIGHT).filter(|row| bits & (1 << row) != 0) {
+                let width = if bold { scale_x * 1.6 } else { scale_x };
+                bitmap.fill_rect(left + col as f64 * scale_x, top + row as f64 * scale_y, width, scale_y);
+            }
+        }
+    }
+}
+
+/// Dessine un élément de mise en page à la résolution donnée (points par mm)
+fn draw_element(bitmap: &mut Bitmap, element: &LabelElement, dots_per_mm: f64) -> Result<(), DeliveryError> {
+    let d = |mm: f64| mm * dots_per_mm;
+
+    match element {
+        LabelElement::Text { x, y, size, bold, text } => {
+            draw_text(bitmap, d(*x), d(*y), d(size * MM_PER_POINT), *bold, text);
+        }
+        LabelElement::Rectangle { x, y, width, height, thickness } => {
+            if *thickness * 2.0 >= width.min(*height) {
+                bitmap.fill_rect(d(*x), d(*y), d(*width), d(*height));
+            } else {
+                let t = d(*thickness).max(1.0);
+                bitmap.fill_rect(d(*x), d(*y), d(*width), t);
+                bitmap.fill_rect(d(*x), d(y + height) - t, d(*width), t);
+                bitmap.fill_rect(d(*x), d(*y), t, d(*height));
+                bitmap.fill_rect(d(x + width) - t, d(*y), t, d(*height));
+            }
+        }
+        LabelElement::Code128 { x, y, module_width, height, data } => {
+            // Largeur de module entière pour garder des barres régulières
+            let module = d(*module_width).round().max(1.0);
+            for (index, _) in code128::encode(data)?.iter().enumerate().filter(|(_, dark)| **dark) {
+                bitmap.fill_rect(d(*x) + index as f64 * module, d(*y), module, d(*height));
+            }
+        }
+        LabelElement::DataMatrix { x, y, module_size, .. } | LabelElement::QrCode { x, y, module_size, .. } => {
+            let (size, modules) = element.matrix_modules()?.unwrap_or_default();
+            let module = d(*module_size).round().max(1.0);
+            for (index, _) in modules.iter().enumerate().filter(|(_, dark)| **dark) {
+                let module_x = d(*x) + (index % size) as f64 * module;
+                let module_y = d(*y) + (index / size) as f64 * module;
+                bitmap.fill_rect(module_x, module_y, module, module);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Rastérise une mise en page à la résolution donnée en dpi, rotation comprise
+pub fn rasterize(layout: &LabelLayout, dpi: u32) -> Result<Bitmap, DeliveryError> {
+    let dots_per_mm = dpi as f64 / 25.4;
+    let mut bitmap = Bitmap::new(
+        (layout.width * dots_per_mm).round() as usize,
+        (layout.height * dots_per_mm).round() as usize,
+    );
+
+    for element in &layout.elements {
+        draw_element(&mut bitmap, element, dots_per_mm)?;
+    }
+
+    if layout.rotation == LabelRotation::None {
+        return Ok(bitmap);
+    }
+
+    Ok(bitmap.rotated(layout.rotation))
+}
+
+/// Encode une image monochrome en PNG 1 bit, résolution incluse
+pub fn encode_png(bitmap: &Bitmap, dpi: u32) -> Result<Vec<u8>, DeliveryError> {
+    let to_error = |e: png::EncodingError| DeliveryError::LabelGenerationError(format!("Encodage PNG impossible: {}", e));
+
+    // Un bit par point, 1 = blanc
+    let row_bytes = bitmap.width.div_ceil(8);
+    let mut data = vec![0u8; row_bytes * bitmap.height];
+    for y in 0..bitmap.height {
+        for x in (0..bitmap.width).filter(|x| !bitmap.is_dark(*x, y)) {
+            data[y * row_bytes + x / 8] |= 0x80 >> (x % 8);
+        }
+    }
+
+    let mut output = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut output, bitmap.width as u32, bitmap.height as u32);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::One);
+
+        let dots_per_meter = (dpi as f64 / 0.0254).round() as u32;
+        encoder.set_pixel_dims(Some(png::PixelDimensions {
+            xppu: dots_per_meter,
+            yppu: dots_per_meter,
+            unit: png::Unit::Meter,
+        }));
+
+        let mut writer = encoder.write_header().map_err(to_error)?;
+        writer.write_image_data(&data).map_err(to_error)?;
+    }
+
+    Ok(output)
+}
+
+/// Produit une image PNG monochrome d'une mise en page
+pub fn render_png(layout: &LabelLayout, dpi: u32) -> Result<Vec<u8>, DeliveryError> {
+    encode_png(&rasterize(layout, dpi)?, dpi)
+}
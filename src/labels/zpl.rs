@@ -0,0 +1,444 @@
+use std::fmt::Write;
+
+use crate::errors::DeliveryError;
+use crate::labels::barcode::{code128, datamatrix, qr};
+use crate::labels::layout::{LabelElement, LabelLayout, MM_PER_POINT};
+use crate::models::{LabelRotation, LabelSize};
+
+/// Hauteur par défaut des polices ZPL en points d'impression
+const DEFAULT_FONT_HEIGHT: f64 = 9.0;
+
+/// Hauteur par défaut des codes-barres ZPL en points d'impression
+const DEFAULT_BAR_HEIGHT: f64 = 10.0;
+
+/// Échappe les caractères réservés d'un champ ^FD (utilisé avec ^FH_)
+fn escape_field(data: &str) -> String {
+    let mut escaped = String::with_capacity(data.len());
+    for c in data.chars() {
+        match c {
+            '^' | '~' | '_' => {
+                let _ = write!(escaped, "_{:02X}", c as u32);
+            }
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Écrit un champ de données avec indicateur hexadécimal si nécessaire
+fn field_data(zpl: &mut String, data: &str) {
+    if data.contains(['^', '~', '_']) {
+        let _ = write!(zpl, "^FH_^FD{}^FS", escape_field(data));
+    } else {
+        let _ = write!(zpl, "^FD{}^FS", data);
+    }
+}
+
+/// Génère le ZPL II d'une mise en page pour une imprimante de la résolution donnée
+///
+/// Les textes utilisent la police vectorielle 0 ; un texte gras est rendu par une
+/// chasse supérieure à la hauteur, convention reprise par [`parse_zpl`].
+/// Les rotations d'un quart de tour orientent chaque champ (R), le demi-tour inverse l'impression (^POI).
+pub fn to_zpl(layout: &LabelLayout, dpi: u32) -> Result<String, DeliveryError> {
+    let dots_per_mm = dpi as f64 / 25.4;
+    let d = |mm: f64| (mm * dots_per_mm).round().max(0.0) as u32;
+
+    let quarter_turn = layout.rotation.is_quarter_turn();
+    let (page_width, page_height) = layout.output_dimensions();
+    let orientation = if quarter_turn { 'R' } else { 'N' };
+
+    // Coin supérieur gauche d'un champ de hauteur donnée (en points), après rotation
+    let origin = |x: f64, y: f64, height: u32| {
+        if quarter_turn {
+            (d(page_width).saturating_sub(d(y) + height), d(x))
+        } else {
+            (d(x), d(y))
+        }
+    };
+
+    let mut zpl = String::new();
+    zpl.push_str("^XA\n^CI28\n");
+    let _ = writeln!(zpl, "^PW{}\n^LL{}", d(page_width), d(page_height));
+    if matches!(layout.rotation, LabelRotation::Rotate180 | LabelRotation::Rotate270) {
+        zpl.push_str("^POI\n");
+    }
+
+    for element in &layout.elements {
+        match element {
+            LabelElement::Text { x, y, size, bold, text } => {
+                let height = d(size * MM_PER_POINT).max(1);
+                let width = if *bold { height * 11 / 10 + 1 } else { height };
+                let (fo_x, fo_y) = origin(*x, *y, height);
+                let _ = write!(zpl, "^FO{},{}^A0{},{},{}", fo_x, fo_y, orientation, height, width);
+                field_data(&mut zpl, text);
+            }
+            LabelElement::Rectangle { x, y, width, height, thickness } => {
+                let (w, h) = (d(*width).max(1), d(*height).max(1));
+                let t = if *thickness * 2.0 >= width.min(*height) { w.min(h) } else { d(*thickness).max(1) };
+                let (fo_x, fo_y) = origin(*x, *y, h);
+                let (w, h) = if quarter_turn { (h, w) } else { (w, h) };
+                let _ = write!(zpl, "^FO{},{}^GB{},{},{}^FS", fo_x, fo_y, w, h, t);
+            }
+            LabelElement::Code128 { x, y, module_width, height, data } => {
+                let module = d(*module_width).clamp(1, 10);
+                let bar_height = d(*height).max(1);
+                let (fo_x, fo_y) = origin(*x, *y, bar_height);
+                let _ = write!(zpl, "^FO{},{}^BY{}^BC{},{},N,N,N,A", fo_x, fo_y, module, orientation, bar_height);
+                field_data(&mut zpl, data);
+            }
+            LabelElement::DataMatrix { x, y, module_size, data } => {
+                let module = d(*module_size).max(1);
+                let side = datamatrix::encode(data)?.size as u32 * module;
+                let (fo_x, fo_y) = origin(*x, *y, side);
+                let _ = write!(zpl, "^FO{},{}^BX{},{},200", fo_x, fo_y, orientation, module);
+                field_data(&mut zpl, data);
+            }
+            LabelElement::QrCode { x, y, module_size, data } => {
+                // ^BQ ne s'oriente pas : le symbole reste lisible dans tous les sens
+                let module = d(*module_size).clamp(1, 10);
+                let side = qr::encode(data)?.size as u32 * module;
+                let (fo_x, fo_y) = origin(*x, *y, side);
+                let _ = write!(zpl, "^FO{},{}^BQN,2,{}", fo_x, fo_y, module);
+                field_data(&mut zpl, &format!("MA,{}", data));
+            }
+        }
+        zpl.push('\n');
+    }
+
+    zpl.push_str("^XZ\n");
+    Ok(zpl)
+}
+
+/// Ajoute le nombre d'exemplaires (^PQ) à chaque étiquette d'un flux ZPL
+pub fn with_copies(zpl: &str, copies: u32) -> String {
+    if copies <= 1 {
+        return zpl.to_string();
+    }
+    zpl.replace("^XZ", &format!("^PQ{}\n^XZ", copies))
+}
+
+/// Champ en cours de construction entre ^FO et ^FS
+#[derive(Debug, Clone, Copy)]
+enum Field {
+    Text,
+    Code128 { height: f64, interpretation: bool, automatic: bool },
+    DataMatrix { module: f64 },
+    QrCode { module: f64 },
+}
+
+/// Commande ZPL découpée : nom en majuscules et paramètres bruts
+struct Command<'a> {
+    name: String,
+    params: &'a str,
+}
+
+/// Découpe un flux ZPL en commandes ^xx / ~xx
+fn tokenize(zpl: &str) -> Vec<Command<'_>> {
+    let starts: Vec<usize> = zpl.char_indices()
+        .filter(|(_, c)| *c == '^' || *c == '~')
+        .map(|(index, _)| index)
+        .collect();
+
+    let mut commands = Vec::new();
+    let mut skip_until = 0;
+
+    for (position, &start) in starts.iter().enumerate() {
+        if start < skip_until {
+            continue;
+        }
+
+        let rest = &zpl[start + 1..];
+        // ^A est suivi directement du nom de police (^A0N,30,30)
+        let name_len = if rest.starts_with(['A', 'a']) {
+            1
+        } else {
+            rest.chars().take(2).map(char::len_utf8).sum()
+        };
+        let name = rest[..name_len].to_uppercase();
+
+        // Les données de champ ne se terminent qu'au prochain ^, pas au ~
+        let end = if name == "FD" || name == "FX" {
+            zpl[start + 1..].find('^').map(|offset| start + 1 + offset).unwrap_or(zpl.len())
+        } else {
+            starts.get(position + 1).copied().unwrap_or(zpl.len())
+        };
+        skip_until = end;
+
+        commands.push(Command { name, params: &zpl[start + 1 + name_len..end] });
+    }
+
+    commands
+}
+
+/// Paramètres séparés par des virgules, retours à la ligne ignorés
+fn params(raw: &str) -> Vec<String> {
+    raw.split(',').map(|param| param.replace(['\r', '\n'], "").trim().to_string()).collect()
+}
+
+/// Paramètre numérique à la position donnée
+fn number(params: &[String], index: usize) -> Option<f64> {
+    params.get(index).and_then(|param| param.parse::<f64>().ok())
+}
+
+/// Décode les séquences hexadécimales d'un champ ^FH
+fn unescape_field(data: &str, indicator: char) -> String {
+    let mut bytes = Vec::with_capacity(data.len());
+    let mut chars = data.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == indicator {
+            let hex: String = chars.by_ref().take(2).collect();
+            if let Ok(byte) = u8::from_str_radix(&hex, 16) {
+                bytes.push(byte);
+                continue;
+            }
+            bytes.extend(hex.bytes());
+        } else {
+            let mut buffer = [0u8; 4];
+            bytes.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+        }
+    }
+
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// Retire les codes d'invocation (>: >; >5 ...) d'un champ Code 128 en mode manuel
+fn strip_invocation_codes(data: &str) -> String {
+    let mut stripped = String::with_capacity(data.len());
+    let mut chars = data.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '>' && chars.peek().is_some_and(|next| ":;<=5678".contains(*next)) {
+            chars.next();
+        } else {
+            stripped.push(c);
+        }
+    }
+
+    stripped
+}
+
+/// Interprète les commandes ZPL courantes et reconstruit la mise en page de la première étiquette
+///
+/// Commandes prises en charge : ^XA ^XZ ^PW ^LL ^LH ^PO ^FW ^FO ^FT ^A ^CF ^FH ^FD ^FS ^GB ^BY ^BC ^BX ^BQ ^FX.
+/// Les autres commandes sont ignorées.
+///
+/// Des champs tous orientés d'un quart de tour (R, comme [`to_zpl`] les produit)
+/// donnent une mise en page tournée de 90° (270° avec ^POI). Les champs inversés
+/// (I), lus de bas en haut (B) ou d'orientations différentes ne sont pas pris en
+/// charge et sont refusés.
+pub fn parse_zpl(zpl: &str, dpi: u32) -> Result<LabelLayout, DeliveryError> {
+    let dots_per_mm = dpi as f64 / 25.4;
+    let mm = |dots: f64| dots / dots_per_mm;
+
+    let commands = tokenize(zpl);
+    let start = commands.iter().position(|command| command.name == "XA")
+        .ok_or_else(|| DeliveryError::LabelGenerationError("ZPL: début d'étiquette ^XA introuvable".to_string()))?;
+
+    let (default_width, default_height) = LabelSize::Inch4x6.dimensions_mm();
+    let mut layout = LabelLayout::new(default_width, default_height);
+
+    let mut home = (0.0, 0.0);
+    let mut origin = (0.0, 0.0);
+    let mut baseline = false;
+    let mut default_font = (DEFAULT_FONT_HEIGHT, DEFAULT_FONT_HEIGHT);
+    let mut font = None;
+    let mut module_width = 2.0;
+    let mut bar_height = DEFAULT_BAR_HEIGHT;
+    let mut hex_indicator = None;
+    let mut field = Field::Text;
+    let mut data: Option<String> = None;
+    let mut default_orientation = 'N';
+    let mut field_orientation = None;
+    let mut label_orientation = None;
+
+    for command in &commands[start + 1..] {
+        let p = params(command.params);
+
+        match command.name.as_str() {
+            "XZ" => break,
+            "PW" => if let Some(width) = number(&p, 0) {
+                layout.width = mm(width);
+            },
+            "LL" => if let Some(height) = number(&p, 0) {
+                layout.height = mm(height);
+            },
+            "PO" if command.params.trim().eq_ignore_ascii_case("I") => layout.rotation = LabelRotation::Rotate180,
+            "LH" => home = (number(&p, 0).unwrap_or(0.0), number(&p, 1).unwrap_or(0.0)),
+            "FW" => if let Some(orientation) = orientation_param(p.first().map(String::as_str)) {
+                default_orientation = orientation;
+            },
+            "FO" | "FT" => {
+                origin = (home.0 + number(&p, 0).unwrap_or(0.0), home.1 + number(&p, 1).unwrap_or(0.0));
+                baseline = command.name == "FT";
+            }
+            "CF" => {
+                let height = number(&p, 1).unwrap_or(default_font.0);
+                default_font = (height, number(&p, 2).unwrap_or(height));
+            }
+            "A" => {
+                // Premier paramètre : nom de police suivi de l'orientation (0N)
+                field_orientation = orientation_param(p.first().and_then(|font| font.get(1..)));
+                let height = number(&p, 1).unwrap_or(default_font.0);
+                font = Some((height, number(&p, 2).filter(|width| *width > 0.0).unwrap_or(height)));
+            }
+            "BY" => {
+                module_width = number(&p, 0).unwrap_or(module_width);
+                bar_height = number(&p, 2).unwrap_or(bar_height);
+            }
+            "BC" => {
+                field_orientation = orientation_param(p.first().map(String::as_str));
+                field = Field::Code128 {
+                    height: number(&p, 1).unwrap_or(bar_height),
+                    interpretation: p.get(2).is_none_or(|flag| flag != "N"),
+                    automatic: p.get(5).is_some_and(|mode| mode == "A"),
+                };
+            }
+            "BX" => {
+                field_orientation = orientation_param(p.first().map(String::as_str));
+                field = Field::DataMatrix { module: number(&p, 1).unwrap_or(module_width) };
+            }
+            "BQ" => field = Field::QrCode { module: number(&p, 2).unwrap_or(2.0) },
+            "FH" => hex_indicator = Some(command.params.chars().next().filter(|c| !c.is_whitespace()).unwrap_or('_')),
+            "FD" => {
+                let raw = command.params.trim_end_matches(['\r', '\n']);
+                data = Some(match hex_indicator {
+                    Some(indicator) => unescape_field(raw, indicator),
+                    None => raw.to_string(),
+                });
+            }
+            "GB" => {
+                let thickness = number(&p, 2).unwrap_or(1.0).max(1.0);
+                let width = number(&p, 0).unwrap_or(thickness).max(thickness);
+                let height = number(&p, 1).unwrap_or(thickness).max(thickness);
+                let y = if baseline { origin.1 - height } else { origin.1 };
+                layout.rectangle(mm(origin.0), mm(y), mm(width), mm(height), mm(thickness));
+            }
+            "FS" => {
+                if let Some(text) = data.take() {
+                    // ^BQ n'a pas d'orientation : le symbole se lit dans tous les sens
+                    if !matches!(field, Field::QrCode { .. }) {
+                        let orientation = field_orientation.unwrap_or(default_orientation);
+                        match (orientation, label_orientation) {
+                            ('I' | 'B', _) => {
+                                return Err(DeliveryError::LabelGenerationError(format!(
+                                    "ZPL: orientation de champ {} non prise en charge", orientation
+                                )));
+                            }
+                            (_, Some(previous)) if previous != orientation => {
+                                return Err(DeliveryError::LabelGenerationError(format!(
+                                    "ZPL: champs d'orientations différentes ({} et {}) non pris en charge", previous, orientation
+                                )));
+                            }
+                            _ => label_orientation = Some(orientation),
+                        }
+                    }
+                    emit_field(&mut layout, field, font.unwrap_or(default_font), origin, baseline, module_width, text, dots_per_mm)?;
+                }
+                field = Field::Text;
+                font = None;
+                field_orientation = None;
+                hex_indicator = None;
+            }
+            _ => {}
+        }
+    }
+
+    if label_orientation == Some('R') {
+        rotate_quarter_turn(&mut layout)?;
+    }
+    Ok(layout)
+}
+
+/// Orientation d'un champ (N, R, I ou B) en tête d'un paramètre
+fn orientation_param(param: Option<&str>) -> Option<char> {
+    param
+        .and_then(|param| param.chars().next())
+        .map(|c| c.to_ascii_uppercase())
+        .filter(|c| matches!(c, 'N' | 'R' | 'I' | 'B'))
+}
+
+/// Ramène dans le sens de lecture une étiquette dont les champs sont tournés d'un quart de tour
+///
+/// Inverse de la transformation de [`to_zpl`] : le coin supérieur gauche d'un
+/// champ tourné devient le coin inférieur gauche de l'élément non tourné.
+fn rotate_quarter_turn(layout: &mut LabelLayout) -> Result<(), DeliveryError> {
+    let page_width = layout.width;
+    layout.rotation = if layout.rotation == LabelRotation::Rotate180 { LabelRotation::Rotate270 } else { LabelRotation::Rotate90 };
+    layout.width = layout.height;
+    layout.height = page_width;
+
+    for element in &mut layout.elements {
+        let extent = match element {
+            LabelElement::Text { size, .. } => *size * MM_PER_POINT,
+            LabelElement::Rectangle { width, height, .. } => {
+                std::mem::swap(width, height);
+                *height
+            }
+            LabelElement::Code128 { height, .. } => *height,
+            LabelElement::DataMatrix { module_size, data, .. } => datamatrix::encode(data)?.size as f64 * *module_size,
+            LabelElement::QrCode { module_size, data, .. } => qr::encode(data)?.size as f64 * *module_size,
+        };
+        let (x, y) = element.origin_mut();
+        let (page_x, page_y) = (*x, *y);
+        *x = page_y;
+        *y = page_width - page_x - extent;
+    }
+
+    Ok(())
+}
+
+/// Ajoute à la mise en page le champ terminé par ^FS
+#[allow(clippy::too_many_arguments)]
+fn emit_field(
+    layout: &mut LabelLayout,
+    field: Field,
+    font: (f64, f64),
+    origin: (f64, f64),
+    baseline: bool,
+    module_width: f64,
+    data: String,
+    dots_per_mm: f64,
+) -> Result<(), DeliveryError> {
+    let mm = |dots: f64| dots / dots_per_mm;
+    let (x, y) = origin;
+
+    match field {
+        Field::Text => {
+            let (height, width) = font;
+            let top = if baseline { y - height * 0.8 } else { y };
+            let size = mm(height) / MM_PER_POINT;
+            layout.text(mm(x), mm(top), size, width > height, &data);
+        }
+        Field::Code128 { height, interpretation, automatic } => {
+            let data = if automatic { data } else { strip_invocation_codes(&data) };
+            let top = if baseline { y - height } else { y };
+            layout.code128(mm(x), mm(top), mm(module_width), mm(height), &data);
+
+            if interpretation {
+                // Ligne d'interprétation centrée sous le code-barres
+                let width = code128::encode(&data)?.len() as f64 * module_width;
+                let size = mm(font.0.max(DEFAULT_FONT_HEIGHT * 2.0)) / MM_PER_POINT;
+                let text_width = crate::labels::layout::text_width(&data, size, false);
+                layout.text(mm(x + width / 2.0) - text_width / 2.0, mm(top + height) + 1.0, size, false, &data);
+            }
+        }
+        Field::DataMatrix { module } => {
+            let size = datamatrix::encode(&data)?.size as f64;
+            let top = if baseline { y - size * module } else { y };
+            layout.datamatrix(mm(x), mm(top), mm(module), &data);
+        }
+        Field::QrCode { module } => {
+            // Données préfixées par le niveau de correction et le mode (MA,...)
+            let content = match data.split_once(',') {
+                Some((prefix, content)) if prefix.len() == 2 => content.to_string(),
+                _ => data,
+            };
+            let size = qr::encode(&content)?.size as f64;
+            let top = if baseline { y - size * module } else { y };
+            layout.qrcode(mm(x), mm(top), mm(module), &content);
+        }
+    }
+
+    Ok(())
+}
@@ -4,12 +4,22 @@ pub mod carriers;
 pub mod models;
 pub mod errors;
 pub mod utils;
+pub mod tariffs;
+pub mod labels;
+pub mod printing;
+pub mod manifest;
+pub mod store;
+pub mod lifecycle;
+pub mod eta;
+pub mod i18n;
+pub mod notifications;
+pub mod verification;
 
 // Réexportations principales pour faciliter l'utilisation
 pub use crate::core::ShippingManager;
 pub use crate::errors::DeliveryError;
 pub use crate::models::{
-    Address, Carrier, CarrierCode, Parcel, Rate,
+    Address, Carrier, CarrierCode, LabelFormat, LabelOptions, Parcel, Rate,
     ShippingLabel, TrackingEvent, TrackingInfo,
 };
 
@@ -18,3 +28,4 @@ pub use crate::core::traits::{
     RateProvider, LabelGenerator,
     ShipmentTracker, DataNormalizer,
 };
+pub use crate::utils::catalog::YamlCatalog;
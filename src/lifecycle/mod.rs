@@ -0,0 +1,287 @@
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::errors::DeliveryError;
+use crate::models::{ShipmentStatus, TrackingEvent, TrackingInfo};
+
+/// Étape du cycle de vie d'un envoi
+///
+/// Brouillon → tarifé → étiqueté → remis sur bordereau → en transit → livré, retourné ou annulé.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LifecycleState {
+    Draft,
+    Rated,
+    Labelled,
+    Manifested,
+    InTransit,
+    OutForDelivery,
+    Exception,  // Incident transporteur, dont l'envoi peut sortir
+    Delivered,
+    Returned,
+    Cancelled,
+}
+
+impl LifecycleState {
+    /// Rang dans la progression normale de l'envoi (l'incident n'en a pas)
+    fn rank(self) -> Option<u8> {
+        match self {
+            LifecycleState::Draft => Some(0),
+            LifecycleState::Rated => Some(1),
+            LifecycleState::Labelled => Some(2),
+            LifecycleState::Manifested => Some(3),
+            LifecycleState::InTransit => Some(4),
+            LifecycleState::OutForDelivery => Some(5),
+            LifecycleState::Delivered | LifecycleState::Returned => Some(6),
+            LifecycleState::Exception | LifecycleState::Cancelled => None,
+        }
+    }
+
+    /// Indique si l'envoi ne peut plus changer d'état
+    pub fn is_terminal(self) -> bool {
+        matches!(self, LifecycleState::Delivered | LifecycleState::Returned | LifecycleState::Cancelled)
+    }
+
+    /// Indique si l'envoi a été confié au transporteur
+    pub fn is_shipped(self) -> bool {
+        matches!(
+            self,
+            LifecycleState::InTransit | LifecycleState::OutForDelivery | LifecycleState::Exception
+                | LifecycleState::Delivered | LifecycleState::Returned
+        )
+    }
+
+    /// Indique si la transition vers `next` est autorisée
+    ///
+    /// Les étapes intermédiaires peuvent être sautées (un transporteur qui ne
+    /// scanne pas la prise en charge), mais un envoi ne revient jamais en arrière,
+    /// sauf vers le transit après une tentative de livraison ou un incident.
+    pub fn can_transition_to(self, next: LifecycleState) -> bool {
+        if self == next || self.is_terminal() {
+            return false;
+        }
+
+        match (self, next) {
+            // Annulation possible tant que le colis n'est pas chez le transporteur
+            (_, LifecycleState::Cancelled) => !self.is_shipped(),
+            // Incident signalé une fois l'étiquette créée
+            (_, LifecycleState::Exception) => self.rank().is_some_and(|rank| rank >= 2),
+            (LifecycleState::Exception, next) => next.is_shipped(),
+            (LifecycleState::OutForDelivery, LifecycleState::InTransit) => true,
+            // Un retour suppose une étiquette
+            (_, LifecycleState::Returned) => self.rank().is_some_and(|rank| rank >= 2),
+            (_, next) => match (self.rank(), next.rank()) {
+                (Some(current), Some(next)) => next > current,
+                _ => false,
+            },
+        }
+    }
+
+    /// Étape correspondant à un statut de suivi normalisé (aucune pour un statut inconnu)
+    pub fn from_status(status: ShipmentStatus) -> Option<Self> {
+        match status {
+            ShipmentStatus::Created => Some(LifecycleState::Labelled),
+            ShipmentStatus::Pickup | ShipmentStatus::InTransit => Some(LifecycleState::InTransit),
+            ShipmentStatus::OutForDelivery => Some(LifecycleState::OutForDelivery),
+            ShipmentStatus::Delivered => Some(LifecycleState::Delivered),
+            ShipmentStatus::Exception => Some(LifecycleState::Exception),
+            ShipmentStatus::Returned => Some(LifecycleState::Returned),
+            ShipmentStatus::Unknown => None,
+        }
+    }
+
+    /// Statut de suivi normalisé correspondant (aucun pour un envoi annulé)
+    pub fn to_status(self) -> Option<ShipmentStatus> {
+        match self {
+            LifecycleState::Draft | LifecycleState::Rated | LifecycleState::Labelled
+                | LifecycleState::Manifested => Some(ShipmentStatus::Created),
+            LifecycleState::InTransit => Some(ShipmentStatus::InTransit),
+            LifecycleState::OutForDelivery => Some(ShipmentStatus::OutForDelivery),
+            LifecycleState::Exception => Some(ShipmentStatus::Exception),
+            LifecycleState::Delivered => Some(ShipmentStatus::Delivered),
+            LifecycleState::Returned => Some(ShipmentStatus::Returned),
+            LifecycleState::Cancelled => None,
+        }
+    }
+}
+
+impl fmt::Display for LifecycleState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LifecycleState::Draft => write!(f, "Brouillon"),
+            LifecycleState::Rated => write!(f, "Tarifé"),
+            LifecycleState::Labelled => write!(f, "Étiqueté"),
+            LifecycleState::Manifested => write!(f, "Remis sur bordereau"),
+            LifecycleState::InTransit => write!(f, "En transit"),
+            LifecycleState::OutForDelivery => write!(f, "En cours de livraison"),
+            LifecycleState::Exception => write!(f, "Incident"),
+            LifecycleState::Delivered => write!(f, "Livré"),
+            LifecycleState::Returned => write!(f, "Retourné"),
+            LifecycleState::Cancelled => write!(f, "Annulé"),
+        }
+    }
+}
+
+/// Origine d'un changement d'état
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TransitionOrigin {
+    /// Action de la bibliothèque ou de l'application (tarification, étiquette, clôture…)
+    Action(String),
+    /// Événement de suivi du transporteur
+    CarrierEvent {
+        raw_status: String,
+        description: String,
+        location: Option<String>,
+    },
+}
+
+/// Sort d'un changement d'état demandé
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TransitionOutcome {
+    Applied,
+    Ignored(String),  // Motif : événement ancien, contradictoire ou transition interdite
+}
+
+/// Entrée du journal d'audit d'un envoi
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub recorded_at: DateTime<Utc>,
+    pub occurred_at: DateTime<Utc>,  // Horodatage de l'action ou de l'événement transporteur
+    pub from: LifecycleState,
+    pub to: LifecycleState,
+    pub origin: TransitionOrigin,
+    pub outcome: TransitionOutcome,
+}
+
+impl AuditEntry {
+    /// Indique si le changement a été appliqué
+    pub fn is_applied(&self) -> bool {
+        self.outcome == TransitionOutcome::Applied
+    }
+}
+
+/// Cycle de vie d'un envoi : état courant et journal d'audit de ses changements
+///
+/// Les actions internes interdites sont refusées avec une erreur ; les événements
+/// transporteur anciens ou contradictoires sont ignorés mais tracés dans le journal.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShipmentLifecycle {
+    pub state: LifecycleState,
+    pub audit: Vec<AuditEntry>,
+    pub last_event_at: Option<DateTime<Utc>>,  // Horodatage du dernier événement transporteur pris en compte
+}
+
+impl Default for ShipmentLifecycle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ShipmentLifecycle {
+    /// Crée le cycle de vie d'un envoi à l'état de brouillon
+    pub fn new() -> Self {
+        Self {
+            state: LifecycleState::Draft,
+            audit: Vec::new(),
+            last_event_at: None,
+        }
+    }
+
+    /// Change l'état suite à une action, en refusant les transitions interdites
+    pub fn transition(&mut self, to: LifecycleState, action: &str) -> Result<(), DeliveryError> {
+        if !self.state.can_transition_to(to) {
+            return Err(DeliveryError::InvalidTransition(format!("{} → {} ({})", self.state, to, action)));
+        }
+
+        self.push(to, Utc::now(), TransitionOrigin::Action(action.to_string()), TransitionOutcome::Applied);
+        Ok(())
+    }
+
+    /// Prend en compte un événement de suivi ; retourne `true` si l'état a changé
+    ///
+    /// Un événement déjà vu, de statut inconnu ou confirmant l'état courant
+    /// ne laisse pas de trace ; un événement antérieur au dernier pris en compte
+    /// ou menant à une transition interdite est ignoré et journalisé.
+    pub fn apply_event(&mut self, event: &TrackingEvent) -> bool {
+        let Some(to) = LifecycleState::from_status(event.status) else {
+            return false;
+        };
+        if self.has_seen(event) {
+            return false;
+        }
+
+        let origin = TransitionOrigin::CarrierEvent {
+            raw_status: event.raw_status.clone(),
+            description: event.description.clone(),
+            location: event.location.clone(),
+        };
+
+        if self.last_event_at.is_some_and(|last| event.timestamp < last) {
+            if to != self.state {
+                let reason = "Événement antérieur au dernier événement pris en compte".to_string();
+                self.push(to, event.timestamp, origin, TransitionOutcome::Ignored(reason));
+            }
+            return false;
+        }
+
+        if to == self.state {
+            self.last_event_at = Some(event.timestamp);
+            return false;
+        }
+
+        if !self.state.can_transition_to(to) {
+            let reason = format!("Transition {} → {} interdite", self.state, to);
+            self.push(to, event.timestamp, origin, TransitionOutcome::Ignored(reason));
+            return false;
+        }
+
+        self.last_event_at = Some(event.timestamp);
+        self.push(to, event.timestamp, origin, TransitionOutcome::Applied);
+        true
+    }
+
+    /// Prend en compte les événements d'un relevé de suivi, du plus ancien au plus récent
+    ///
+    /// Retourne le nombre de changements d'état appliqués.
+    pub fn apply_tracking(&mut self, info: &TrackingInfo) -> usize {
+        let mut events: Vec<&TrackingEvent> = info.events.iter().collect();
+        events.sort_by_key(|event| event.timestamp);
+
+        events.into_iter().filter(|event| self.apply_event(event)).count()
+    }
+
+    /// Statut de suivi normalisé de l'état courant
+    pub fn status(&self) -> Option<ShipmentStatus> {
+        self.state.to_status()
+    }
+
+    /// Changements d'état appliqués, du plus ancien au plus récent
+    pub fn history(&self) -> impl Iterator<Item = &AuditEntry> {
+        self.audit.iter().filter(|entry| entry.is_applied())
+    }
+
+    /// Indique si un événement transporteur figure déjà dans le journal
+    fn has_seen(&self, event: &TrackingEvent) -> bool {
+        self.audit.iter().any(|entry| {
+            entry.occurred_at == event.timestamp
+                && matches!(&entry.origin, TransitionOrigin::CarrierEvent { raw_status, .. } if *raw_status == event.raw_status)
+        })
+    }
+
+    fn push(&mut self, to: LifecycleState, occurred_at: DateTime<Utc>, origin: TransitionOrigin, outcome: TransitionOutcome) {
+        let from = self.state;
+        if outcome == TransitionOutcome::Applied {
+            self.state = to;
+        }
+
+        self.audit.push(AuditEntry {
+            recorded_at: Utc::now(),
+            occurred_at,
+            from,
+            to,
+            origin,
+            outcome,
+        });
+    }
+}
@@ -0,0 +1,235 @@
+/// Rendu PDF et CSV des bordereaux
+mod render;
+
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::errors::DeliveryError;
+use crate::models::{CarrierCode, Parcel, Rate, ShippingLabel};
+use crate::store::ShipmentRecord;
+
+/// Expédition portée sur un bordereau de remise
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub tracking_number: String,
+    pub carrier: CarrierCode,
+    pub account: Option<String>,  // Compte client chez le transporteur
+    pub service_code: Option<String>,
+    pub reference: Option<String>,
+    pub recipient_name: Option<String>,
+    pub recipient_postal_code: Option<String>,
+    pub recipient_country: Option<String>,
+    pub weight: Option<f64>,  // en kg
+    pub label_created_at: DateTime<Utc>,
+}
+
+impl ManifestEntry {
+    /// Crée une ligne de bordereau à partir d'une étiquette générée
+    pub fn from_label(label: &ShippingLabel) -> Self {
+        Self {
+            tracking_number: label.tracking_number.clone(),
+            carrier: label.carrier,
+            account: None,
+            service_code: None,
+            reference: None,
+            recipient_name: None,
+            recipient_postal_code: None,
+            recipient_country: None,
+            weight: None,
+            label_created_at: label.created_at,
+        }
+    }
+
+    /// Crée la ligne de bordereau d'un envoi enregistré, étiquette générée à la date indiquée
+    pub fn from_record(record: &ShipmentRecord, label_created_at: DateTime<Utc>) -> Self {
+        let parcel = &record.parcel;
+        Self {
+            tracking_number: record.tracking_number.clone(),
+            carrier: record.carrier,
+            account: record.account.clone(),
+            service_code: record.rate.as_ref().map(|rate| rate.service_code.clone()),
+            reference: parcel.reference.clone(),
+            recipient_name: Some(parcel.recipient.name.clone()),
+            recipient_postal_code: Some(parcel.recipient.postal_code.clone()),
+            recipient_country: Some(parcel.recipient.country.clone()),
+            weight: Some(parcel.weight),
+            label_created_at,
+        }
+    }
+
+    /// Complète la ligne avec le colis et le tarif de l'envoi
+    pub fn with_parcel(mut self, parcel: &Parcel, rate: &Rate) -> Self {
+        self.service_code = Some(rate.service_code.clone());
+        self.reference = parcel.reference.clone();
+        self.recipient_name = Some(parcel.recipient.name.clone());
+        self.recipient_postal_code = Some(parcel.recipient.postal_code.clone());
+        self.recipient_country = Some(parcel.recipient.country.clone());
+        self.weight = Some(parcel.weight);
+        self
+    }
+
+    /// Rattache la ligne à un compte client
+    pub fn with_account(mut self, account: Option<&str>) -> Self {
+        self.account = account.map(str::to_string);
+        self
+    }
+}
+
+/// Bordereau de remise (clôture de fin de journée) d'un transporteur et d'un compte
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub id: String,
+    pub carrier: CarrierCode,
+    pub account: Option<String>,
+    pub entries: Vec<ManifestEntry>,
+    pub closed_at: DateTime<Utc>,
+    pub confirmation_number: Option<String>,  // Référence de clôture renvoyée par l'API du transporteur
+}
+
+impl Manifest {
+    /// Nombre de colis remis
+    pub fn parcel_count(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Poids total des colis dont le poids est connu, en kg
+    pub fn total_weight(&self) -> f64 {
+        self.entries.iter().filter_map(|entry| entry.weight).sum()
+    }
+
+    /// Produit le bordereau imprimable (A4, code-barres par envoi, totaux et signatures)
+    pub fn to_pdf(&self) -> Result<Vec<u8>, DeliveryError> {
+        render::render_pdf(self)
+    }
+
+    /// Produit le bordereau au format CSV, terminé par une ligne de totaux
+    pub fn to_csv(&self) -> Result<Vec<u8>, DeliveryError> {
+        render::render_csv(self)
+    }
+}
+
+/// Transporteur et compte d'un bordereau
+type ManifestKey = (CarrierCode, Option<String>);
+
+/// Transporteur, compte et jour d'une série de bordereaux numérotés
+type SequenceKey = (ManifestKey, NaiveDate);
+
+#[derive(Debug, Default)]
+struct CollectorState {
+    pending: HashMap<ManifestKey, Vec<ManifestEntry>>,
+    /// Dernier numéro de bordereau confirmé par jour
+    sequences: HashMap<SequenceKey, u32>,
+    /// Numéros des bordereaux clôturés en attente de confirmation, par identifiant
+    reserved: HashMap<String, (SequenceKey, u32)>,
+}
+
+/// Collecte les envois depuis la dernière clôture, par transporteur et par compte
+#[derive(Debug, Default)]
+pub struct ManifestCollector {
+    state: Mutex<CollectorState>,
+}
+
+impl ManifestCollector {
+    /// Crée un collecteur vide
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ajoute un envoi au prochain bordereau (une étiquette regénérée n'est comptée qu'une fois)
+    pub fn record(&self, entry: ManifestEntry) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let pending = state.pending.entry((entry.carrier, entry.account.clone())).or_default();
+
+        match pending.iter_mut().find(|pending| pending.tracking_number == entry.tracking_number) {
+            Some(existing) => *existing = entry,
+            None => pending.push(entry),
+        }
+    }
+
+    /// Retire un envoi annulé avant la clôture
+    pub fn remove(&self, tracking_number: &str) -> bool {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+
+        state.pending.values_mut().any(|pending| {
+            let before = pending.len();
+            pending.retain(|entry| entry.tracking_number != tracking_number);
+            pending.len() != before
+        })
+    }
+
+    /// Remplace les envois en attente, par exemple par ceux relus dans le stockage
+    pub fn reload(&self, entries: Vec<ManifestEntry>) {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner).pending.clear();
+        for entry in entries {
+            self.record(entry);
+        }
+    }
+
+    /// Envois en attente de clôture pour un transporteur et un compte
+    pub fn pending(&self, carrier: CarrierCode, account: Option<&str>) -> Vec<ManifestEntry> {
+        let state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.pending.get(&(carrier, account.map(str::to_string))).cloned().unwrap_or_default()
+    }
+
+    /// Clôture les envois en attente et retourne le bordereau correspondant
+    ///
+    /// Le numéro du bordereau est réservé jusqu'à `confirm` (bordereau accepté) ou
+    /// `restore` (clôture échouée, numéro rendu).
+    pub fn close(&self, carrier: CarrierCode, account: Option<&str>) -> Result<Manifest, DeliveryError> {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let key = (carrier, account.map(str::to_string));
+
+        let entries = state.pending.remove(&key).unwrap_or_default();
+        if entries.is_empty() {
+            return Err(DeliveryError::ManifestError(format!(
+                "Aucun envoi {} à clôturer{}", carrier, account.map(|account| format!(" (compte {})", account)).unwrap_or_default()
+            )));
+        }
+
+        let closed_at = Utc::now();
+        let sequence_key = (key, closed_at.date_naive());
+        let sequence = state.reserved.values()
+            .filter(|(reserved, _)| *reserved == sequence_key)
+            .map(|(_, sequence)| *sequence)
+            .chain(state.sequences.get(&sequence_key).copied())
+            .max()
+            .unwrap_or_default() + 1;
+
+        let id = match account {
+            Some(account) => format!("{}-{}-{}-{:03}", carrier.to_string().to_uppercase(), account, closed_at.format("%Y%m%d"), sequence),
+            None => format!("{}-{}-{:03}", carrier.to_string().to_uppercase(), closed_at.format("%Y%m%d"), sequence),
+        };
+
+        state.reserved.insert(id.clone(), (sequence_key, sequence));
+
+        Ok(Manifest {
+            id,
+            carrier,
+            account: account.map(str::to_string),
+            entries,
+            closed_at,
+            confirmation_number: None,
+        })
+    }
+
+    /// Confirme le numéro d'un bordereau accepté
+    pub fn confirm(&self, manifest: &Manifest) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if let Some((key, sequence)) = state.reserved.remove(&manifest.id) {
+            let confirmed = state.sequences.entry(key).or_default();
+            *confirmed = (*confirmed).max(sequence);
+        }
+    }
+
+    /// Remet en attente les envois d'un bordereau dont la clôture a échoué et rend son numéro
+    pub fn restore(&self, manifest: Manifest) {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner).reserved.remove(&manifest.id);
+        for entry in manifest.entries {
+            self.record(entry);
+        }
+    }
+}
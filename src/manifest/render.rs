@@ -0,0 +1,204 @@
+use crate::errors::DeliveryError;
+use crate::labels::barcode::code128;
+use crate::labels::layout::{fit_text, text_width, LabelLayout};
+use crate::labels::pdf::render_pdf_pages;
+use crate::manifest::{Manifest, ManifestEntry};
+use crate::models::LabelSize;
+
+/// Marge de la page A4 en mm
+const MARGIN: f64 = 12.0;
+
+/// Ordonnée du haut du tableau des envois
+const TABLE_TOP: f64 = 52.0;
+
+/// Hauteur d'une ligne d'envoi (code-barres compris)
+const ROW_HEIGHT: f64 = 14.0;
+
+/// Lignes par page, et lignes laissant la place aux totaux et aux signatures
+const ROWS_PER_PAGE: usize = 15;
+const ROWS_WITH_FOOTER: usize = 12;
+
+/// Abscisses des colonnes : n°, suivi, destinataire, code postal, service, poids (aligné à droite)
+const COLUMNS: [f64; 6] = [MARGIN, 20.0, 82.0, 132.0, 158.0, 198.0];
+
+/// Largeur maximale du code-barres d'un envoi
+const BARCODE_WIDTH: f64 = 58.0;
+
+fn weight_text(weight: Option<f64>) -> String {
+    weight.map(|weight| format!("{:.2}", weight)).unwrap_or_else(|| "-".to_string())
+}
+
+/// Trait horizontal sur toute la largeur utile
+fn rule(page: &mut LabelLayout, y: f64, thickness: f64) {
+    page.rectangle(MARGIN, y, page.width - 2.0 * MARGIN, thickness, thickness);
+}
+
+/// En-tête répété sur chaque page : transporteur, compte, bordereau et son code-barres
+fn header(page: &mut LabelLayout, manifest: &Manifest, number: usize, count: usize) -> Result<(), DeliveryError> {
+    page.text(MARGIN, MARGIN, 16.0, true, "BORDEREAU DE REMISE");
+    page.text(MARGIN, MARGIN + 9.0, 11.0, true, &manifest.carrier.to_string());
+
+    let details = [
+        manifest.account.as_ref().map(|account| format!("Compte : {}", account)),
+        Some(format!("Bordereau : {}", manifest.id)),
+        Some(format!("Clôture : {}", manifest.closed_at.format("%d/%m/%Y %H:%M UTC"))),
+        manifest.confirmation_number.as_ref().map(|confirmation| format!("Confirmation : {}", confirmation)),
+    ];
+    for (index, detail) in details.into_iter().flatten().enumerate() {
+        page.text(MARGIN, MARGIN + 15.0 + index as f64 * 4.5, 9.0, false, &detail);
+    }
+
+    // Code-barres du bordereau, scanné par le chauffeur à l'enlèvement
+    let modules = code128::encode(&manifest.id)?.len() as f64;
+    let module_width = (BARCODE_WIDTH * 1.5 / modules).min(0.3);
+    let barcode_width = modules * module_width;
+    let right = page.width - MARGIN;
+    page.code128(right - barcode_width, MARGIN, module_width, 12.0, &manifest.id);
+    page.text(right - text_width(&manifest.id, 8.0, false), MARGIN + 13.0, 8.0, false, &manifest.id);
+
+    let page_text = format!("Page {}/{}", number, count);
+    page.text(right - text_width(&page_text, 8.0, false), page.height - MARGIN, 8.0, false, &page_text);
+
+    let titles = ["N°", "Numéro de suivi", "Destinataire", "CP / Pays", "Service"];
+    for (x, title) in COLUMNS.iter().zip(titles) {
+        page.text(*x, TABLE_TOP - 5.0, 8.0, true, title);
+    }
+    let weight_title = "Poids (kg)";
+    page.text(COLUMNS[5] - text_width(weight_title, 8.0, true), TABLE_TOP - 5.0, 8.0, true, weight_title);
+    rule(page, TABLE_TOP - 1.0, 0.4);
+
+    Ok(())
+}
+
+/// Ligne d'un envoi : numéro, code-barres du suivi, destinataire, service et poids
+fn row(page: &mut LabelLayout, y: f64, index: usize, entry: &ManifestEntry) -> Result<(), DeliveryError> {
+    page.text(COLUMNS[0], y + 1.0, 8.0, false, &(index + 1).to_string());
+
+    let modules = code128::encode(&entry.tracking_number)?.len() as f64;
+    let module_width = (BARCODE_WIDTH / modules).min(0.3);
+    page.code128(COLUMNS[1], y + 1.0, module_width, 7.0, &entry.tracking_number);
+    page.text(COLUMNS[1], y + 9.0, 7.0, false, &entry.tracking_number);
+
+    let recipient_width = COLUMNS[3] - COLUMNS[2] - 2.0;
+    if let Some(name) = &entry.recipient_name {
+        page.text(COLUMNS[2], y + 1.0, 8.0, false, &fit_text(name, 8.0, false, recipient_width));
+    }
+    if let Some(reference) = &entry.reference {
+        page.text(COLUMNS[2], y + 5.0, 7.0, false, &fit_text(&format!("Réf: {}", reference), 7.0, false, recipient_width));
+    }
+
+    let destination = [entry.recipient_postal_code.as_deref(), entry.recipient_country.as_deref()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ");
+    page.text(COLUMNS[3], y + 1.0, 8.0, false, &destination);
+
+    if let Some(service) = &entry.service_code {
+        page.text(COLUMNS[4], y + 1.0, 8.0, false, &fit_text(service, 8.0, false, COLUMNS[5] - COLUMNS[4] - 16.0));
+    }
+
+    let weight = weight_text(entry.weight);
+    page.text(COLUMNS[5] - text_width(&weight, 8.0, false), y + 1.0, 8.0, false, &weight);
+
+    rule(page, y + ROW_HEIGHT - 0.5, 0.1);
+    Ok(())
+}
+
+/// Totaux et cadres de signature, sous la dernière ligne
+fn footer(page: &mut LabelLayout, y: f64, manifest: &Manifest) {
+    rule(page, y, 0.4);
+    let total = format!("Total : {} colis, {} kg", manifest.parcel_count(), weight_text(Some(manifest.total_weight())));
+    page.text(MARGIN, y + 3.0, 10.0, true, &total);
+
+    let box_width = (page.width - 2.0 * MARGIN - 6.0) / 2.0;
+    let box_y = y + 12.0;
+    for (index, title) in ["Signature de l'expéditeur", "Signature du transporteur (date et heure)"].iter().enumerate() {
+        let x = MARGIN + index as f64 * (box_width + 6.0);
+        page.rectangle(x, box_y, box_width, 25.0, 0.3);
+        page.text(x + 2.0, box_y + 2.0, 8.0, false, title);
+    }
+}
+
+/// Met en page le bordereau sur des feuilles A4
+fn layouts(manifest: &Manifest) -> Result<Vec<LabelLayout>, DeliveryError> {
+    let mut chunks: Vec<&[ManifestEntry]> = manifest.entries.chunks(ROWS_PER_PAGE).collect();
+
+    // Page supplémentaire si la dernière ne laisse pas la place aux totaux
+    if chunks.last().is_none_or(|last| last.len() > ROWS_WITH_FOOTER) {
+        chunks.push(&[]);
+    }
+
+    let (width, height) = LabelSize::A4.dimensions_mm();
+    let count = chunks.len();
+    let mut pages = Vec::with_capacity(count);
+
+    for (number, chunk) in chunks.iter().enumerate() {
+        let mut page = LabelLayout::new(width, height);
+        header(&mut page, manifest, number + 1, count)?;
+
+        for (offset, entry) in chunk.iter().enumerate() {
+            let index = number * ROWS_PER_PAGE + offset;
+            row(&mut page, TABLE_TOP + offset as f64 * ROW_HEIGHT, index, entry)?;
+        }
+
+        if number + 1 == count {
+            footer(&mut page, TABLE_TOP + chunk.len() as f64 * ROW_HEIGHT + 2.0, manifest);
+        }
+        pages.push(page);
+    }
+
+    Ok(pages)
+}
+
+/// Produit le bordereau PDF
+pub fn render_pdf(manifest: &Manifest) -> Result<Vec<u8>, DeliveryError> {
+    render_pdf_pages(&layouts(manifest)?)
+}
+
+/// Produit le bordereau CSV
+pub fn render_csv(manifest: &Manifest) -> Result<Vec<u8>, DeliveryError> {
+    let to_error = |e: csv::Error| DeliveryError::SerializationError(format!("Bordereau CSV: {}", e));
+
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record([
+        "manifest_id", "carrier", "account", "tracking_number", "service_code", "reference",
+        "recipient_name", "recipient_postal_code", "recipient_country", "weight_kg", "created_at",
+    ]).map_err(to_error)?;
+
+    let carrier = manifest.carrier.to_string();
+    let account = manifest.account.clone().unwrap_or_default();
+
+    for entry in &manifest.entries {
+        writer.write_record([
+            manifest.id.as_str(),
+            &carrier,
+            &account,
+            &entry.tracking_number,
+            entry.service_code.as_deref().unwrap_or_default(),
+            entry.reference.as_deref().unwrap_or_default(),
+            entry.recipient_name.as_deref().unwrap_or_default(),
+            entry.recipient_postal_code.as_deref().unwrap_or_default(),
+            entry.recipient_country.as_deref().unwrap_or_default(),
+            &entry.weight.map(|weight| format!("{:.3}", weight)).unwrap_or_default(),
+            &entry.label_created_at.to_rfc3339(),
+        ]).map_err(to_error)?;
+    }
+
+    // Ligne de totaux : nombre de colis dans la colonne du destinataire, poids total
+    writer.write_record([
+        manifest.id.as_str(),
+        &carrier,
+        &account,
+        "TOTAL",
+        "",
+        "",
+        &format!("{} colis", manifest.parcel_count()),
+        "",
+        "",
+        &format!("{:.3}", manifest.total_weight()),
+        &manifest.closed_at.to_rfc3339(),
+    ]).map_err(to_error)?;
+
+    writer.into_inner().map_err(|e| DeliveryError::SerializationError(format!("Bordereau CSV: {}", e)))
+}
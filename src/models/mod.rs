@@ -186,6 +186,17 @@ impl Parcel {
         self.is_return = true;
         self
     }
+
+    /// Crée le colis retour d'un envoi : nouvel identifiant, expéditeur et destinataire inversés
+    pub fn for_return(&self) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            sender: self.recipient.clone(),
+            recipient: self.sender.clone(),
+            is_return: true,
+            ..self.clone()
+        }
+    }
 }
 
 impl Default for Parcel {
@@ -207,6 +218,19 @@ pub struct Rate {
     pub delivery_days: Option<u32>,
     pub guaranteed_delivery: bool,
     pub features: Vec<String>,  // Options comme signature, assurance, etc.
+    #[serde(default)]
+    pub billable_weight: Option<f64>,  // en kg, max(poids réel, poids volumétrique)
+    #[serde(default)]
+    pub estimated: bool,  // Tarif calculé localement, sans appel à l'API du transporteur
+}
+
+impl Rate {
+    /// Renseigne le poids facturable du tarif à partir des dimensions du colis et des diviseurs donnés
+    pub fn with_billable_weight(mut self, parcel: &Parcel, rules: &crate::utils::weight::VolumetricRules) -> Self {
+        let weight = rules.billable_weight(parcel, self.carrier, &self.service_code);
+        self.billable_weight = Some(weight.billable);
+        self
+    }
 }
 
 /// Status normalisé d'un colis en transit
@@ -237,14 +261,149 @@ impl fmt::Display for ShipmentStatus {
     }
 }
 
+/// Sous-statut détaillé d'un colis, rattaché à un statut normalisé
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TrackingSubStatus {
+    InfoReceived,  // Étiquette créée, colis pas encore remis
+    PickedUp,
+    InTransit,
+    ArrivedAtHub,
+    CustomsClearance,
+    OutForDelivery,
+    AvailableForPickup,  // En attente de retrait en point relais ou en agence
+    Delivered,
+    DeliveredToNeighbour,
+    CollectedAtPickupPoint,
+    FailedAttempt,
+    AddressIssue,
+    HeldAtCustoms,
+    CustomsPaymentRequired,
+    RefusedByRecipient,
+    Damaged,
+    Lost,
+    Delayed,
+    Incident,  // Incident sans précision du transporteur
+    ReturnToSender,  // Retour à l'expéditeur en cours
+    ReturnedToSender,
+    #[default]
+    Unknown,
+}
+
+impl TrackingSubStatus {
+    /// Statut normalisé de premier niveau
+    pub fn status(self) -> ShipmentStatus {
+        match self {
+            TrackingSubStatus::InfoReceived => ShipmentStatus::Created,
+            TrackingSubStatus::PickedUp => ShipmentStatus::Pickup,
+            TrackingSubStatus::InTransit
+                | TrackingSubStatus::ArrivedAtHub
+                | TrackingSubStatus::CustomsClearance => ShipmentStatus::InTransit,
+            TrackingSubStatus::OutForDelivery
+                | TrackingSubStatus::AvailableForPickup => ShipmentStatus::OutForDelivery,
+            TrackingSubStatus::Delivered
+                | TrackingSubStatus::DeliveredToNeighbour
+                | TrackingSubStatus::CollectedAtPickupPoint => ShipmentStatus::Delivered,
+            TrackingSubStatus::FailedAttempt
+                | TrackingSubStatus::AddressIssue
+                | TrackingSubStatus::HeldAtCustoms
+                | TrackingSubStatus::CustomsPaymentRequired
+                | TrackingSubStatus::RefusedByRecipient
+                | TrackingSubStatus::Damaged
+                | TrackingSubStatus::Lost
+                | TrackingSubStatus::Delayed
+                | TrackingSubStatus::Incident
+                // Retour en cours : l'envoi ne sera retourné qu'une fois remis à l'expéditeur
+                | TrackingSubStatus::ReturnToSender => ShipmentStatus::Exception,
+            TrackingSubStatus::ReturnedToSender => ShipmentStatus::Returned,
+            TrackingSubStatus::Unknown => ShipmentStatus::Unknown,
+        }
+    }
+
+    /// Indique si le destinataire doit agir pour que la livraison aboutisse
+    /// (retrait, nouvelle tentative, adresse à préciser, droits de douane)
+    pub fn requires_action(self) -> bool {
+        matches!(
+            self,
+            TrackingSubStatus::AvailableForPickup
+                | TrackingSubStatus::FailedAttempt
+                | TrackingSubStatus::AddressIssue
+                | TrackingSubStatus::HeldAtCustoms
+                | TrackingSubStatus::CustomsPaymentRequired
+        )
+    }
+}
+
+impl fmt::Display for TrackingSubStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrackingSubStatus::InfoReceived => write!(f, "Étiquette créée"),
+            TrackingSubStatus::PickedUp => write!(f, "Pris en charge"),
+            TrackingSubStatus::InTransit => write!(f, "En cours d'acheminement"),
+            TrackingSubStatus::ArrivedAtHub => write!(f, "Arrivé sur une plateforme"),
+            TrackingSubStatus::CustomsClearance => write!(f, "En cours de dédouanement"),
+            TrackingSubStatus::OutForDelivery => write!(f, "En cours de livraison"),
+            TrackingSubStatus::AvailableForPickup => write!(f, "Disponible au retrait"),
+            TrackingSubStatus::Delivered => write!(f, "Livré"),
+            TrackingSubStatus::DeliveredToNeighbour => write!(f, "Livré à un voisin ou au gardien"),
+            TrackingSubStatus::CollectedAtPickupPoint => write!(f, "Retiré en point relais"),
+            TrackingSubStatus::FailedAttempt => write!(f, "Tentative de livraison infructueuse"),
+            TrackingSubStatus::AddressIssue => write!(f, "Adresse incorrecte ou incomplète"),
+            TrackingSubStatus::HeldAtCustoms => write!(f, "Retenu en douane"),
+            TrackingSubStatus::CustomsPaymentRequired => write!(f, "Droits de douane à régler"),
+            TrackingSubStatus::RefusedByRecipient => write!(f, "Refusé par le destinataire"),
+            TrackingSubStatus::Damaged => write!(f, "Endommagé"),
+            TrackingSubStatus::Lost => write!(f, "Perdu"),
+            TrackingSubStatus::Delayed => write!(f, "Retardé"),
+            TrackingSubStatus::Incident => write!(f, "Incident"),
+            TrackingSubStatus::ReturnToSender => write!(f, "En retour vers l'expéditeur"),
+            TrackingSubStatus::ReturnedToSender => write!(f, "Retourné à l'expéditeur"),
+            TrackingSubStatus::Unknown => write!(f, "Inconnu"),
+        }
+    }
+}
+
 /// Représente un événement de suivi d'un colis
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TrackingEvent {
     pub timestamp: DateTime<Utc>,
     pub status: ShipmentStatus,
+    #[serde(default)]
+    pub sub_status: TrackingSubStatus,
     pub location: Option<String>,
     pub description: String,
-    pub raw_status: String,
+    pub raw_status: String,  // Code d'origine du transporteur, conservé tel quel
+}
+
+impl TrackingEvent {
+    /// Crée un événement à partir du code brut d'un transporteur, normalisé sur deux niveaux
+    pub fn from_carrier_status(
+        carrier: CarrierCode,
+        raw_status: &str,
+        timestamp: DateTime<Utc>,
+        description: &str
+    ) -> Self {
+        let sub_status = crate::utils::formatting::normalize_sub_status(raw_status, carrier);
+
+        Self {
+            timestamp,
+            status: sub_status.status(),
+            sub_status,
+            location: None,
+            description: description.to_string(),
+            raw_status: raw_status.to_string(),
+        }
+    }
+
+    /// Renseigne le lieu de l'événement
+    pub fn with_location(mut self, location: &str) -> Self {
+        self.location = Some(location.to_string());
+        self
+    }
+
+    /// Indique si le destinataire doit agir
+    pub fn requires_action(&self) -> bool {
+        self.sub_status.requires_action()
+    }
 }
 
 /// Représente les informations complètes de suivi d'un colis
@@ -260,6 +419,65 @@ pub struct TrackingInfo {
     pub signature_name: Option<String>,
 }
 
+impl TrackingInfo {
+    /// Événement le plus récent
+    pub fn latest_event(&self) -> Option<&TrackingEvent> {
+        self.events.iter().max_by_key(|event| event.timestamp)
+    }
+
+    /// Sous-statut du dernier événement
+    pub fn sub_status(&self) -> TrackingSubStatus {
+        self.latest_event().map(|event| event.sub_status).unwrap_or_default()
+    }
+
+    /// Indique si le destinataire doit agir pour que la livraison aboutisse
+    pub fn requires_action(&self) -> bool {
+        self.sub_status().requires_action()
+    }
+}
+
+/// Issue de l'interrogation d'un transporteur pour un numéro de suivi
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttemptOutcome {
+    Found,
+    NotFound,  // Numéro inconnu du transporteur : le suivant est interrogé
+    Failed(String),  // Erreur du transporteur, qui interrompt la recherche
+}
+
+/// Transporteur interrogé lors d'un suivi
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackingAttempt {
+    pub carrier: CarrierCode,
+    pub outcome: AttemptOutcome,
+}
+
+/// Résultat d'un suivi avec la liste des transporteurs interrogés, dans l'ordre
+#[derive(Debug)]
+pub struct TrackingLookup {
+    pub tracking_number: String,  // Numéro normalisé
+    pub attempts: Vec<TrackingAttempt>,
+    pub result: Result<TrackingInfo, crate::errors::DeliveryError>,
+}
+
+impl TrackingLookup {
+    /// Transporteur ayant fourni le relevé
+    pub fn carrier(&self) -> Option<CarrierCode> {
+        self.attempts.iter()
+            .find(|attempt| attempt.outcome == AttemptOutcome::Found)
+            .map(|attempt| attempt.carrier)
+    }
+
+    /// Transporteurs interrogés, dans l'ordre
+    pub fn attempted_carriers(&self) -> Vec<CarrierCode> {
+        self.attempts.iter().map(|attempt| attempt.carrier).collect()
+    }
+
+    /// Relevé de suivi ou erreur
+    pub fn into_result(self) -> Result<TrackingInfo, crate::errors::DeliveryError> {
+        self.result
+    }
+}
+
 /// Représente une étiquette d'expédition générée
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ShippingLabel {
@@ -269,6 +487,23 @@ pub struct ShippingLabel {
     pub label_data: Vec<u8>,
     pub created_at: DateTime<Utc>,
     pub expires_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub dimensions: Option<LabelDimensions>,  // Dimensions réelles de l'étiquette produite
+    #[serde(default = "default_copies")]
+    pub copies: u32,  // Exemplaires demandés (pages PDF, ^PQ en ZPL, à répéter à l'impression en PNG)
+}
+
+fn default_copies() -> u32 {
+    1
+}
+
+/// Dimensions réelles d'une étiquette produite
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LabelDimensions {
+    pub width_mm: f64,
+    pub height_mm: f64,
+    pub dpi: Option<u32>,  // Absent pour les formats vectoriels (PDF)
+    pub rotation: LabelRotation,
 }
 
 impl ShippingLabel {
@@ -276,6 +511,33 @@ impl ShippingLabel {
     pub fn save_to_file(&self, path: &str) -> Result<(), std::io::Error> {
         std::fs::write(path, &self.label_data)
     }
+
+    /// Convertit l'étiquette dans un autre format
+    ///
+    /// Seules les étiquettes ZPL peuvent être converties (vers PDF ou PNG, à la résolution de l'étiquette) :
+    /// les commandes courantes (textes, cadres, Code 128, QR Code, DataMatrix) sont interprétées.
+    pub fn convert_to(&self, format: LabelFormat) -> Result<ShippingLabel, crate::errors::DeliveryError> {
+        if format == self.label_format {
+            return Ok(self.clone());
+        }
+
+        if self.label_format != LabelFormat::ZPL {
+            return Err(crate::errors::DeliveryError::LabelGenerationError(format!(
+                "Conversion {:?} vers {:?} non supportée", self.label_format, format
+            )));
+        }
+
+        let zpl = String::from_utf8_lossy(&self.label_data);
+        let dpi = self.dimensions.and_then(|dimensions| dimensions.dpi).unwrap_or(crate::labels::DEFAULT_DPI);
+        let layout = crate::labels::zpl::parse_zpl(&zpl, dpi)?;
+
+        Ok(ShippingLabel {
+            label_format: format,
+            label_data: crate::labels::render_layout(&layout, format, dpi, self.copies)?,
+            dimensions: Some(crate::labels::layout_dimensions(&layout, format, dpi)),
+            ..self.clone()
+        })
+    }
 }
 
 /// Format de l'étiquette d'expédition
@@ -286,6 +548,296 @@ pub enum LabelFormat {
     PNG,
 }
 
+/// Taille physique de l'étiquette d'expédition
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LabelSize {
+    /// 4 × 6 pouces (101,6 × 152,4 mm), standard des imprimantes thermiques
+    Inch4x6,
+    /// 10 × 15 cm
+    Cm10x15,
+    /// Feuille A4 (210 × 297 mm) portant une étiquette 10 × 15 cm, pour imprimante de bureau
+    A4,
+}
+
+impl LabelSize {
+    /// Dimensions de la page en mm (largeur, hauteur)
+    pub fn dimensions_mm(&self) -> (f64, f64) {
+        match self {
+            LabelSize::Inch4x6 => (101.6, 152.4),
+            LabelSize::Cm10x15 => (100.0, 150.0),
+            LabelSize::A4 => (210.0, 297.0),
+        }
+    }
+}
+
+/// Résolution d'impression des imprimantes thermiques
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LabelDpi {
+    /// 203 dpi (8 points/mm)
+    Dpi203,
+    /// 300 dpi (12 points/mm)
+    Dpi300,
+}
+
+impl LabelDpi {
+    /// Résolution en points par pouce
+    pub fn value(&self) -> u32 {
+        match self {
+            LabelDpi::Dpi203 => 203,
+            LabelDpi::Dpi300 => 300,
+        }
+    }
+}
+
+/// Rotation de l'étiquette, dans le sens horaire
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum LabelRotation {
+    #[default]
+    None,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+}
+
+impl LabelRotation {
+    /// Angle en degrés
+    pub fn degrees(&self) -> u32 {
+        match self {
+            LabelRotation::None => 0,
+            LabelRotation::Rotate90 => 90,
+            LabelRotation::Rotate180 => 180,
+            LabelRotation::Rotate270 => 270,
+        }
+    }
+
+    /// Indique si la largeur et la hauteur sont inversées
+    pub fn is_quarter_turn(&self) -> bool {
+        matches!(self, LabelRotation::Rotate90 | LabelRotation::Rotate270)
+    }
+}
+
+/// Options de génération d'une étiquette
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LabelOptions {
+    pub formats: Vec<LabelFormat>,  // Formats acceptés, par ordre de préférence
+    pub size: LabelSize,
+    pub dpi: LabelDpi,
+    pub rotation: LabelRotation,
+    pub copies: u32,
+}
+
+impl Default for LabelOptions {
+    fn default() -> Self {
+        Self {
+            formats: vec![LabelFormat::PDF],
+            size: LabelSize::Inch4x6,
+            dpi: LabelDpi::Dpi203,
+            rotation: LabelRotation::None,
+            copies: 1,
+        }
+    }
+}
+
+impl LabelOptions {
+    /// Crée des options pour un format préféré
+    pub fn new(format: LabelFormat) -> Self {
+        Self {
+            formats: vec![format],
+            ..Self::default()
+        }
+    }
+
+    /// Ajoute un format de repli, utilisé si les précédents ne sont pas disponibles
+    pub fn with_fallback(mut self, format: LabelFormat) -> Self {
+        if !self.formats.contains(&format) {
+            self.formats.push(format);
+        }
+        self
+    }
+
+    /// Définit la taille de l'étiquette
+    pub fn with_size(mut self, size: LabelSize) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Définit la résolution d'impression
+    pub fn with_dpi(mut self, dpi: LabelDpi) -> Self {
+        self.dpi = dpi;
+        self
+    }
+
+    /// Définit la rotation
+    pub fn with_rotation(mut self, rotation: LabelRotation) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Définit le nombre d'exemplaires (au moins 1)
+    pub fn with_copies(mut self, copies: u32) -> Self {
+        self.copies = copies.max(1);
+        self
+    }
+
+    /// Choisit le premier format préféré parmi ceux supportés
+    pub fn negotiate(&self, supported: &[LabelFormat]) -> Result<LabelFormat, crate::errors::DeliveryError> {
+        self.formats.iter()
+            .copied()
+            .find(|format| supported.contains(format))
+            .ok_or_else(|| crate::errors::DeliveryError::UnsupportedService(format!(
+                "Aucun format d'étiquette compatible parmi {:?} (disponibles: {:?})", self.formats, supported
+            )))
+    }
+}
+
+/// Mode de retour proposé au destinataire
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ReturnMethod {
+    /// Étiquette à imprimer par le client
+    PrintAtHome,
+    /// QR Code présenté au point de dépôt, qui imprime l'étiquette
+    QrCode,
+    /// Code de retour communiqué au point de dépôt, sans étiquette ni QR Code
+    Labelless,
+}
+
+/// Demande de retour (autorisation de retour de marchandise)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReturnRequest {
+    pub method: ReturnMethod,
+    pub rma: Option<String>,  // Numéro d'autorisation de retour, généré s'il est absent
+    pub reason: Option<String>,
+    pub label_options: LabelOptions,  // Pour les étiquettes à imprimer
+}
+
+impl ReturnRequest {
+    /// Crée une demande de retour pour un mode donné
+    pub fn new(method: ReturnMethod) -> Self {
+        Self {
+            method,
+            rma: None,
+            reason: None,
+            label_options: LabelOptions::default(),
+        }
+    }
+
+    /// Utilise un numéro d'autorisation de retour existant
+    pub fn with_rma(mut self, rma: &str) -> Self {
+        self.rma = Some(rma.to_string());
+        self
+    }
+
+    /// Indique le motif du retour
+    pub fn with_reason(mut self, reason: &str) -> Self {
+        self.reason = Some(reason.to_string());
+        self
+    }
+
+    /// Définit les options de l'étiquette retour
+    pub fn with_label_options(mut self, options: LabelOptions) -> Self {
+        self.label_options = options;
+        self
+    }
+}
+
+/// Retour créé auprès d'un transporteur, rattaché à l'envoi d'origine
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReturnShipment {
+    pub rma: String,
+    pub original_tracking_number: String,
+    pub carrier: CarrierCode,
+    pub method: ReturnMethod,
+    pub tracking_number: String,
+    pub label: Option<ShippingLabel>,  // Étiquette à imprimer (PrintAtHome)
+    pub qr_code: Option<Vec<u8>>,  // Image PNG du QR Code de dépôt (QrCode)
+    pub return_code: Option<String>,  // Code à présenter au point de dépôt
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Étiquette d'envoi accompagnée de son retour pré-généré
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LabelBundle {
+    pub outbound: ShippingLabel,
+    pub return_shipment: ReturnShipment,
+}
+
+impl LabelBundle {
+    /// Lot d'impression de l'étiquette d'envoi suivie de l'étiquette retour, si elle existe
+    pub fn to_batch(&self) -> crate::labels::batch::LabelBatch {
+        crate::labels::batch::LabelBatch::new(
+            std::iter::once(self.outbound.clone()).chain(self.return_shipment.label.clone())
+        )
+    }
+}
+
+/// Demande d'enlèvement de colis par un transporteur
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PickupRequest {
+    pub address: Address,
+    pub ready_at: DateTime<Utc>,  // Colis prêts à partir de
+    pub close_at: DateTime<Utc>,  // Fermeture du lieu d'enlèvement
+    pub package_count: u32,
+    pub total_weight: f64,  // en kg
+    pub tracking_numbers: Vec<String>,  // Envois concernés
+    pub instructions: Option<String>,
+}
+
+impl PickupRequest {
+    /// Crée une demande d'enlèvement pour une adresse et un créneau
+    pub fn new(address: Address, ready_at: DateTime<Utc>, close_at: DateTime<Utc>) -> Self {
+        Self {
+            address,
+            ready_at,
+            close_at,
+            package_count: 0,
+            total_weight: 0.0,
+            tracking_numbers: Vec::new(),
+            instructions: None,
+        }
+    }
+
+    /// Ajoute un envoi à enlever
+    pub fn with_parcel(mut self, tracking_number: &str, weight: f64) -> Self {
+        self.tracking_numbers.push(tracking_number.to_string());
+        self.package_count += 1;
+        self.total_weight += weight;
+        self
+    }
+
+    /// Définit le nombre de colis et le poids total sans les rattacher à des envois
+    pub fn with_packages(mut self, count: u32, total_weight: f64) -> Self {
+        self.package_count = count;
+        self.total_weight = total_weight;
+        self
+    }
+
+    /// Ajoute des instructions pour le chauffeur
+    pub fn with_instructions(mut self, instructions: &str) -> Self {
+        self.instructions = Some(instructions.to_string());
+        self
+    }
+}
+
+/// État d'un enlèvement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PickupStatus {
+    Scheduled,
+    Cancelled,
+}
+
+/// Enlèvement confirmé par un transporteur
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Pickup {
+    pub confirmation_number: String,
+    pub carrier: CarrierCode,
+    pub status: PickupStatus,
+    pub request: PickupRequest,  // Tel que confirmé par le transporteur (créneau éventuellement ajusté)
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
 /// Abstraction pour un transporteur
 pub trait Carrier {
     /// Obtient le code du transporteur
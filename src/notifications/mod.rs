@@ -0,0 +1,482 @@
+/// Envois de notifications : SMTP (remplaçant), fichier et journal
+pub mod sinks;
+
+/// Limites de longueur et segmentation des SMS
+pub mod sms;
+
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::errors::DeliveryError;
+use crate::i18n::{Catalog, Locale, Localize};
+use crate::models::{Parcel, ShipmentStatus, TrackingEvent, TrackingInfo, TrackingSubStatus};
+use crate::utils::catalog::{CatalogTables, YamlCatalog};
+use sms::SmsMessage;
+
+/// Canal de notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Channel {
+    Email,
+    Sms,
+}
+
+/// Modèle de message pour une étape de l'acheminement
+///
+/// Variables `{nom}` ; `{?nom}...{/nom}` n'est rendu que si la variable est renseignée.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MessageTemplate {
+    pub subject: String,
+    pub text: String,
+    #[serde(default)]
+    pub html: Option<String>,  // Contenu du corps HTML (construit à partir du texte si absent)
+    pub sms: String,
+}
+
+/// Modèles de notification par langue et par étape
+///
+/// Document YAML : `fr: { status: { Delivered: { subject, text, html, sms } }, sub_status: { ... } }`.
+/// Le modèle d'un sous-statut est préféré à celui de son statut ; une langue sans
+/// modèle pour l'étape se replie sur la langue de repli (l'anglais par défaut), puis le français.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NotificationTemplates {
+    templates: CatalogTables<Locale, MessageTemplate>,  // Par langue, clé `status.Delivered`
+    fallback: Locale,
+}
+
+impl Default for NotificationTemplates {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl YamlCatalog for NotificationTemplates {
+    const NAME: &'static str = "Modèles de notification";
+    const EMBEDDED: &'static str = include_str!("templates.yaml");
+
+    fn from_yaml(yaml: &str) -> Result<Self, DeliveryError> {
+        Ok(Self { templates: CatalogTables::from_sectioned_yaml(yaml, Self::NAME)?, ..Self::new() })
+    }
+
+    fn with_overrides(mut self, overrides: NotificationTemplates) -> Self {
+        self.templates.extend(overrides.templates);
+        self
+    }
+}
+
+impl NotificationTemplates {
+    /// Crée un jeu de modèles vide
+    pub fn new() -> Self {
+        Self {
+            templates: CatalogTables::default(),
+            fallback: Locale::En,
+        }
+    }
+
+    /// Ajoute ou remplace le modèle d'un statut
+    pub fn with_status_template(mut self, locale: Locale, status: ShipmentStatus, template: MessageTemplate) -> Self {
+        self.templates.insert(locale, format!("status.{:?}", status), template);
+        self
+    }
+
+    /// Ajoute ou remplace le modèle d'un sous-statut
+    pub fn with_sub_status_template(mut self, locale: Locale, sub_status: TrackingSubStatus, template: MessageTemplate) -> Self {
+        self.templates.insert(locale, format!("sub_status.{:?}", sub_status), template);
+        self
+    }
+
+    /// Change la langue de repli (l'anglais par défaut)
+    pub fn with_fallback(mut self, fallback: Locale) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    /// Modèle à utiliser pour une étape, avec la langue retenue
+    ///
+    /// Aucun modèle pour une étape sans notification (statut inconnu par exemple).
+    pub fn find(&self, locale: Locale, status: ShipmentStatus, sub_status: TrackingSubStatus) -> Option<(Locale, &MessageTemplate)> {
+        let keys = [format!("sub_status.{:?}", sub_status), format!("status.{:?}", status)];
+
+        self.templates.find_localized(locale, self.fallback, |locale, templates| {
+            keys.iter().find_map(|key| templates.get(key)).map(|template| (locale, template))
+        })
+    }
+}
+
+/// Message rendu pour une étape, prêt à être adressé par e-mail ou SMS
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NotificationContent {
+    pub tracking_number: String,
+    pub status: ShipmentStatus,
+    pub sub_status: TrackingSubStatus,
+    pub locale: Locale,
+    pub subject: String,
+    pub text: String,
+    pub html: String,  // Document HTML complet
+    pub sms: SmsMessage,
+}
+
+impl NotificationContent {
+    /// Notification par e-mail
+    pub fn to_email(&self, address: &str) -> Notification {
+        Notification {
+            channel: Channel::Email,
+            recipient: address.to_string(),
+            subject: Some(self.subject.clone()),
+            text: self.text.clone(),
+            html: Some(self.html.clone()),
+            ..self.notification()
+        }
+    }
+
+    /// Notification par SMS
+    pub fn to_sms(&self, phone: &str) -> Notification {
+        Notification {
+            channel: Channel::Sms,
+            recipient: phone.to_string(),
+            subject: None,
+            text: self.sms.text.clone(),
+            html: None,
+            ..self.notification()
+        }
+    }
+
+    fn notification(&self) -> Notification {
+        Notification {
+            channel: Channel::Email,
+            recipient: String::new(),
+            subject: None,
+            text: String::new(),
+            html: None,
+            tracking_number: self.tracking_number.clone(),
+            status: self.status,
+            sub_status: self.sub_status,
+            locale: self.locale,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// Notification adressée à un destinataire
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Notification {
+    pub channel: Channel,
+    pub recipient: String,  // Adresse e-mail ou numéro de téléphone
+    pub subject: Option<String>,  // E-mail uniquement
+    pub text: String,
+    pub html: Option<String>,  // E-mail uniquement
+    pub tracking_number: String,
+    pub status: ShipmentStatus,
+    pub sub_status: TrackingSubStatus,
+    pub locale: Locale,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Trait pour l'envoi des notifications
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    /// Envoie une notification
+    async fn send(&self, notification: &Notification) -> Result<(), DeliveryError>;
+
+    /// Version synchrone (bloquante) de send
+    fn send_blocking(&self, notification: &Notification) -> Result<(), DeliveryError>;
+
+    /// Indique si l'envoi prend en charge un canal
+    fn supports(&self, _channel: Channel) -> bool {
+        true
+    }
+}
+
+/// Rendu des notifications à partir du suivi et du colis
+#[derive(Debug, Clone)]
+pub struct NotificationRenderer {
+    templates: NotificationTemplates,
+    catalog: Option<Catalog>,  // Traductions des libellés (sinon les traductions actives)
+    variables: BTreeMap<String, String>,  // Variables fixes (nom de la boutique, service client…)
+    tracking_url: Option<String>,  // Lien de suivi, `{tracking_number}` et `{carrier}` remplacés
+    sms_max_segments: usize,
+}
+
+impl Default for NotificationRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NotificationRenderer {
+    /// Crée un rendu avec les modèles intégrés et des SMS d'un seul segment
+    pub fn new() -> Self {
+        Self {
+            templates: NotificationTemplates::embedded(),
+            catalog: None,
+            variables: BTreeMap::new(),
+            tracking_url: None,
+            sms_max_segments: 1,
+        }
+    }
+
+    /// Remplace les modèles
+    pub fn with_templates(mut self, templates: NotificationTemplates) -> Self {
+        self.templates = templates;
+        self
+    }
+
+    /// Utilise des traductions propres au rendu pour les statuts et descriptions
+    pub fn with_catalog(mut self, catalog: Catalog) -> Self {
+        self.catalog = Some(catalog);
+        self
+    }
+
+    /// Ajoute une variable fixe, disponible dans tous les modèles
+    pub fn with_variable(mut self, name: &str, value: &str) -> Self {
+        self.variables.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    /// Définit le lien de suivi (ex: `https://boutique.fr/suivi/{tracking_number}`)
+    pub fn with_tracking_url(mut self, pattern: &str) -> Self {
+        self.tracking_url = Some(pattern.to_string());
+        self
+    }
+
+    /// Définit le nombre maximal de segments d'un SMS (1 par défaut)
+    pub fn with_sms_max_segments(mut self, segments: usize) -> Self {
+        self.sms_max_segments = segments.max(1);
+        self
+    }
+
+    /// Modèles utilisés
+    pub fn templates(&self) -> &NotificationTemplates {
+        &self.templates
+    }
+
+    /// Rend la notification du dernier événement d'un suivi
+    ///
+    /// Retourne `None` si aucun modèle ne correspond à l'étape.
+    pub fn render(
+        &self,
+        info: &TrackingInfo,
+        parcel: Option<&Parcel>,
+        locale: Locale
+    ) -> Result<Option<NotificationContent>, DeliveryError> {
+        self.render_event(info, info.latest_event(), parcel, locale)
+    }
+
+    /// Rend la notification d'un événement donné (à défaut, du statut du suivi)
+    pub fn render_event(
+        &self,
+        info: &TrackingInfo,
+        event: Option<&TrackingEvent>,
+        parcel: Option<&Parcel>,
+        locale: Locale
+    ) -> Result<Option<NotificationContent>, DeliveryError> {
+        let (status, sub_status) = match event {
+            Some(event) => (event.status, event.sub_status),
+            None => (info.status, TrackingSubStatus::Unknown),
+        };
+        let Some((locale, template)) = self.templates.find(locale, status, sub_status) else {
+            return Ok(None);
+        };
+
+        let variables = self.variables(info, event, parcel, locale);
+        let subject = render_template(&template.subject, &variables, false)?
+            .split_whitespace().collect::<Vec<_>>().join(" ");
+        let text = render_template(&template.text, &variables, false)?;
+        let body = match &template.html {
+            Some(html) => render_template(html, &variables, true)?,
+            None => text_to_html(&text, variables.get("tracking_url").map(String::as_str)),
+        };
+        let sms = SmsMessage::new(&render_template(&template.sms, &variables, false)?, self.sms_max_segments);
+
+        Ok(Some(NotificationContent {
+            tracking_number: info.tracking_number.clone(),
+            status,
+            sub_status,
+            locale,
+            html: html_document(&subject, &body, locale),
+            subject,
+            text,
+            sms,
+        }))
+    }
+
+    /// Rend et envoie les notifications d'un suivi aux coordonnées du destinataire du colis
+    ///
+    /// Un e-mail et un SMS sont envoyés selon les coordonnées connues et les canaux
+    /// pris en charge. Sans langue précisée, celle du pays du destinataire est utilisée.
+    pub async fn notify(
+        &self,
+        notifier: &dyn Notifier,
+        info: &TrackingInfo,
+        parcel: &Parcel,
+        locale: Option<Locale>
+    ) -> Result<Vec<Notification>, DeliveryError> {
+        let notifications = self.notifications(notifier, info, parcel, locale)?;
+        for notification in &notifications {
+            notifier.send(notification).await?;
+        }
+        Ok(notifications)
+    }
+
+    /// Version synchrone de notify
+    pub fn notify_blocking(
+        &self,
+        notifier: &dyn Notifier,
+        info: &TrackingInfo,
+        parcel: &Parcel,
+        locale: Option<Locale>
+    ) -> Result<Vec<Notification>, DeliveryError> {
+        let notifications = self.notifications(notifier, info, parcel, locale)?;
+        for notification in &notifications {
+            notifier.send_blocking(notification)?;
+        }
+        Ok(notifications)
+    }
+
+    /// Notifications à envoyer au destinataire d'un colis
+    fn notifications(
+        &self,
+        notifier: &dyn Notifier,
+        info: &TrackingInfo,
+        parcel: &Parcel,
+        locale: Option<Locale>
+    ) -> Result<Vec<Notification>, DeliveryError> {
+        let locale = locale.unwrap_or_else(|| Locale::for_country(&parcel.recipient.country));
+        let Some(content) = self.render(info, Some(parcel), locale)? else {
+            return Ok(Vec::new());
+        };
+
+        let mut notifications = Vec::new();
+
+        if let Some(email) = contact(&parcel.recipient.email) && notifier.supports(Channel::Email) {
+            notifications.push(content.to_email(email));
+        }
+        if let Some(phone) = contact(&parcel.recipient.phone) && notifier.supports(Channel::Sms) {
+            notifications.push(content.to_sms(phone));
+        }
+
+        Ok(notifications)
+    }
+
+    /// Variables disponibles dans les modèles ; celles qui sont inconnues sont vides
+    fn variables(
+        &self,
+        info: &TrackingInfo,
+        event: Option<&TrackingEvent>,
+        parcel: Option<&Parcel>,
+        locale: Locale
+    ) -> BTreeMap<String, String> {
+        let active;
+        let catalog = match &self.catalog {
+            Some(catalog) => catalog,
+            None => {
+                active = crate::i18n::catalog();
+                &active
+            }
+        };
+        let date = |date: Option<DateTime<Utc>>| {
+            date.map(|date| date.format(locale.date_format()).to_string()).unwrap_or_default()
+        };
+
+        let mut variables = self.variables.clone();
+        let mut set = |name: &str, value: String| {
+            variables.insert(name.to_string(), value);
+        };
+
+        set("tracking_number", info.tracking_number.clone());
+        set("carrier", info.carrier.to_string());
+        set("status", event.map_or(info.status, |event| event.status).localize_with(catalog, locale));
+        set("sub_status", event.map(|event| event.sub_status).unwrap_or_default().localize_with(catalog, locale));
+        set("description", event.map(|event| event.localize_with(catalog, locale)).unwrap_or_default());
+        set("carrier_description", event.map(|event| event.description.clone()).unwrap_or_default());
+        set("location", event.and_then(|event| event.location.clone()).unwrap_or_default());
+        set("event_date", date(event.map(|event| event.timestamp)));
+        set("estimated_delivery", date(info.estimated_delivery));
+        set("recipient_name", parcel.map(|parcel| parcel.recipient.name.clone()).unwrap_or_default());
+        set("sender_name", parcel.map(|parcel| {
+            parcel.sender.company.clone().filter(|company| !company.is_empty()).unwrap_or_else(|| parcel.sender.name.clone())
+        }).unwrap_or_default());
+        set("reference", parcel.and_then(|parcel| parcel.reference.clone()).unwrap_or_default());
+
+        let tracking_url = self.tracking_url.as_ref().map(|pattern| {
+            pattern.replace("{tracking_number}", &info.tracking_number).replace("{carrier}", &info.carrier.to_string())
+        });
+        set("tracking_url", tracking_url.unwrap_or_default());
+
+        variables
+    }
+}
+
+/// Coordonnée renseignée (adresse e-mail ou téléphone)
+fn contact(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|value| !value.is_empty())
+}
+
+/// Remplace les variables d'un modèle, échappées pour le HTML au besoin
+fn render_template(template: &str, variables: &BTreeMap<String, String>, html: bool) -> Result<String, DeliveryError> {
+    let invalid = |message: String| DeliveryError::NotificationError(format!("Modèle invalide: {}", message));
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        let end = start + rest[start..].find('}').ok_or_else(|| invalid("accolade non fermée".to_string()))?;
+        let tag = &rest[start + 1..end];
+        rest = &rest[end + 1..];
+
+        if let Some(name) = tag.strip_prefix('?') {
+            // Section conditionnelle, rendue si la variable est renseignée
+            let close = format!("{{/{}}}", name);
+            let section_end = rest.find(&close).ok_or_else(|| invalid(format!("section {{?{}}} non fermée", name)))?;
+            if variables.get(name).is_some_and(|value| !value.is_empty()) {
+                output.push_str(&render_template(&rest[..section_end], variables, html)?);
+            }
+            rest = &rest[section_end + close.len()..];
+        } else {
+            let value = variables.get(tag).ok_or_else(|| invalid(format!("variable inconnue {{{}}}", tag)))?;
+            output.push_str(&if html { escape_html(value) } else { value.clone() });
+        }
+    }
+
+    output.push_str(rest);
+    Ok(output)
+}
+
+/// Échappe les caractères spéciaux HTML
+fn escape_html(value: &str) -> String {
+    value.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Corps HTML construit à partir du texte : paragraphes, retours à la ligne et lien de suivi
+fn text_to_html(text: &str, tracking_url: Option<&str>) -> String {
+    let link = tracking_url.filter(|url| !url.is_empty()).map(|url| {
+        let url = escape_html(url);
+        (url.clone(), format!("<a href=\"{}\">{}</a>", url, url))
+    });
+
+    text.split("\n\n")
+        .filter(|paragraph| !paragraph.trim().is_empty())
+        .map(|paragraph| {
+            let mut paragraph = escape_html(paragraph.trim()).replace('\n', "<br>\n");
+            if let Some((url, anchor)) = &link {
+                paragraph = paragraph.replace(url.as_str(), anchor);
+            }
+            format!("<p>{}</p>", paragraph)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Document HTML complet d'un e-mail
+fn html_document(subject: &str, body: &str, locale: Locale) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"{}\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n{}\n</body>\n</html>\n",
+        locale, escape_html(subject), body
+    )
+}
@@ -0,0 +1,225 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::errors::DeliveryError;
+use crate::notifications::{Channel, Notification, Notifier};
+
+/// Port SMTP de soumission par défaut
+pub const DEFAULT_SMTP_PORT: u16 = 587;
+
+/// Remplaçant d'un envoi SMTP
+///
+/// Construit le message MIME (texte et HTML) tel qu'il serait soumis au serveur,
+/// sans s'y connecter : les messages sont conservés dans une boîte d'envoi, à
+/// consulter avec `outbox`. Ne prend en charge que les e-mails.
+#[derive(Debug)]
+pub struct SmtpNotifier {
+    host: String,
+    port: u16,
+    from: String,
+    outbox: Mutex<Vec<String>>,
+}
+
+impl SmtpNotifier {
+    /// Crée un envoi SMTP depuis une adresse d'expédition
+    pub fn new(host: &str, from: &str) -> Self {
+        Self {
+            host: host.to_string(),
+            port: DEFAULT_SMTP_PORT,
+            from: from.to_string(),
+            outbox: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Définit le port du serveur
+    pub fn with_port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    /// Serveur auquel les messages seraient soumis (`hôte:port`)
+    pub fn server(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+
+    /// Messages MIME construits, du plus ancien au plus récent
+    pub fn outbox(&self) -> Vec<String> {
+        self.outbox.lock().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+
+    /// Message MIME d'une notification
+    pub fn to_mime(&self, notification: &Notification) -> Result<String, DeliveryError> {
+        if notification.channel != Channel::Email {
+            return Err(DeliveryError::UnsupportedService(format!(
+                "Canal {:?} non pris en charge par l'envoi SMTP", notification.channel
+            )));
+        }
+        if notification.recipient.contains(['\r', '\n']) || !notification.recipient.contains('@') {
+            return Err(DeliveryError::NotificationError(format!(
+                "Adresse e-mail invalide: {:?}", notification.recipient
+            )));
+        }
+
+        let subject = encode_header(&notification.subject.as_deref().unwrap_or_default().replace(['\r', '\n'], " "));
+        let mut message = format!(
+            "From: {}\r\nTo: {}\r\nSubject: {}\r\nDate: {}\r\nContent-Language: {}\r\nMIME-Version: 1.0\r\n",
+            self.from, notification.recipient, subject, notification.created_at.to_rfc2822(), notification.locale
+        );
+
+        let text = crlf(&notification.text);
+        match &notification.html {
+            Some(html) => {
+                let boundary = format!("=_{}", Uuid::new_v4().simple());
+                message.push_str(&format!(
+                    "Content-Type: multipart/alternative; boundary=\"{boundary}\"\r\n\r\n\
+                     --{boundary}\r\nContent-Type: text/plain; charset=UTF-8\r\nContent-Transfer-Encoding: 8bit\r\n\r\n{}\r\n\
+                     --{boundary}\r\nContent-Type: text/html; charset=UTF-8\r\nContent-Transfer-Encoding: 8bit\r\n\r\n{}\r\n\
+                     --{boundary}--\r\n",
+                    text, crlf(html)
+                ));
+            }
+            None => {
+                message.push_str(&format!(
+                    "Content-Type: text/plain; charset=UTF-8\r\nContent-Transfer-Encoding: 8bit\r\n\r\n{}\r\n", text
+                ));
+            }
+        }
+
+        Ok(message)
+    }
+}
+
+#[async_trait]
+impl Notifier for SmtpNotifier {
+    async fn send(&self, notification: &Notification) -> Result<(), DeliveryError> {
+        self.send_blocking(notification)
+    }
+
+    fn send_blocking(&self, notification: &Notification) -> Result<(), DeliveryError> {
+        let message = self.to_mime(notification)?;
+        log::debug!("Message SMTP pour {} via {} ({} octets)", notification.recipient, self.server(), message.len());
+        self.outbox.lock().unwrap_or_else(PoisonError::into_inner).push(message);
+        Ok(())
+    }
+
+    fn supports(&self, channel: Channel) -> bool {
+        channel == Channel::Email
+    }
+}
+
+/// Fins de ligne CRLF imposées par SMTP
+fn crlf(text: &str) -> String {
+    text.replace("\r\n", "\n").replace('\n', "\r\n")
+}
+
+/// En-tête encodé selon la RFC 2047 (« Q ») s'il contient des caractères non ASCII
+fn encode_header(value: &str) -> String {
+    if value.is_ascii() {
+        return value.to_string();
+    }
+
+    let encoded: String = value.bytes()
+        .map(|byte| match byte {
+            b' ' => "_".to_string(),
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'!' | b'*' | b'+' | b'-' | b'/' => (byte as char).to_string(),
+            _ => format!("={:02X}", byte),
+        })
+        .collect();
+    format!("=?UTF-8?Q?{}?=", encoded)
+}
+
+/// Journal des notifications dans un fichier, une notification JSON par ligne
+///
+/// Destiné aux tests et à la recette : rien n'est envoyé.
+#[derive(Debug)]
+pub struct FileNotifier {
+    path: PathBuf,
+    lock: Mutex<()>,  // Sérialise les écritures concurrentes
+}
+
+impl FileNotifier {
+    /// Écrit dans un fichier, créé au premier envoi
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Chemin du fichier
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Relit les notifications écrites dans un fichier
+    pub fn read(path: impl AsRef<Path>) -> Result<Vec<Notification>, DeliveryError> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        std::fs::read_to_string(path)?
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str(line).map_err(|e| DeliveryError::SerializationError(format!(
+                "Journal de notifications {} illisible: {}", path.display(), e
+            ))))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Notifier for FileNotifier {
+    async fn send(&self, notification: &Notification) -> Result<(), DeliveryError> {
+        self.send_blocking(notification)
+    }
+
+    fn send_blocking(&self, notification: &Notification) -> Result<(), DeliveryError> {
+        let line = serde_json::to_string(notification)?;
+        let _guard = self.lock.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if let Some(parent) = self.path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::fs::OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}", line)?;
+        Ok(())
+    }
+}
+
+/// Notifications écrites dans le journal de l'application (niveau info)
+///
+/// Destiné aux tests et au développement : rien n'est envoyé.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LogNotifier;
+
+impl LogNotifier {
+    /// Crée un journal de notifications
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl Notifier for LogNotifier {
+    async fn send(&self, notification: &Notification) -> Result<(), DeliveryError> {
+        self.send_blocking(notification)
+    }
+
+    fn send_blocking(&self, notification: &Notification) -> Result<(), DeliveryError> {
+        match notification.channel {
+            Channel::Email => log::info!(
+                "Notification e-mail {} à {}: {}",
+                notification.tracking_number, notification.recipient, notification.subject.as_deref().unwrap_or_default()
+            ),
+            Channel::Sms => log::info!(
+                "Notification SMS {} à {}: {}", notification.tracking_number, notification.recipient, notification.text
+            ),
+        }
+        Ok(())
+    }
+}
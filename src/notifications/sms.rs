@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+
+/// Alphabet GSM 03.38 de base (un septet par caractère)
+const GSM7_BASIC: &str = "@£$¥èéùìòÇ\nØø\rÅåΔ_ΦΓΛΩΠΨΣΘΞÆæßÉ !\"#¤%&'()*+,-./0123456789:;<=>?\
+    ¡ABCDEFGHIJKLMNOPQRSTUVWXYZÄÖÑÜ§¿abcdefghijklmnopqrstuvwxyzäöñüà";
+
+/// Table d'extension GSM 03.38 (deux septets par caractère : échappement + code)
+const GSM7_EXTENSION: &str = "\u{c}^{}\\[~]|€";
+
+/// Codage d'un SMS, qui détermine sa longueur maximale
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SmsEncoding {
+    Gsm7,  // 160 caractères, 153 par segment d'un message long
+    Ucs2,  // 70 caractères, 67 par segment d'un message long
+}
+
+impl SmsEncoding {
+    /// Codage nécessaire pour un texte
+    pub fn for_text(text: &str) -> Self {
+        if text.chars().all(|c| GSM7_BASIC.contains(c) || GSM7_EXTENSION.contains(c)) {
+            SmsEncoding::Gsm7
+        } else {
+            SmsEncoding::Ucs2
+        }
+    }
+
+    /// Longueur maximale d'un message d'un seul segment, en unités du codage
+    pub fn single_limit(self) -> usize {
+        match self {
+            SmsEncoding::Gsm7 => 160,
+            SmsEncoding::Ucs2 => 70,
+        }
+    }
+
+    /// Longueur d'un segment de message long (l'en-tête de concaténation en prend une part)
+    pub fn segment_limit(self) -> usize {
+        match self {
+            SmsEncoding::Gsm7 => 153,
+            SmsEncoding::Ucs2 => 67,
+        }
+    }
+
+    /// Longueur d'un caractère, en unités du codage
+    fn units(self, c: char) -> usize {
+        match self {
+            SmsEncoding::Gsm7 if GSM7_EXTENSION.contains(c) => 2,
+            SmsEncoding::Gsm7 => 1,
+            SmsEncoding::Ucs2 => c.len_utf16(),
+        }
+    }
+}
+
+/// Texte prêt à être envoyé par SMS
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SmsMessage {
+    pub text: String,
+    pub encoding: SmsEncoding,
+    pub segments: usize,  // Nombre de SMS facturés
+    pub truncated: bool,  // Texte raccourci pour respecter la limite de segments
+}
+
+impl SmsMessage {
+    /// Prépare un texte, raccourci au besoin pour tenir en `max_segments` segments
+    ///
+    /// Les espaces superflus sont supprimés ; un texte raccourci se termine par
+    /// des points de suspension.
+    pub fn new(text: &str, max_segments: usize) -> Self {
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        let encoding = SmsEncoding::for_text(&text);
+        let max_units = match max_segments {
+            0 | 1 => encoding.single_limit(),
+            segments => encoding.segment_limit() * segments,
+        };
+
+        if length(&text, encoding) <= max_units {
+            return Self { segments: segment_count(&text, encoding), text, encoding, truncated: false };
+        }
+
+        let ellipsis = match encoding {
+            SmsEncoding::Gsm7 => "...",
+            SmsEncoding::Ucs2 => "…",
+        };
+        let mut budget = max_units - length(ellipsis, encoding);
+        let mut shortened: String = text.chars()
+            .take_while(|c| {
+                let units = encoding.units(*c);
+                let fits = units <= budget;
+                budget = budget.saturating_sub(units);
+                fits
+            })
+            .collect();
+
+        // Coupe au dernier mot complet
+        if let Some(space) = shortened.rfind(' ') {
+            shortened.truncate(space);
+        }
+        shortened = shortened.trim_end_matches([',', ';', ':', '.', ' ']).to_string();
+        shortened.push_str(ellipsis);
+
+        Self { segments: segment_count(&shortened, encoding), text: shortened, encoding, truncated: true }
+    }
+}
+
+/// Longueur d'un texte en unités d'un codage
+fn length(text: &str, encoding: SmsEncoding) -> usize {
+    text.chars().map(|c| encoding.units(c)).sum()
+}
+
+/// Nombre de segments nécessaires pour un texte
+pub fn segment_count(text: &str, encoding: SmsEncoding) -> usize {
+    let units = length(text, encoding);
+    if units <= encoding.single_limit() {
+        1
+    } else {
+        units.div_ceil(encoding.segment_limit())
+    }
+}
@@ -0,0 +1,280 @@
+use std::time::Duration;
+
+use reqwest::header::CONTENT_TYPE;
+
+use crate::errors::DeliveryError;
+use crate::printing::{PrinterState, PrinterStatus};
+
+/// Port IPP par défaut
+pub const DEFAULT_PORT: u16 = 631;
+
+/// Type MIME des requêtes et réponses IPP
+const IPP_MIME: &str = "application/ipp";
+
+/// Version du protocole (IPP/2.0)
+const VERSION: [u8; 2] = [2, 0];
+
+/// Opérations utilisées
+const PRINT_JOB: u16 = 0x0002;
+const GET_PRINTER_ATTRIBUTES: u16 = 0x000B;
+
+/// Délimiteurs de groupes d'attributs
+const OPERATION_ATTRIBUTES: u8 = 0x01;
+const JOB_ATTRIBUTES: u8 = 0x02;
+const END_OF_ATTRIBUTES: u8 = 0x03;
+
+/// Types de valeurs
+const INTEGER: u8 = 0x21;
+const ENUM: u8 = 0x23;
+const TEXT: u8 = 0x41;
+const NAME: u8 = 0x42;
+const KEYWORD: u8 = 0x44;
+const URI: u8 = 0x45;
+const CHARSET: u8 = 0x47;
+const NATURAL_LANGUAGE: u8 = 0x48;
+const MIME_MEDIA_TYPE: u8 = 0x49;
+
+/// Réponse IPP décodée
+#[derive(Debug, Clone, PartialEq)]
+pub struct IppResponse {
+    pub status_code: u16,
+    pub request_id: u32,
+    /// Attributs dans l'ordre de la réponse, une entrée par valeur (valeurs brutes)
+    pub attributes: Vec<(String, u8, Vec<u8>)>,
+}
+
+impl IppResponse {
+    /// Indique si la requête a abouti (codes successful-*)
+    pub fn is_success(&self) -> bool {
+        self.status_code < 0x0100
+    }
+
+    /// Première valeur entière (integer ou enum) d'un attribut
+    pub fn integer(&self, name: &str) -> Option<i32> {
+        self.attributes.iter()
+            .find(|(attribute, tag, value)| attribute == name && matches!(*tag, INTEGER | ENUM) && value.len() == 4)
+            .map(|(_, _, value)| i32::from_be_bytes([value[0], value[1], value[2], value[3]]))
+    }
+
+    /// Valeurs textuelles d'un attribut
+    pub fn strings(&self, name: &str) -> Vec<String> {
+        self.attributes.iter()
+            .filter(|(attribute, tag, _)| attribute == name && *tag >= TEXT)
+            .map(|(_, _, value)| String::from_utf8_lossy(value).to_string())
+            .collect()
+    }
+}
+
+/// Ajoute un attribut à une requête
+fn attribute(request: &mut Vec<u8>, tag: u8, name: &str, value: &[u8]) {
+    request.push(tag);
+    request.extend_from_slice(&(name.len() as u16).to_be_bytes());
+    request.extend_from_slice(name.as_bytes());
+    request.extend_from_slice(&(value.len() as u16).to_be_bytes());
+    request.extend_from_slice(value);
+}
+
+/// En-tête et attributs d'opération communs à toutes les requêtes
+fn request(operation: u16, request_id: u32, printer_uri: &str) -> Vec<u8> {
+    let mut request = Vec::new();
+    request.extend_from_slice(&VERSION);
+    request.extend_from_slice(&operation.to_be_bytes());
+    request.extend_from_slice(&request_id.to_be_bytes());
+    request.push(OPERATION_ATTRIBUTES);
+    attribute(&mut request, CHARSET, "attributes-charset", b"utf-8");
+    attribute(&mut request, NATURAL_LANGUAGE, "attributes-natural-language", b"fr");
+    attribute(&mut request, URI, "printer-uri", printer_uri.as_bytes());
+    attribute(&mut request, NAME, "requesting-user-name", b"zyou_delivery");
+    request
+}
+
+/// Construit une requête Print-Job suivie des données du document
+pub fn print_job_request(printer_uri: &str, job_name: &str, document_format: &str, copies: u32, data: &[u8]) -> Vec<u8> {
+    let mut request = request(PRINT_JOB, 1, printer_uri);
+    attribute(&mut request, NAME, "job-name", job_name.as_bytes());
+    attribute(&mut request, MIME_MEDIA_TYPE, "document-format", document_format.as_bytes());
+
+    if copies > 1 {
+        request.push(JOB_ATTRIBUTES);
+        attribute(&mut request, INTEGER, "copies", &(copies as i32).to_be_bytes());
+    }
+
+    request.push(END_OF_ATTRIBUTES);
+    request.extend_from_slice(data);
+    request
+}
+
+/// Construit une requête Get-Printer-Attributes limitée à l'état de l'imprimante
+pub fn printer_attributes_request(printer_uri: &str) -> Vec<u8> {
+    let mut request = request(GET_PRINTER_ATTRIBUTES, 1, printer_uri);
+
+    let requested = ["printer-state", "printer-state-reasons", "printer-state-message", "queued-job-count"];
+    for (index, name) in requested.iter().enumerate() {
+        // Les valeurs suivantes d'un attribut multivalué ont un nom vide
+        let attribute_name = if index == 0 { "requested-attributes" } else { "" };
+        attribute(&mut request, KEYWORD, attribute_name, name.as_bytes());
+    }
+
+    request.push(END_OF_ATTRIBUTES);
+    request
+}
+
+/// Décode une réponse IPP
+pub fn parse_response(bytes: &[u8]) -> Result<IppResponse, DeliveryError> {
+    let invalid = || DeliveryError::PrintError("Réponse IPP tronquée ou invalide".to_string());
+
+    if bytes.len() < 8 {
+        return Err(invalid());
+    }
+
+    let mut response = IppResponse {
+        status_code: u16::from_be_bytes([bytes[2], bytes[3]]),
+        request_id: u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]),
+        attributes: Vec::new(),
+    };
+
+    let mut position = 8;
+    let mut last_name = String::new();
+    let read_u16 = |position: usize| -> Option<usize> {
+        bytes.get(position..position + 2).map(|b| u16::from_be_bytes([b[0], b[1]]) as usize)
+    };
+
+    while let Some(&tag) = bytes.get(position) {
+        position += 1;
+
+        match tag {
+            END_OF_ATTRIBUTES => break,
+            // Délimiteur de groupe
+            0x00..=0x0F => continue,
+            _ => {}
+        }
+
+        let name_length = read_u16(position).ok_or_else(invalid)?;
+        let name = bytes.get(position + 2..position + 2 + name_length).ok_or_else(invalid)?;
+        position += 2 + name_length;
+
+        let value_length = read_u16(position).ok_or_else(invalid)?;
+        let value = bytes.get(position + 2..position + 2 + value_length).ok_or_else(invalid)?;
+        position += 2 + value_length;
+
+        if !name.is_empty() {
+            last_name = String::from_utf8_lossy(name).to_string();
+        }
+        response.attributes.push((last_name.clone(), tag, value.to_vec()));
+    }
+
+    Ok(response)
+}
+
+/// Libellé des principaux codes d'état IPP (RFC 8011)
+fn status_message(status_code: u16) -> &'static str {
+    match status_code {
+        0x0400 => "requête invalide",
+        0x0401 => "accès interdit",
+        0x0402 | 0x0403 => "authentification requise",
+        0x0406 => "imprimante introuvable",
+        0x040A => "format de document non supporté",
+        0x040B => "valeur d'attribut non supportée",
+        0x0500 => "erreur interne de l'imprimante",
+        0x0501 => "opération non supportée",
+        0x0502 => "service indisponible",
+        0x0506 => "imprimante à l'arrêt",
+        0x0507 => "imprimante occupée",
+        0x0508 => "travail annulé",
+        _ if status_code >= 0x0500 => "erreur de l'imprimante",
+        _ => "requête refusée",
+    }
+}
+
+/// Convertit une adresse ipp:// ou ipps:// en URL HTTP
+pub fn http_url(printer_uri: &str) -> Result<url::Url, DeliveryError> {
+    let (http, default_port) = match printer_uri.split_once("://") {
+        Some(("ipp", rest)) => (format!("http://{}", rest), true),
+        Some(("ipps", rest)) => (format!("https://{}", rest), true),
+        _ => (printer_uri.to_string(), false),
+    };
+
+    let mut url = url::Url::parse(&http)
+        .map_err(|e| DeliveryError::PrintError(format!("Adresse IPP invalide {}: {}", printer_uri, e)))?;
+
+    // Les schémas ipp et ipps utilisent le port 631, pas celui de HTTP
+    if default_port && url.port().is_none() {
+        let _ = url.set_port(Some(DEFAULT_PORT));
+    }
+
+    Ok(url)
+}
+
+/// Vérifie le code HTTP et le code d'état IPP de la réponse
+fn check_response(printer_uri: &str, http_status: reqwest::StatusCode, body: &[u8]) -> Result<IppResponse, DeliveryError> {
+    if !http_status.is_success() {
+        return Err(DeliveryError::PrintError(format!("{} a répondu HTTP {}", printer_uri, http_status)));
+    }
+
+    let response = parse_response(body)?;
+    if !response.is_success() {
+        let detail = response.strings("status-message").into_iter().next()
+            .unwrap_or_else(|| status_message(response.status_code).to_string());
+        return Err(DeliveryError::PrintError(format!(
+            "{} (code IPP 0x{:04x})", detail, response.status_code
+        )));
+    }
+
+    Ok(response)
+}
+
+fn connection_error(printer_uri: &str, e: reqwest::Error) -> DeliveryError {
+    DeliveryError::ConnectionError(format!("Imprimante {} injoignable: {}", printer_uri, e))
+}
+
+/// Envoie une requête IPP et retourne la réponse si elle a abouti
+pub async fn send(printer_uri: &str, request: Vec<u8>, timeout: Duration) -> Result<IppResponse, DeliveryError> {
+    let client = reqwest::Client::builder().timeout(timeout).build()?;
+    let response = client.post(http_url(printer_uri)?)
+        .header(CONTENT_TYPE, IPP_MIME)
+        .body(request)
+        .send()
+        .await
+        .map_err(|e| connection_error(printer_uri, e))?;
+
+    let status = response.status();
+    let body = response.bytes().await.map_err(|e| connection_error(printer_uri, e))?;
+    check_response(printer_uri, status, &body)
+}
+
+/// Version synchrone de send
+pub fn send_blocking(printer_uri: &str, request: Vec<u8>, timeout: Duration) -> Result<IppResponse, DeliveryError> {
+    let client = reqwest::blocking::Client::builder().timeout(timeout).build()?;
+    let response = client.post(http_url(printer_uri)?)
+        .header(CONTENT_TYPE, IPP_MIME)
+        .body(request)
+        .send()
+        .map_err(|e| connection_error(printer_uri, e))?;
+
+    let status = response.status();
+    let body = response.bytes().map_err(|e| connection_error(printer_uri, e))?;
+    check_response(printer_uri, status, &body)
+}
+
+/// Extrait l'état de l'imprimante d'une réponse Get-Printer-Attributes
+///
+/// Les motifs informatifs (suffixes -report et -warning) et « none » sont ignorés.
+pub fn printer_status(response: &IppResponse) -> PrinterStatus {
+    let state = match response.integer("printer-state") {
+        Some(4) => PrinterState::Processing,
+        Some(5) => PrinterState::Stopped,
+        _ => PrinterState::Idle,
+    };
+
+    let reasons = response.strings("printer-state-reasons").into_iter()
+        .filter(|reason| reason != "none" && !reason.ends_with("-report") && !reason.ends_with("-warning"))
+        .map(|reason| reason.trim_end_matches("-error").to_string())
+        .collect();
+
+    PrinterStatus {
+        state,
+        reasons,
+        message: response.strings("printer-state-message").into_iter().next().filter(|message| !message.is_empty()),
+        queued_jobs: response.integer("queued-job-count").map(|count| count.max(0) as u32),
+    }
+}
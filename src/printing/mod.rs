@@ -0,0 +1,315 @@
+/// Impression via IPP (Internet Printing Protocol)
+pub mod ipp;
+
+/// Impression brute sur le port TCP 9100
+pub mod raw;
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::DeliveryError;
+use crate::labels::{zpl, DEFAULT_DPI};
+use crate::models::{LabelDpi, LabelFormat, LabelOptions, LabelSize, ShippingLabel};
+
+/// Tolérance sur la taille du support, en mm
+const MEDIA_TOLERANCE_MM: f64 = 1.0;
+
+/// Délai d'attente par défaut des échanges avec l'imprimante
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Moyen d'accès à une imprimante réseau
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PrinterConnection {
+    /// Flux brut sur une socket TCP (port 9100 en général)
+    Raw { host: String, port: u16 },
+    /// Point d'accès IPP (ipp://, ipps:// ou http://)
+    Ipp { uri: String },
+}
+
+/// Profil d'une imprimante d'étiquettes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrinterProfile {
+    pub name: String,
+    pub connection: PrinterConnection,
+    pub format: LabelFormat,  // Langage accepté par l'imprimante
+    pub dpi: LabelDpi,
+    pub media: LabelSize,  // Support chargé
+    pub timeout: Duration,
+}
+
+impl PrinterProfile {
+    /// Imprimante ZPL en impression brute sur le port 9100
+    pub fn raw(name: &str, host: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            connection: PrinterConnection::Raw { host: host.to_string(), port: raw::DEFAULT_PORT },
+            format: LabelFormat::ZPL,
+            dpi: LabelDpi::Dpi203,
+            media: LabelSize::Inch4x6,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Imprimante IPP, en PDF par défaut
+    pub fn ipp(name: &str, uri: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            connection: PrinterConnection::Ipp { uri: uri.to_string() },
+            format: LabelFormat::PDF,
+            dpi: LabelDpi::Dpi203,
+            media: LabelSize::Inch4x6,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Définit le port TCP d'une imprimante en impression brute
+    pub fn with_port(mut self, port: u16) -> Self {
+        if let PrinterConnection::Raw { port: current, .. } = &mut self.connection {
+            *current = port;
+        }
+        self
+    }
+
+    /// Définit le langage accepté par l'imprimante
+    pub fn with_format(mut self, format: LabelFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Définit la résolution de la tête d'impression
+    pub fn with_dpi(mut self, dpi: LabelDpi) -> Self {
+        self.dpi = dpi;
+        self
+    }
+
+    /// Définit le support chargé
+    pub fn with_media(mut self, media: LabelSize) -> Self {
+        self.media = media;
+        self
+    }
+
+    /// Définit le délai d'attente des échanges
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Options de génération d'étiquettes adaptées à l'imprimante
+    pub fn label_options(&self) -> LabelOptions {
+        LabelOptions::new(self.format)
+            .with_size(self.media)
+            .with_dpi(self.dpi)
+    }
+}
+
+/// État général d'une imprimante
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PrinterState {
+    Idle,
+    Processing,
+    Stopped,
+}
+
+/// État d'une imprimante au moment de la requête
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrinterStatus {
+    pub state: PrinterState,
+    pub reasons: Vec<String>,  // Motifs bloquants (mots-clés IPP : media-empty, paused...)
+    pub message: Option<String>,
+    pub queued_jobs: Option<u32>,
+}
+
+impl PrinterStatus {
+    /// Indique si l'imprimante peut imprimer
+    pub fn is_ready(&self) -> bool {
+        self.state != PrinterState::Stopped && self.reasons.is_empty()
+    }
+}
+
+/// Travail d'impression transmis
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrintJob {
+    pub printer: String,
+    pub tracking_number: String,
+    pub job_id: Option<u32>,  // Identifiant attribué par l'imprimante (IPP uniquement)
+    pub format: LabelFormat,
+    pub copies: u32,
+    pub bytes_sent: usize,
+}
+
+/// Document prêt à être transmis à l'imprimante
+struct PrintData {
+    data: Vec<u8>,
+    copies: u32,  // Exemplaires à demander à l'imprimante, en plus de ceux déjà contenus
+}
+
+/// Imprimante d'étiquettes réseau
+#[derive(Debug, Clone)]
+pub struct LabelPrinter {
+    profile: PrinterProfile,
+}
+
+impl LabelPrinter {
+    /// Crée une imprimante à partir de son profil
+    pub fn new(profile: PrinterProfile) -> Self {
+        Self { profile }
+    }
+
+    /// Profil de l'imprimante
+    pub fn profile(&self) -> &PrinterProfile {
+        &self.profile
+    }
+
+    /// Adapte une étiquette à l'imprimante
+    ///
+    /// L'étiquette est convertie dans le langage de l'imprimante au besoin ;
+    /// une étiquette ZPL est remise à l'échelle de la résolution de la tête.
+    fn prepare(&self, label: &ShippingLabel) -> Result<PrintData, DeliveryError> {
+        self.check_media(label)?;
+
+        let label = label.convert_to(self.profile.format)?;
+        let printer_dpi = self.profile.dpi.value();
+        let label_dpi = label.dimensions.and_then(|dimensions| dimensions.dpi).unwrap_or(DEFAULT_DPI);
+
+        match label.label_format {
+            LabelFormat::ZPL if label_dpi != printer_dpi => {
+                let layout = zpl::parse_zpl(&String::from_utf8_lossy(&label.label_data), label_dpi)?;
+                let data = zpl::with_copies(&zpl::to_zpl(&layout, printer_dpi)?, label.copies).into_bytes();
+                Ok(PrintData { data, copies: 1 })
+            }
+            // Une image PNG ne porte qu'un exemplaire
+            LabelFormat::PNG => Ok(PrintData { data: label.label_data, copies: label.copies.max(1) }),
+            _ => Ok(PrintData { data: label.label_data, copies: 1 }),
+        }
+    }
+
+    /// Vérifie que l'étiquette tient sur le support chargé, dans un sens ou dans l'autre
+    fn check_media(&self, label: &ShippingLabel) -> Result<(), DeliveryError> {
+        let Some(dimensions) = label.dimensions else {
+            return Ok(());
+        };
+
+        let (media_width, media_height) = self.profile.media.dimensions_mm();
+        let fits = |width: f64, height: f64| {
+            width <= media_width + MEDIA_TOLERANCE_MM && height <= media_height + MEDIA_TOLERANCE_MM
+        };
+
+        if fits(dimensions.width_mm, dimensions.height_mm) || fits(dimensions.height_mm, dimensions.width_mm) {
+            Ok(())
+        } else {
+            Err(DeliveryError::PrintError(format!(
+                "Étiquette {} de {:.1} × {:.1} mm trop grande pour le support de {} ({:.1} × {:.1} mm)",
+                label.tracking_number, dimensions.width_mm, dimensions.height_mm,
+                self.profile.name, media_width, media_height
+            )))
+        }
+    }
+
+    fn job(&self, label: &ShippingLabel, job_id: Option<u32>, bytes_sent: usize) -> PrintJob {
+        PrintJob {
+            printer: self.profile.name.clone(),
+            tracking_number: label.tracking_number.clone(),
+            job_id,
+            format: self.profile.format,
+            copies: label.copies.max(1),
+            bytes_sent,
+        }
+    }
+
+    /// Envoie une étiquette à l'imprimante
+    pub async fn print(&self, label: &ShippingLabel) -> Result<PrintJob, DeliveryError> {
+        let print = self.prepare(label)?;
+
+        match &self.profile.connection {
+            PrinterConnection::Raw { host, port } => {
+                let data = print.data.repeat(print.copies as usize);
+                raw::send(host, *port, &data, self.profile.timeout).await?;
+                Ok(self.job(label, None, data.len()))
+            }
+            PrinterConnection::Ipp { uri } => {
+                let request = self.print_job_request(uri, label, &print);
+                let response = ipp::send(uri, request, self.profile.timeout).await?;
+                Ok(self.job(label, job_id(&response), print.data.len()))
+            }
+        }
+    }
+
+    /// Version synchrone de print
+    pub fn print_blocking(&self, label: &ShippingLabel) -> Result<PrintJob, DeliveryError> {
+        let print = self.prepare(label)?;
+
+        match &self.profile.connection {
+            PrinterConnection::Raw { host, port } => {
+                let data = print.data.repeat(print.copies as usize);
+                raw::send_blocking(host, *port, &data, self.profile.timeout)?;
+                Ok(self.job(label, None, data.len()))
+            }
+            PrinterConnection::Ipp { uri } => {
+                let request = self.print_job_request(uri, label, &print);
+                let response = ipp::send_blocking(uri, request, self.profile.timeout)?;
+                Ok(self.job(label, job_id(&response), print.data.len()))
+            }
+        }
+    }
+
+    fn print_job_request(&self, uri: &str, label: &ShippingLabel, print: &PrintData) -> Vec<u8> {
+        ipp::print_job_request(uri, &label.tracking_number, document_format(self.profile.format), print.copies, &print.data)
+    }
+
+    /// Interroge l'état de l'imprimante
+    ///
+    /// En impression brute, seules les imprimantes ZPL répondent (commande ~HS).
+    pub async fn status(&self) -> Result<PrinterStatus, DeliveryError> {
+        match &self.profile.connection {
+            PrinterConnection::Raw { host, port } => {
+                self.check_status_support()?;
+                raw::query_status(host, *port, self.profile.timeout).await
+            }
+            PrinterConnection::Ipp { uri } => {
+                let response = ipp::send(uri, ipp::printer_attributes_request(uri), self.profile.timeout).await?;
+                Ok(ipp::printer_status(&response))
+            }
+        }
+    }
+
+    /// Version synchrone de status
+    pub fn status_blocking(&self) -> Result<PrinterStatus, DeliveryError> {
+        match &self.profile.connection {
+            PrinterConnection::Raw { host, port } => {
+                self.check_status_support()?;
+                raw::query_status_blocking(host, *port, self.profile.timeout)
+            }
+            PrinterConnection::Ipp { uri } => {
+                let response = ipp::send_blocking(uri, ipp::printer_attributes_request(uri), self.profile.timeout)?;
+                Ok(ipp::printer_status(&response))
+            }
+        }
+    }
+
+    fn check_status_support(&self) -> Result<(), DeliveryError> {
+        if self.profile.format == LabelFormat::ZPL {
+            Ok(())
+        } else {
+            Err(DeliveryError::UnsupportedService(format!(
+                "État non disponible pour l'imprimante {} en impression brute {:?}", self.profile.name, self.profile.format
+            )))
+        }
+    }
+}
+
+/// Type MIME IPP d'un format d'étiquette
+///
+/// Le ZPL n'a pas de type normalisé : application/octet-stream est accepté
+/// par toutes les imprimantes IPP, qui détectent alors le langage.
+fn document_format(format: LabelFormat) -> &'static str {
+    match format {
+        LabelFormat::PDF => "application/pdf",
+        LabelFormat::PNG => "image/png",
+        LabelFormat::ZPL => "application/octet-stream",
+    }
+}
+
+fn job_id(response: &ipp::IppResponse) -> Option<u32> {
+    response.integer("job-id").map(|id| id.max(0) as u32)
+}
@@ -0,0 +1,164 @@
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use crate::errors::DeliveryError;
+use crate::printing::{PrinterState, PrinterStatus};
+
+/// Port d'impression brute (AppSocket / JetDirect)
+pub const DEFAULT_PORT: u16 = 9100;
+
+/// Commande ZPL de demande d'état de l'imprimante (Host Status)
+const HOST_STATUS: &[u8] = b"~HS";
+
+/// Début et fin de chacune des trois lignes de la réponse ~HS
+const STX: u8 = 0x02;
+const ETX: u8 = 0x03;
+
+fn unreachable(host: &str, port: u16, e: impl std::fmt::Display) -> DeliveryError {
+    DeliveryError::ConnectionError(format!("Imprimante {}:{} injoignable: {}", host, port, e))
+}
+
+/// Ouvre une connexion vers l'imprimante, délais d'attente compris
+fn connect(host: &str, port: u16, timeout: Duration) -> Result<TcpStream, DeliveryError> {
+    let addresses = (host, port).to_socket_addrs().map_err(|e| unreachable(host, port, e))?;
+
+    let mut last_error = None;
+    for address in addresses {
+        match TcpStream::connect_timeout(&address, timeout) {
+            Ok(stream) => {
+                stream.set_read_timeout(Some(timeout))?;
+                stream.set_write_timeout(Some(timeout))?;
+                return Ok(stream);
+            }
+            Err(e) => last_error = Some(e),
+        }
+    }
+
+    Err(unreachable(host, port, last_error.map_or("adresse introuvable".to_string(), |e| e.to_string())))
+}
+
+/// Envoie des données brutes à l'imprimante
+pub async fn send(host: &str, port: u16, data: &[u8], timeout: Duration) -> Result<(), DeliveryError> {
+    let exchange = async {
+        let mut stream = tokio::net::TcpStream::connect((host, port)).await?;
+        stream.write_all(data).await?;
+        stream.shutdown().await
+    };
+
+    match tokio::time::timeout(timeout, exchange).await {
+        Ok(result) => result.map_err(|e| unreachable(host, port, e)),
+        Err(_) => Err(unreachable(host, port, "délai dépassé")),
+    }
+}
+
+/// Version synchrone de send
+pub fn send_blocking(host: &str, port: u16, data: &[u8], timeout: Duration) -> Result<(), DeliveryError> {
+    let mut stream = connect(host, port, timeout)?;
+    stream.write_all(data).map_err(|e| unreachable(host, port, e))?;
+    stream.shutdown(std::net::Shutdown::Write).map_err(|e| unreachable(host, port, e))
+}
+
+/// Interroge une imprimante ZPL par la commande ~HS
+pub async fn query_status(host: &str, port: u16, timeout: Duration) -> Result<PrinterStatus, DeliveryError> {
+    let exchange = async {
+        let mut stream = tokio::net::TcpStream::connect((host, port)).await?;
+        stream.write_all(HOST_STATUS).await?;
+
+        let mut response = Vec::new();
+        let mut buffer = [0u8; 256];
+        while !is_complete(&response) {
+            match stream.read(&mut buffer).await? {
+                0 => break,
+                read => response.extend_from_slice(&buffer[..read]),
+            }
+        }
+        Ok::<_, std::io::Error>(response)
+    };
+
+    let response = match tokio::time::timeout(timeout, exchange).await {
+        Ok(result) => result.map_err(|e| unreachable(host, port, e))?,
+        Err(_) => return Err(unreachable(host, port, "délai dépassé")),
+    };
+
+    parse_host_status(&response)
+}
+
+/// Version synchrone de query_status
+pub fn query_status_blocking(host: &str, port: u16, timeout: Duration) -> Result<PrinterStatus, DeliveryError> {
+    let mut stream = connect(host, port, timeout)?;
+    stream.write_all(HOST_STATUS).map_err(|e| unreachable(host, port, e))?;
+
+    let mut response = Vec::new();
+    let mut buffer = [0u8; 256];
+    while !is_complete(&response) {
+        match stream.read(&mut buffer).map_err(|e| unreachable(host, port, e))? {
+            0 => break,
+            read => response.extend_from_slice(&buffer[..read]),
+        }
+    }
+
+    parse_host_status(&response)
+}
+
+/// La réponse ~HS compte trois lignes terminées par ETX
+fn is_complete(response: &[u8]) -> bool {
+    response.iter().filter(|byte| **byte == ETX).count() >= 3
+}
+
+/// Interprète la réponse à la commande ~HS d'une imprimante Zebra
+///
+/// Les motifs d'arrêt reprennent les mots-clés IPP `printer-state-reasons`
+/// quand ils existent (media-empty, paused, cover-open...).
+pub fn parse_host_status(response: &[u8]) -> Result<PrinterStatus, DeliveryError> {
+    let text = String::from_utf8_lossy(response);
+    let lines: Vec<Vec<&str>> = text
+        .split(STX as char)
+        .filter_map(|chunk| chunk.split(ETX as char).next())
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| line.split(',').map(str::trim).collect())
+        .collect();
+
+    let (first, second) = match lines.as_slice() {
+        [first, second, ..] if first.len() >= 12 && second.len() >= 9 => (first, second),
+        _ => return Err(DeliveryError::PrintError(format!("Réponse ~HS invalide: {:?}", text))),
+    };
+
+    let flag = |field: &str| field == "1";
+    let count = |field: &str| field.parse::<u32>().unwrap_or(0);
+
+    let mut reasons = Vec::new();
+    for (set, reason) in [
+        (flag(first[1]), "media-empty"),
+        (flag(first[2]), "paused"),
+        (flag(first[5]), "spool-area-full"),
+        (flag(first[9]), "ram-corrupt"),
+        (flag(first[10]), "head-too-cold"),
+        (flag(first[11]), "head-too-hot"),
+        (flag(second[2]), "cover-open"),
+        (flag(second[3]), "marker-supply-empty"),
+    ] {
+        if set {
+            reasons.push(reason.to_string());
+        }
+    }
+
+    let queued = count(first[4]);
+    let state = if !reasons.is_empty() {
+        PrinterState::Stopped
+    } else if queued > 0 || count(second[8]) > 0 {
+        PrinterState::Processing
+    } else {
+        PrinterState::Idle
+    };
+
+    Ok(PrinterStatus {
+        state,
+        reasons,
+        message: None,
+        queued_jobs: Some(queued),
+    })
+}
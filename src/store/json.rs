@@ -0,0 +1,282 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::DeliveryError;
+use crate::models::{LabelFormat, ShippingLabel, TrackingInfo};
+use crate::store::{ShipmentQuery, ShipmentRecord, ShipmentStore, StoreData, TrackingSnapshot};
+
+/// Nombre d'entrées du journal au-delà duquel il est fusionné dans le fichier JSON
+pub const DEFAULT_COMPACTION_THRESHOLD: usize = 1000;
+
+/// Modification consignée dans le journal, une par ligne
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum JournalEntry {
+    Shipment(Box<ShipmentRecord>),
+    Label(ShippingLabel),  // Métadonnées seules, les données sont dans le répertoire des étiquettes
+    Tracking(TrackingSnapshot),
+}
+
+/// Données chargées et taille du journal
+#[derive(Debug)]
+struct JsonState {
+    data: StoreData,
+    journal_entries: usize,
+}
+
+/// Stockage dans un fichier JSON complété par un journal
+///
+/// Chaque modification est ajoutée en fin de journal (`envois.json` →
+/// `envois.journal`, une entrée JSON par ligne) : une écriture ne coûte que la
+/// taille de la modification. Au-delà d'un nombre d'entrées, le journal est
+/// fusionné dans le fichier JSON, réécrit via un fichier temporaire puis
+/// renommé. Une ligne tronquée par un arrêt brutal en fin de journal est ignorée
+/// à l'ouverture.
+///
+/// Les données des étiquettes sont enregistrées à part, dans des fichiers bruts
+/// (PDF, ZPL, PNG) d'un répertoire voisin : `envois.json` → `envois.labels/`.
+#[derive(Debug)]
+pub struct JsonFileStore {
+    path: PathBuf,
+    journal_path: PathBuf,
+    labels_dir: PathBuf,
+    compaction_threshold: usize,
+    state: Mutex<JsonState>,
+}
+
+impl JsonFileStore {
+    /// Ouvre un stockage, créé au premier enregistrement si le fichier n'existe pas
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, DeliveryError> {
+        let path = path.as_ref().to_path_buf();
+        let journal_path = path.with_extension("journal");
+
+        let mut data: StoreData = if path.exists() {
+            let content = std::fs::read(&path)?;
+            serde_json::from_slice(&content).map_err(|e| DeliveryError::SerializationError(format!(
+                "Stockage JSON {} illisible: {}", path.display(), e
+            )))?
+        } else {
+            StoreData::default()
+        };
+        let (journal_entries, truncated) = replay_journal(&journal_path, &mut data)?;
+
+        let store = Self {
+            labels_dir: path.with_extension("labels"),
+            path,
+            journal_path,
+            compaction_threshold: DEFAULT_COMPACTION_THRESHOLD,
+            state: Mutex::new(JsonState { data, journal_entries }),
+        };
+        // Les écritures suivantes ne doivent pas prolonger la ligne tronquée
+        if truncated {
+            store.compact()?;
+        }
+        Ok(store)
+    }
+
+    /// Définit le nombre d'entrées du journal déclenchant sa fusion dans le fichier JSON
+    pub fn with_compaction_threshold(mut self, entries: usize) -> Self {
+        self.compaction_threshold = entries.max(1);
+        self
+    }
+
+    /// Chemin du fichier JSON
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Chemin du journal des modifications
+    pub fn journal_path(&self) -> &Path {
+        &self.journal_path
+    }
+
+    /// Fusionne le journal dans le fichier JSON
+    pub fn compact(&self) -> Result<(), DeliveryError> {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        self.compact_locked(&mut state)
+    }
+
+    /// Réécrit le fichier JSON via un fichier temporaire, puis vide le journal
+    ///
+    /// Un arrêt entre les deux étapes est sans conséquence : rejouer le journal
+    /// sur le fichier déjà à jour ne change rien.
+    fn compact_locked(&self, state: &mut JsonState) -> Result<(), DeliveryError> {
+        if let Some(parent) = self.path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let temporary = self.path.with_extension("json.tmp");
+        let mut file = std::fs::File::create(&temporary)?;
+        file.write_all(&serde_json::to_vec(&state.data)?)?;
+        file.sync_all()?;
+        std::fs::rename(&temporary, &self.path)?;
+
+        if self.journal_path.exists() {
+            std::fs::File::create(&self.journal_path)?.sync_all()?;
+        }
+        state.journal_entries = 0;
+        Ok(())
+    }
+
+    /// Fichier des données d'une étiquette
+    fn label_path(&self, tracking_number: &str, format: LabelFormat) -> PathBuf {
+        let name: String = tracking_number.chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+            .collect();
+        let extension = match format {
+            LabelFormat::PDF => "pdf",
+            LabelFormat::ZPL => "zpl",
+            LabelFormat::PNG => "png",
+        };
+
+        self.labels_dir.join(format!("{}.{}", name, extension))
+    }
+
+    /// Ajoute une entrée au journal
+    ///
+    /// Les données en mémoire ne sont modifiées qu'ensuite, une fois l'entrée écrite.
+    fn append(&self, state: &mut JsonState, entry: &JournalEntry) -> Result<(), DeliveryError> {
+        if let Some(parent) = self.journal_path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+        let mut journal = std::fs::OpenOptions::new().create(true).append(true).open(&self.journal_path)?;
+        journal.write_all(&line)?;
+        journal.sync_data()?;
+
+        state.journal_entries += 1;
+        Ok(())
+    }
+
+    /// Fusionne le journal s'il a atteint le seuil
+    ///
+    /// La modification est déjà écrite dans le journal : un échec de la fusion est
+    /// seulement journalisé, elle sera retentée à la prochaine écriture.
+    fn compact_if_due(&self, state: &mut JsonState) {
+        if state.journal_entries >= self.compaction_threshold
+            && let Err(e) = self.compact_locked(state) {
+            log::warn!("Fusion du journal {} impossible: {}", self.journal_path.display(), e);
+        }
+    }
+}
+
+/// Rejoue le journal sur les données du fichier JSON
+///
+/// Retourne le nombre d'entrées du journal et si sa dernière ligne est tronquée.
+fn replay_journal(path: &Path, data: &mut StoreData) -> Result<(usize, bool), DeliveryError> {
+    if !path.exists() {
+        return Ok((0, false));
+    }
+
+    let content = std::fs::read_to_string(path)?;
+    let lines: Vec<&str> = content.lines().filter(|line| !line.trim().is_empty()).collect();
+    let mut truncated = false;
+
+    for (index, line) in lines.iter().enumerate() {
+        let entry = match serde_json::from_str::<JournalEntry>(line) {
+            Ok(entry) => entry,
+            // Dernière écriture interrompue par un arrêt brutal
+            Err(e) if index + 1 == lines.len() && !content.ends_with('\n') => {
+                log::warn!("Dernière entrée du journal {} incomplète, ignorée: {}", path.display(), e);
+                truncated = true;
+                continue;
+            }
+            Err(e) => {
+                return Err(DeliveryError::SerializationError(format!(
+                    "Journal {} illisible ligne {}: {}", path.display(), index + 1, e
+                )));
+            }
+        };
+
+        match entry {
+            // Les versions déjà fusionnées dans le fichier JSON sont refusées : rien à faire
+            JournalEntry::Shipment(shipment) => {
+                let _ = data.save_shipment(&shipment);
+            }
+            JournalEntry::Label(label) => data.save_label(&label),
+            JournalEntry::Tracking(snapshot) => {
+                data.push_tracking(snapshot);
+            }
+        }
+    }
+
+    Ok((lines.len(), truncated))
+}
+
+impl ShipmentStore for JsonFileStore {
+    fn save_shipment(&self, shipment: &ShipmentRecord) -> Result<(), DeliveryError> {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let stored = state.data.prepare_shipment(shipment)?;
+        self.append(&mut state, &JournalEntry::Shipment(Box::new(stored.clone())))?;
+        state.data.insert_shipment(stored);
+        self.compact_if_due(&mut state);
+        Ok(())
+    }
+
+    fn load_shipment(&self, tracking_number: &str) -> Result<Option<ShipmentRecord>, DeliveryError> {
+        Ok(self.state.lock().unwrap_or_else(PoisonError::into_inner).data.load_shipment(tracking_number))
+    }
+
+    fn save_label(&self, label: &ShippingLabel) -> Result<(), DeliveryError> {
+        // Fichier et métadonnées sont modifiés ensemble, sous le verrou
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let path = self.label_path(&label.tracking_number, label.label_format);
+        let temporary = path.with_extension("tmp");
+
+        std::fs::create_dir_all(&self.labels_dir)?;
+        std::fs::write(&temporary, &label.label_data)?;
+        let metadata = ShippingLabel { label_data: Vec::new(), ..label.clone() };
+        if let Err(e) = self.append(&mut state, &JournalEntry::Label(metadata.clone())) {
+            let _ = std::fs::remove_file(&temporary);
+            return Err(e);
+        }
+        std::fs::rename(&temporary, &path)?;
+
+        let previous_format = state.data
+            .load_label(&label.tracking_number)
+            .map(|previous| previous.label_format)
+            .filter(|format| *format != label.label_format);
+        if let Some(format) = previous_format {
+            let _ = std::fs::remove_file(self.label_path(&label.tracking_number, format));
+        }
+
+        state.data.save_label(&metadata);
+        self.compact_if_due(&mut state);
+        Ok(())
+    }
+
+    fn load_label(&self, tracking_number: &str) -> Result<Option<ShippingLabel>, DeliveryError> {
+        let metadata = self.state.lock().unwrap_or_else(PoisonError::into_inner).data.load_label(tracking_number);
+
+        match metadata {
+            Some(mut label) => {
+                label.label_data = std::fs::read(self.label_path(tracking_number, label.label_format))?;
+                Ok(Some(label))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn save_tracking(&self, info: &TrackingInfo) -> Result<(), DeliveryError> {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(snapshot) = state.data.tracking_snapshot(info) {
+            self.append(&mut state, &JournalEntry::Tracking(snapshot.clone()))?;
+            state.data.push_tracking(snapshot);
+            self.compact_if_due(&mut state);
+        }
+        Ok(())
+    }
+
+    fn load_tracking(&self, tracking_number: &str) -> Result<Vec<TrackingSnapshot>, DeliveryError> {
+        Ok(self.state.lock().unwrap_or_else(PoisonError::into_inner).data.load_tracking(tracking_number))
+    }
+
+    fn find_shipments(&self, query: &ShipmentQuery) -> Result<Vec<ShipmentRecord>, DeliveryError> {
+        Ok(self.state.lock().unwrap_or_else(PoisonError::into_inner).data.find_shipments(query))
+    }
+}
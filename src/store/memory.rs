@@ -0,0 +1,50 @@
+use std::sync::{PoisonError, RwLock};
+
+use crate::errors::DeliveryError;
+use crate::models::{ShippingLabel, TrackingInfo};
+use crate::store::{ShipmentQuery, ShipmentRecord, ShipmentStore, StoreData, TrackingSnapshot};
+
+/// Stockage en mémoire, perdu à l'arrêt du processus
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    data: RwLock<StoreData>,
+}
+
+impl MemoryStore {
+    /// Crée un stockage vide
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ShipmentStore for MemoryStore {
+    fn save_shipment(&self, shipment: &ShipmentRecord) -> Result<(), DeliveryError> {
+        self.data.write().unwrap_or_else(PoisonError::into_inner).save_shipment(shipment)
+    }
+
+    fn load_shipment(&self, tracking_number: &str) -> Result<Option<ShipmentRecord>, DeliveryError> {
+        Ok(self.data.read().unwrap_or_else(PoisonError::into_inner).load_shipment(tracking_number))
+    }
+
+    fn save_label(&self, label: &ShippingLabel) -> Result<(), DeliveryError> {
+        self.data.write().unwrap_or_else(PoisonError::into_inner).save_label(label);
+        Ok(())
+    }
+
+    fn load_label(&self, tracking_number: &str) -> Result<Option<ShippingLabel>, DeliveryError> {
+        Ok(self.data.read().unwrap_or_else(PoisonError::into_inner).load_label(tracking_number))
+    }
+
+    fn save_tracking(&self, info: &TrackingInfo) -> Result<(), DeliveryError> {
+        self.data.write().unwrap_or_else(PoisonError::into_inner).save_tracking(info);
+        Ok(())
+    }
+
+    fn load_tracking(&self, tracking_number: &str) -> Result<Vec<TrackingSnapshot>, DeliveryError> {
+        Ok(self.data.read().unwrap_or_else(PoisonError::into_inner).load_tracking(tracking_number))
+    }
+
+    fn find_shipments(&self, query: &ShipmentQuery) -> Result<Vec<ShipmentRecord>, DeliveryError> {
+        Ok(self.data.read().unwrap_or_else(PoisonError::into_inner).find_shipments(query))
+    }
+}
@@ -0,0 +1,308 @@
+/// Stockage en mémoire
+pub mod memory;
+
+/// Stockage dans un fichier JSON complété par un journal
+pub mod json;
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::errors::DeliveryError;
+use crate::lifecycle::{LifecycleState, ShipmentLifecycle};
+use crate::models::{CarrierCode, Parcel, Rate, ReturnShipment, ShipmentStatus, ShippingLabel, TrackingInfo};
+
+pub use json::JsonFileStore;
+pub use memory::MemoryStore;
+
+/// Envoi enregistré : colis, tarif retenu et dernier état connu
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShipmentRecord {
+    pub tracking_number: String,
+    pub carrier: CarrierCode,
+    #[serde(default)]
+    pub account: Option<String>,  // Compte client chez le transporteur
+    pub parcel: Parcel,
+    pub rate: Option<Rate>,
+    pub status: ShipmentStatus,
+    pub return_of: Option<String>,  // Numéro de suivi de l'envoi d'origine pour un retour
+    #[serde(default)]
+    pub return_shipment: Option<ReturnShipment>,  // Retour créé auprès du transporteur, sans son étiquette
+    #[serde(default)]
+    pub lifecycle: ShipmentLifecycle,  // État du cycle de vie et journal d'audit
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ShipmentRecord {
+    /// Crée l'enregistrement d'un envoi dont l'étiquette vient d'être générée
+    pub fn new(parcel: &Parcel, carrier: CarrierCode, tracking_number: &str) -> Self {
+        Self {
+            tracking_number: tracking_number.to_string(),
+            carrier,
+            account: None,
+            parcel: parcel.clone(),
+            rate: None,
+            status: ShipmentStatus::Created,
+            return_of: None,
+            return_shipment: None,
+            lifecycle: ShipmentLifecycle::new(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Renseigne le tarif retenu
+    pub fn with_rate(mut self, rate: &Rate) -> Self {
+        self.rate = Some(rate.clone());
+        self
+    }
+
+    /// Rattache l'envoi à un compte client
+    pub fn with_account(mut self, account: Option<&str>) -> Self {
+        self.account = account.map(str::to_string);
+        self
+    }
+
+    /// Rattache un retour à son envoi d'origine
+    pub fn with_return_of(mut self, original_tracking_number: &str) -> Self {
+        self.return_of = Some(original_tracking_number.to_string());
+        self
+    }
+
+    /// Rattache un retour à son envoi d'origine et conserve ses informations
+    /// (autorisation, code et QR Code de dépôt, expiration)
+    ///
+    /// L'étiquette du retour est enregistrée à part, avec `ShipmentStore::save_label`.
+    pub fn with_return(self, shipment: &ReturnShipment) -> Self {
+        let mut record = self.with_return_of(&shipment.original_tracking_number);
+        record.return_shipment = Some(ReturnShipment { label: None, ..shipment.clone() });
+        record
+    }
+}
+
+/// Informations de suivi relevées à un instant donné
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrackingSnapshot {
+    pub recorded_at: DateTime<Utc>,
+    pub info: TrackingInfo,
+}
+
+/// Critères de recherche d'envois, tous optionnels et cumulatifs
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ShipmentQuery {
+    pub reference: Option<String>,
+    pub parcel_id: Option<Uuid>,
+    pub return_of: Option<String>,  // Retours d'un envoi d'origine
+    pub rma: Option<String>,  // Retour par numéro d'autorisation
+    pub status: Option<ShipmentStatus>,
+    pub state: Option<LifecycleState>,  // État du cycle de vie
+    pub carrier: Option<CarrierCode>,
+    pub created_from: Option<DateTime<Utc>>,  // Inclus
+    pub created_to: Option<DateTime<Utc>>,  // Exclu
+}
+
+impl ShipmentQuery {
+    /// Crée une recherche sans critère
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Filtre sur la référence client du colis
+    pub fn with_reference(mut self, reference: &str) -> Self {
+        self.reference = Some(reference.to_string());
+        self
+    }
+
+    /// Filtre sur l'identifiant du colis
+    pub fn with_parcel_id(mut self, parcel_id: Uuid) -> Self {
+        self.parcel_id = Some(parcel_id);
+        self
+    }
+
+    /// Filtre sur les retours d'un envoi d'origine
+    pub fn with_return_of(mut self, original_tracking_number: &str) -> Self {
+        self.return_of = Some(original_tracking_number.to_string());
+        self
+    }
+
+    /// Filtre sur le numéro d'autorisation d'un retour
+    pub fn with_rma(mut self, rma: &str) -> Self {
+        self.rma = Some(rma.to_string());
+        self
+    }
+
+    /// Filtre sur le dernier statut connu
+    pub fn with_status(mut self, status: ShipmentStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Filtre sur l'état du cycle de vie
+    pub fn with_state(mut self, state: LifecycleState) -> Self {
+        self.state = Some(state);
+        self
+    }
+
+    /// Filtre sur le transporteur
+    pub fn with_carrier(mut self, carrier: CarrierCode) -> Self {
+        self.carrier = Some(carrier);
+        self
+    }
+
+    /// Filtre sur la date de création, début inclus et fin exclue
+    pub fn created_between(mut self, from: DateTime<Utc>, to: DateTime<Utc>) -> Self {
+        self.created_from = Some(from);
+        self.created_to = Some(to);
+        self
+    }
+
+    /// Indique si un envoi correspond à tous les critères
+    pub fn matches(&self, record: &ShipmentRecord) -> bool {
+        self.reference.as_ref().is_none_or(|reference| record.parcel.reference.as_ref() == Some(reference))
+            && self.parcel_id.is_none_or(|id| record.parcel.id == id)
+            && self.return_of.as_ref().is_none_or(|original| record.return_of.as_ref() == Some(original))
+            && self.rma.as_ref().is_none_or(|rma| record.return_shipment.as_ref().is_some_and(|shipment| shipment.rma == *rma))
+            && self.status.is_none_or(|status| record.status == status)
+            && self.state.is_none_or(|state| record.lifecycle.state == state)
+            && self.carrier.is_none_or(|carrier| record.carrier == carrier)
+            && self.created_from.is_none_or(|from| record.created_at >= from)
+            && self.created_to.is_none_or(|to| record.created_at < to)
+    }
+}
+
+/// Stockage persistant des envois, étiquettes et relevés de suivi
+///
+/// Les envois, étiquettes et relevés sont indexés par numéro de suivi.
+pub trait ShipmentStore: Send + Sync {
+    /// Enregistre un envoi, ou met à jour l'envoi déjà enregistré
+    ///
+    /// Une mise à jour doit partir de l'envoi chargé avec `load_shipment` : elle
+    /// est refusée (`DeliveryError::InvalidTransition`) si son journal d'audit ne
+    /// prolonge pas celui de l'envoi enregistré, pour qu'une version périmée
+    /// n'efface pas l'historique. La date de création d'origine est conservée.
+    fn save_shipment(&self, shipment: &ShipmentRecord) -> Result<(), DeliveryError>;
+
+    /// Charge un envoi
+    fn load_shipment(&self, tracking_number: &str) -> Result<Option<ShipmentRecord>, DeliveryError>;
+
+    /// Enregistre ou remplace une étiquette
+    fn save_label(&self, label: &ShippingLabel) -> Result<(), DeliveryError>;
+
+    /// Charge une étiquette
+    fn load_label(&self, tracking_number: &str) -> Result<Option<ShippingLabel>, DeliveryError>;
+
+    /// Ajoute un relevé de suivi à l'historique de l'envoi
+    fn save_tracking(&self, info: &TrackingInfo) -> Result<(), DeliveryError>;
+
+    /// Relevés de suivi d'un envoi, du plus ancien au plus récent
+    fn load_tracking(&self, tracking_number: &str) -> Result<Vec<TrackingSnapshot>, DeliveryError>;
+
+    /// Recherche des envois, triés par date de création
+    fn find_shipments(&self, query: &ShipmentQuery) -> Result<Vec<ShipmentRecord>, DeliveryError>;
+
+    /// Dernier relevé de suivi d'un envoi
+    fn latest_tracking(&self, tracking_number: &str) -> Result<Option<TrackingInfo>, DeliveryError> {
+        Ok(self.load_tracking(tracking_number)?.pop().map(|snapshot| snapshot.info))
+    }
+}
+
+/// Contenu d'un stockage, partagé par les implémentations
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub(crate) struct StoreData {
+    #[serde(default)]
+    shipments: HashMap<String, ShipmentRecord>,
+    #[serde(default)]
+    labels: HashMap<String, ShippingLabel>,
+    #[serde(default)]
+    tracking: HashMap<String, Vec<TrackingSnapshot>>,
+}
+
+impl StoreData {
+    /// Enregistre un envoi, en refusant une version qui ne prolonge pas l'historique enregistré
+    fn save_shipment(&mut self, shipment: &ShipmentRecord) -> Result<(), DeliveryError> {
+        let shipment = self.prepare_shipment(shipment)?;
+        self.insert_shipment(shipment);
+        Ok(())
+    }
+
+    /// Version d'un envoi à enregistrer, sans rien modifier
+    ///
+    /// Une version qui ne prolonge pas l'historique enregistré est refusée ; la date
+    /// de création enregistrée est conservée.
+    fn prepare_shipment(&self, shipment: &ShipmentRecord) -> Result<ShipmentRecord, DeliveryError> {
+        let mut shipment = shipment.clone();
+
+        if let Some(existing) = self.shipments.get(&shipment.tracking_number) {
+            if !shipment.lifecycle.audit.starts_with(&existing.lifecycle.audit) {
+                return Err(DeliveryError::InvalidTransition(format!(
+                    "Envoi {}: version périmée, son journal d'audit ne prolonge pas celui enregistré",
+                    shipment.tracking_number
+                )));
+            }
+            shipment.created_at = existing.created_at;
+        }
+
+        Ok(shipment)
+    }
+
+    fn insert_shipment(&mut self, shipment: ShipmentRecord) {
+        self.shipments.insert(shipment.tracking_number.clone(), shipment);
+    }
+
+    fn load_shipment(&self, tracking_number: &str) -> Option<ShipmentRecord> {
+        self.shipments.get(tracking_number).cloned()
+    }
+
+    fn save_label(&mut self, label: &ShippingLabel) {
+        self.labels.insert(label.tracking_number.clone(), label.clone());
+    }
+
+    fn load_label(&self, tracking_number: &str) -> Option<ShippingLabel> {
+        self.labels.get(tracking_number).cloned()
+    }
+
+    /// Ajoute un relevé ; un relevé identique au précédent n'est pas dupliqué
+    ///
+    /// Retourne le relevé ajouté, le cas échéant.
+    fn save_tracking(&mut self, info: &TrackingInfo) -> Option<TrackingSnapshot> {
+        let snapshot = self.tracking_snapshot(info)?;
+        self.push_tracking(snapshot.clone());
+        Some(snapshot)
+    }
+
+    /// Relevé à ajouter pour des informations de suivi, sauf si elles n'ont pas changé
+    fn tracking_snapshot(&self, info: &TrackingInfo) -> Option<TrackingSnapshot> {
+        let unchanged = self.tracking.get(&info.tracking_number)
+            .and_then(|history| history.last())
+            .is_some_and(|last| last.info == *info);
+        (!unchanged).then(|| TrackingSnapshot { recorded_at: Utc::now(), info: info.clone() })
+    }
+
+    /// Ajoute un relevé horodaté, sauf s'il est identique au précédent ou déjà présent
+    fn push_tracking(&mut self, snapshot: TrackingSnapshot) -> bool {
+        let history = self.tracking.entry(snapshot.info.tracking_number.clone()).or_default();
+
+        if history.last().is_some_and(|last| last.info == snapshot.info) || history.contains(&snapshot) {
+            return false;
+        }
+        history.push(snapshot);
+        true
+    }
+
+    fn load_tracking(&self, tracking_number: &str) -> Vec<TrackingSnapshot> {
+        self.tracking.get(tracking_number).cloned().unwrap_or_default()
+    }
+
+    fn find_shipments(&self, query: &ShipmentQuery) -> Vec<ShipmentRecord> {
+        let mut shipments: Vec<ShipmentRecord> = self.shipments.values()
+            .filter(|record| query.matches(record))
+            .cloned()
+            .collect();
+
+        shipments.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.tracking_number.cmp(&b.tracking_number)));
+        shipments
+    }
+}
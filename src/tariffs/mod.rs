@@ -0,0 +1,146 @@
+pub mod table;
+
+use async_trait::async_trait;
+
+use crate::core::traits::RateProvider;
+use crate::errors::DeliveryError;
+use crate::models::{Parcel, Rate};
+use crate::utils::weight::VolumetricRules;
+
+pub use table::{
+    Surcharge, SurchargeAmount, SurchargeCondition,
+    TariffPrice, TariffService, TariffTable, ZoneRule,
+};
+
+/// Fournisseur de tarifs hors ligne basé sur des grilles tarifaires locales
+///
+/// Les tarifs retournés sont marqués comme estimés (`Rate::estimated`).
+pub struct TariffRateProvider {
+    tables: Vec<TariffTable>,
+    volumetric_rules: VolumetricRules,
+}
+
+impl TariffRateProvider {
+    /// Crée un fournisseur sans grille
+    pub fn new() -> Self {
+        Self {
+            tables: Vec::new(),
+            volumetric_rules: VolumetricRules::default(),
+        }
+    }
+
+    /// Ajoute une grille tarifaire
+    pub fn with_table(mut self, table: TariffTable) -> Self {
+        self.tables.push(table);
+        self
+    }
+
+    /// Remplace les diviseurs volumétriques utilisés pour le poids facturable
+    pub fn with_volumetric_rules(mut self, rules: VolumetricRules) -> Self {
+        self.volumetric_rules = rules;
+        self
+    }
+
+    /// Liste les grilles chargées
+    pub fn tables(&self) -> &[TariffTable] {
+        &self.tables
+    }
+
+    /// Calcule les tarifs estimés d'un colis pour toutes les grilles chargées
+    pub fn estimate(&self, parcel: &Parcel) -> Result<Vec<Rate>, DeliveryError> {
+        let mut rates = Vec::new();
+
+        for table in &self.tables {
+            let Some(zone) = table.resolve_zone(&parcel.sender, &parcel.recipient) else {
+                continue;
+            };
+
+            for service in &table.services {
+                if let Some(rate) = self.estimate_service(table, service, zone, parcel) {
+                    rates.push(rate);
+                }
+            }
+        }
+
+        if rates.is_empty() {
+            return Err(DeliveryError::RateUnavailable);
+        }
+
+        rates.sort_by(|a, b| a.price.total_cmp(&b.price));
+        Ok(rates)
+    }
+
+    /// Calcule le tarif d'un service, None si le poids dépasse la dernière tranche
+    fn estimate_service(&self, table: &TariffTable, service: &TariffService, zone: &str, parcel: &Parcel) -> Option<Rate> {
+        let weight = self.volumetric_rules.billable_weight(parcel, table.carrier, &service.service_code);
+        let base = table.base_price(&service.service_code, zone, weight.billable)?;
+
+        let longest_side = parcel.length.max(parcel.width).max(parcel.height);
+        let mut fuel_base = base;
+        let mut surcharges_total = 0.0;
+        let mut features = Vec::new();
+
+        for surcharge in &table.surcharges {
+            let applies = match &surcharge.condition {
+                SurchargeCondition::Always => true,
+                SurchargeCondition::MinWeight(min) => weight.billable > *min,
+                SurchargeCondition::MinLength(min) => longest_side > *min,
+                SurchargeCondition::Insured => parcel.insurance_value.is_some(),
+                SurchargeCondition::Return => parcel.is_return,
+                SurchargeCondition::Zone(surcharge_zone) => surcharge_zone == zone,
+                SurchargeCondition::Service(code) => *code == service.service_code,
+            };
+
+            if !applies {
+                continue;
+            }
+
+            let amount = match surcharge.amount {
+                SurchargeAmount::Fixed(amount) => amount,
+                SurchargeAmount::PerKg(amount) => amount * weight.billable,
+                SurchargeAmount::Percent(percent) => base * percent / 100.0,
+            };
+
+            surcharges_total += amount;
+            if surcharge.fuel_applies {
+                fuel_base += amount;
+            }
+            features.push(surcharge.name.clone());
+        }
+
+        let fuel = fuel_base * table.fuel_surcharge_percent / 100.0;
+        let price = ((base + surcharges_total + fuel) * 100.0).round() / 100.0;
+
+        Some(Rate {
+            id: format!("tariff-{}-{}-{}", table.carrier, service.service_code, zone),
+            carrier: table.carrier,
+            service: service.name.clone(),
+            service_code: service.service_code.clone(),
+            price,
+            currency: table.currency.clone(),
+            estimated_delivery: None,
+            delivery_days: service.delivery_days,
+            guaranteed_delivery: service.guaranteed_delivery,
+            features,
+            billable_weight: Some(weight.billable),
+            estimated: true,
+        })
+    }
+}
+
+impl Default for TariffRateProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl RateProvider for TariffRateProvider {
+    async fn get_rates(&self, parcel: &Parcel) -> Result<Vec<Rate>, DeliveryError> {
+        self.estimate(parcel)
+    }
+
+    fn get_rates_blocking(&self, parcel: &Parcel) -> Result<Vec<Rate>, DeliveryError> {
+        self.estimate(parcel)
+    }
+}
@@ -0,0 +1,263 @@
+use std::io::Read;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::DeliveryError;
+use crate::models::{Address, CarrierCode};
+
+/// Règle de résolution de zone à partir des pays et des préfixes de code postal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ZoneRule {
+    pub zone: String,
+    /// Pays d'origine (None = tous les pays d'origine)
+    #[serde(default)]
+    pub origin: Option<String>,
+    /// Pays de destination ("*" = tous les pays)
+    pub destination: String,
+    /// Préfixes de code postal de destination (vide = tout le pays)
+    #[serde(default)]
+    pub postal_prefixes: Vec<String>,
+}
+
+impl ZoneRule {
+    /// Retourne la spécificité de la règle si elle s'applique au trajet, None sinon
+    fn specificity(&self, origin: &Address, destination: &Address) -> Option<usize> {
+        let mut score = 0;
+
+        if let Some(country) = &self.origin {
+            if !country.eq_ignore_ascii_case(origin.country.trim()) {
+                return None;
+            }
+            score += 1;
+        }
+
+        if self.destination != "*" {
+            if !self.destination.eq_ignore_ascii_case(destination.country.trim()) {
+                return None;
+            }
+            score += 2;
+        }
+
+        if !self.postal_prefixes.is_empty() {
+            let postal_code = destination.postal_code.replace(' ', "").to_uppercase();
+            let longest = self.postal_prefixes.iter()
+                .filter(|prefix| postal_code.starts_with(&prefix.replace(' ', "").to_uppercase()))
+                .map(|prefix| prefix.len())
+                .max()?;
+            score += 10 * longest;
+        }
+
+        Some(score)
+    }
+}
+
+/// Service proposé par une grille tarifaire
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TariffService {
+    pub service_code: String,
+    pub name: String,
+    #[serde(default)]
+    pub delivery_days: Option<u32>,
+    #[serde(default)]
+    pub guaranteed_delivery: bool,
+}
+
+/// Prix d'une tranche de poids pour un service et une zone
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TariffPrice {
+    pub service_code: String,
+    pub zone: String,
+    /// Poids maximal de la tranche en kg (borne incluse)
+    pub max_weight: f64,
+    pub price: f64,
+}
+
+/// Mode de calcul d'une surcharge
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum SurchargeAmount {
+    /// Montant fixe par colis
+    Fixed(f64),
+    /// Montant par kg facturable
+    PerKg(f64),
+    /// Pourcentage du prix de base
+    Percent(f64),
+}
+
+/// Condition de déclenchement d'une surcharge
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum SurchargeCondition {
+    Always,
+    /// Poids facturable supérieur à la valeur (kg)
+    MinWeight(f64),
+    /// Plus grand côté supérieur à la valeur (cm)
+    MinLength(f64),
+    /// Colis assuré
+    Insured,
+    /// Colis en retour
+    Return,
+    /// Zone de destination donnée
+    Zone(String),
+    /// Service donné
+    Service(String),
+}
+
+/// Surcharge applicable à une grille tarifaire
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Surcharge {
+    pub name: String,
+    pub amount: SurchargeAmount,
+    #[serde(default = "default_condition")]
+    pub condition: SurchargeCondition,
+    /// Indique si la surcharge carburant s'applique aussi à cette surcharge
+    #[serde(default = "default_true")]
+    pub fuel_applies: bool,
+}
+
+fn default_condition() -> SurchargeCondition {
+    SurchargeCondition::Always
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Grille tarifaire d'un transporteur (tranches de poids × zones × services)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TariffTable {
+    pub carrier: CarrierCode,
+    #[serde(default = "default_currency")]
+    pub currency: String,
+    /// Surcharge carburant en pourcentage (ex: 12.5 pour 12,5 %)
+    #[serde(default)]
+    pub fuel_surcharge_percent: f64,
+    #[serde(default)]
+    pub zones: Vec<ZoneRule>,
+    #[serde(default)]
+    pub services: Vec<TariffService>,
+    #[serde(default)]
+    pub prices: Vec<TariffPrice>,
+    #[serde(default)]
+    pub surcharges: Vec<Surcharge>,
+}
+
+fn default_currency() -> String {
+    "EUR".to_string()
+}
+
+/// Ligne d'un fichier CSV de zones : `zone,origin,destination,postal_prefixes`
+#[derive(Debug, Deserialize)]
+struct ZoneRecord {
+    zone: String,
+    origin: Option<String>,
+    destination: String,
+    postal_prefixes: Option<String>,
+}
+
+impl TariffTable {
+    /// Crée une grille vide pour un transporteur
+    pub fn new(carrier: CarrierCode) -> Self {
+        Self {
+            carrier,
+            currency: default_currency(),
+            fuel_surcharge_percent: 0.0,
+            zones: Vec::new(),
+            services: Vec::new(),
+            prices: Vec::new(),
+            surcharges: Vec::new(),
+        }
+    }
+
+    /// Charge une grille complète depuis un document YAML
+    pub fn from_yaml(yaml: &str) -> Result<Self, DeliveryError> {
+        serde_yaml::from_str(yaml)
+            .map_err(|e| DeliveryError::SerializationError(format!("Grille tarifaire YAML invalide: {}", e)))
+    }
+
+    /// Charge une grille complète depuis un fichier YAML
+    pub fn from_yaml_file(path: &str) -> Result<Self, DeliveryError> {
+        let content = std::fs::read_to_string(path)?;
+        Self::from_yaml(&content)
+    }
+
+    /// Définit la devise de la grille
+    pub fn with_currency(mut self, currency: &str) -> Self {
+        self.currency = currency.to_string();
+        self
+    }
+
+    /// Définit le pourcentage de surcharge carburant
+    pub fn with_fuel_surcharge(mut self, percent: f64) -> Self {
+        self.fuel_surcharge_percent = percent;
+        self
+    }
+
+    /// Ajoute un service à la grille
+    pub fn with_service(mut self, service: TariffService) -> Self {
+        self.services.push(service);
+        self
+    }
+
+    /// Ajoute une surcharge à la grille
+    pub fn with_surcharge(mut self, surcharge: Surcharge) -> Self {
+        self.surcharges.push(surcharge);
+        self
+    }
+
+    /// Charge les tranches de prix depuis un CSV `service_code,zone,max_weight,price`
+    pub fn load_prices_csv<R: Read>(&mut self, reader: R) -> Result<(), DeliveryError> {
+        let mut csv_reader = csv::ReaderBuilder::new().trim(csv::Trim::All).from_reader(reader);
+
+        for record in csv_reader.deserialize::<TariffPrice>() {
+            let price = record
+                .map_err(|e| DeliveryError::SerializationError(format!("Tranche tarifaire CSV invalide: {}", e)))?;
+            self.prices.push(price);
+        }
+
+        Ok(())
+    }
+
+    /// Charge les règles de zone depuis un CSV `zone,origin,destination,postal_prefixes`
+    /// (préfixes séparés par `|`)
+    pub fn load_zones_csv<R: Read>(&mut self, reader: R) -> Result<(), DeliveryError> {
+        let mut csv_reader = csv::ReaderBuilder::new().trim(csv::Trim::All).from_reader(reader);
+
+        for record in csv_reader.deserialize::<ZoneRecord>() {
+            let record = record
+                .map_err(|e| DeliveryError::SerializationError(format!("Zone CSV invalide: {}", e)))?;
+
+            self.zones.push(ZoneRule {
+                zone: record.zone,
+                origin: record.origin.filter(|origin| !origin.is_empty()),
+                destination: record.destination,
+                postal_prefixes: record.postal_prefixes
+                    .map(|prefixes| {
+                        prefixes.split('|')
+                            .map(|prefix| prefix.trim().to_string())
+                            .filter(|prefix| !prefix.is_empty())
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Résout la zone tarifaire d'un trajet, la règle la plus spécifique l'emportant
+    pub fn resolve_zone(&self, origin: &Address, destination: &Address) -> Option<&str> {
+        self.zones.iter()
+            .filter_map(|rule| rule.specificity(origin, destination).map(|score| (score, rule)))
+            .max_by_key(|(score, _)| *score)
+            .map(|(_, rule)| rule.zone.as_str())
+    }
+
+    /// Trouve le prix de la plus petite tranche couvrant le poids facturable
+    pub fn base_price(&self, service_code: &str, zone: &str, weight: f64) -> Option<f64> {
+        self.prices.iter()
+            .filter(|band| band.service_code == service_code && band.zone == zone && band.max_weight >= weight)
+            .min_by(|a, b| a.max_weight.total_cmp(&b.max_weight))
+            .map(|band| band.price)
+    }
+}
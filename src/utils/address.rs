@@ -0,0 +1,626 @@
+use std::fmt;
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization;
+
+use crate::models::{Address, CarrierCode};
+use crate::utils::{formatting, geo};
+
+/// Longueur de ligne acceptée par la plupart des transporteurs
+pub const DEFAULT_MAX_LINE_LENGTH: usize = 35;
+
+/// Casse appliquée aux champs de l'adresse
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum CaseStyle {
+    Keep,  // Casse d'origine
+    #[default]
+    Postal,  // Conventions postales du pays de destination
+    Upper,  // Tous les champs en majuscules
+}
+
+/// Traitement des types de voie (« avenue », « street », « Straße »...)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum StreetTypeStyle {
+    #[default]
+    Keep,
+    Expand,  // AV → AVENUE
+    Abbreviate,  // AVENUE → AV
+    AbbreviateToFit,  // Abréviation des seules lignes trop longues
+}
+
+/// Nature d'une modification apportée à un champ
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AddressChangeKind {
+    Whitespace,  // Espaces superflus supprimés, champ vide retiré
+    Country,  // Code pays en majuscules
+    PostalCode,  // Code postal au format du pays
+    StreetTypeExpanded,
+    StreetTypeAbbreviated,
+    Transliterated,  // Accents et ligatures remplacés par des caractères ASCII
+    Uppercased,
+    Wrapped,  // Fin de ligne reportée sur la ligne suivante
+    Truncated,  // Texte coupé faute de place
+}
+
+impl fmt::Display for AddressChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            AddressChangeKind::Whitespace => "espaces",
+            AddressChangeKind::Country => "code pays",
+            AddressChangeKind::PostalCode => "code postal",
+            AddressChangeKind::StreetTypeExpanded => "type de voie développé",
+            AddressChangeKind::StreetTypeAbbreviated => "type de voie abrégé",
+            AddressChangeKind::Transliterated => "translittération",
+            AddressChangeKind::Uppercased => "majuscules",
+            AddressChangeKind::Wrapped => "report à la ligne",
+            AddressChangeKind::Truncated => "troncature",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// Modification apportée à un champ de l'adresse
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressChange {
+    pub field: String,  // "street1", "city"...
+    pub kind: AddressChangeKind,
+    pub before: String,  // Vide si le champ n'existait pas
+    pub after: String,  // Vide si le champ a été retiré
+}
+
+impl fmt::Display for AddressChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}): « {} » → « {} »", self.field, self.kind, self.before, self.after)
+    }
+}
+
+/// Numéro et nom de voie extraits de la première ligne d'adresse
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct StreetParts {
+    pub house_number: Option<String>,  // "12 BIS", "5a", "10-12"
+    pub street: String,  // Ligne sans le numéro
+}
+
+/// Résultat de la normalisation d'une adresse
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NormalizedAddress {
+    pub address: Address,
+    pub street: StreetParts,  // Pour les transporteurs qui attendent le numéro à part
+    pub changes: Vec<AddressChange>,  // Dans l'ordre où elles ont été appliquées
+}
+
+impl NormalizedAddress {
+    /// Indique si l'adresse est restée identique
+    pub fn is_unchanged(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Indique si du texte a été perdu faute de place
+    pub fn is_truncated(&self) -> bool {
+        self.changes.iter().any(|change| change.kind == AddressChangeKind::Truncated)
+    }
+
+    /// Modifications apportées à un champ
+    pub fn changes_for<'a>(&'a self, field: &'a str) -> impl Iterator<Item = &'a AddressChange> + 'a {
+        self.changes.iter().filter(move |change| change.field == field)
+    }
+}
+
+/// Normalisation des adresses selon les conventions postales et les contraintes des transporteurs
+///
+/// Les étapes s'appliquent dans l'ordre : espaces, code pays et code postal,
+/// types de voie, translittération ASCII, casse, puis longueur des lignes
+/// (abréviation si demandée, report de `street1` sur `street2`, troncature en
+/// dernier recours). Chaque modification est consignée dans le résultat.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressNormalizer {
+    case: CaseStyle,
+    ascii_only: bool,  // Transporteurs qui n'acceptent que l'ASCII
+    street_types: StreetTypeStyle,
+    max_line_length: Option<usize>,  // En caractères ; None = pas de limite
+}
+
+impl Default for AddressNormalizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AddressNormalizer {
+    /// Conventions postales du pays de destination, sans contrainte de transporteur
+    pub fn new() -> Self {
+        Self {
+            case: CaseStyle::Postal,
+            ascii_only: false,
+            street_types: StreetTypeStyle::Keep,
+            max_line_length: None,
+        }
+    }
+
+    /// Règles d'un transporteur : ASCII seul, longueur de ligne limitée, types
+    /// de voie abrégés pour les lignes trop longues
+    pub fn for_carrier(carrier: CarrierCode) -> Self {
+        let (case, max_line_length) = match carrier {
+            // La Poste impose les majuscules sur l'ensemble de l'adresse
+            CarrierCode::Colissimo => (CaseStyle::Upper, DEFAULT_MAX_LINE_LENGTH),
+            CarrierCode::Chronopost => (CaseStyle::Upper, 38),
+            CarrierCode::UPS | CarrierCode::FedEx => (CaseStyle::Postal, DEFAULT_MAX_LINE_LENGTH),
+            CarrierCode::DHL => (CaseStyle::Postal, 45),
+        };
+
+        Self {
+            case,
+            ascii_only: true,
+            street_types: StreetTypeStyle::AbbreviateToFit,
+            max_line_length: Some(max_line_length),
+        }
+    }
+
+    /// Définit la casse appliquée
+    pub fn with_case(mut self, case: CaseStyle) -> Self {
+        self.case = case;
+        self
+    }
+
+    /// Remplace accents et ligatures par des caractères ASCII
+    pub fn with_ascii_only(mut self, ascii_only: bool) -> Self {
+        self.ascii_only = ascii_only;
+        self
+    }
+
+    /// Définit le traitement des types de voie
+    pub fn with_street_types(mut self, style: StreetTypeStyle) -> Self {
+        self.street_types = style;
+        self
+    }
+
+    /// Limite la longueur des lignes (0 = pas de limite)
+    pub fn with_max_line_length(mut self, max_line_length: usize) -> Self {
+        self.max_line_length = (max_line_length > 0).then_some(max_line_length);
+        self
+    }
+
+    /// Longueur de ligne maximale, si elle est limitée
+    pub fn max_line_length(&self) -> Option<usize> {
+        self.max_line_length
+    }
+
+    /// Normalise une copie de l'adresse
+    pub fn normalize(&self, address: &Address) -> NormalizedAddress {
+        let mut normalized = address.clone();
+        let changes = self.normalize_in_place(&mut normalized);
+        let street = split_house_number(&normalized.street1, &normalized.country);
+
+        NormalizedAddress { address: normalized, street, changes }
+    }
+
+    /// Normalise une adresse et retourne les modifications apportées
+    pub fn normalize_in_place(&self, address: &mut Address) -> Vec<AddressChange> {
+        let mut changes = Vec::new();
+
+        // Espaces
+        drop_empty(&mut changes, "company", &mut address.company);
+        drop_empty(&mut changes, "street2", &mut address.street2);
+        drop_empty(&mut changes, "state", &mut address.state);
+        for (field, value) in text_fields(address) {
+            update(&mut changes, field, AddressChangeKind::Whitespace, value, collapse_whitespace);
+        }
+        update(&mut changes, "postal_code", AddressChangeKind::Whitespace, &mut address.postal_code, collapse_whitespace);
+
+        // Code pays et code postal
+        update(&mut changes, "country", AddressChangeKind::Country, &mut address.country, |country| country.to_uppercase());
+        let country = address.country.clone();
+        update(&mut changes, "postal_code", AddressChangeKind::PostalCode, &mut address.postal_code, |postal_code| {
+            geo::format_postal_code(&country, postal_code)
+        });
+
+        // Types de voie
+        let rewrite = match self.street_types {
+            StreetTypeStyle::Expand => Some(StreetTypeRewrite::Expand),
+            StreetTypeStyle::Abbreviate => Some(StreetTypeRewrite::Abbreviate),
+            StreetTypeStyle::Keep | StreetTypeStyle::AbbreviateToFit => None,
+        };
+        if let Some(rewrite) = rewrite {
+            for (field, value) in street_fields(address) {
+                update(&mut changes, field, rewrite.kind(), value, |street| rewrite_street_type(street, &country, rewrite));
+            }
+        }
+
+        // Translittération
+        if self.ascii_only {
+            for (field, value) in text_fields(address) {
+                update(&mut changes, field, AddressChangeKind::Transliterated, value, transliterate);
+            }
+        }
+
+        // Casse
+        let uppercase: &[&str] = match self.case {
+            CaseStyle::Keep => &[],
+            CaseStyle::Postal => postal_uppercase_fields(&country),
+            CaseStyle::Upper => &["name", "company", "street1", "street2", "city", "state", "postal_code"],
+        };
+        for (field, value) in text_fields(address) {
+            if uppercase.contains(&field) {
+                update(&mut changes, field, AddressChangeKind::Uppercased, value, |text| text.to_uppercase());
+            }
+        }
+        if uppercase.contains(&"postal_code") {
+            update(&mut changes, "postal_code", AddressChangeKind::Uppercased, &mut address.postal_code, |code| code.to_uppercase());
+        }
+
+        // Longueur des lignes
+        if let Some(max) = self.max_line_length {
+            self.fit_lines(&mut changes, address, max);
+        }
+
+        changes
+    }
+
+    /// Met l'adresse normalisée en forme pour l'affichage
+    pub fn format(&self, address: &Address) -> String {
+        formatting::format_address(&self.normalize(address).address)
+    }
+
+    /// Ramène chaque ligne à la longueur maximale
+    fn fit_lines(&self, changes: &mut Vec<AddressChange>, address: &mut Address, max: usize) {
+        let country = address.country.clone();
+
+        if self.street_types == StreetTypeStyle::AbbreviateToFit {
+            for (field, value) in street_fields(address) {
+                if char_count(value) > max {
+                    update(changes, field, AddressChangeKind::StreetTypeAbbreviated, value, |street| {
+                        rewrite_street_type(street, &country, StreetTypeRewrite::Abbreviate)
+                    });
+                }
+            }
+        }
+
+        // Report de la fin de street1 en tête de street2
+        if char_count(&address.street1) > max {
+            let (head, tail) = split_at_word(&address.street1, max);
+            let street2_before = address.street2.clone().unwrap_or_default();
+            let street2_after = match street2_before.as_str() {
+                "" => tail.to_string(),
+                existing => format!("{} {}", tail, existing),
+            };
+
+            push_change(changes, "street1", AddressChangeKind::Wrapped, &address.street1, head);
+            push_change(changes, "street2", AddressChangeKind::Wrapped, &street2_before, &street2_after);
+            address.street1 = head.to_string();
+            address.street2 = Some(street2_after);
+        }
+
+        for (field, value) in text_fields(address) {
+            if char_count(value) > max {
+                update(changes, field, AddressChangeKind::Truncated, value, |text| split_at_word(text, max).0.to_string());
+            }
+        }
+    }
+}
+
+/// Sens de réécriture des types de voie
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StreetTypeRewrite {
+    Expand,
+    Abbreviate,
+}
+
+impl StreetTypeRewrite {
+    fn kind(self) -> AddressChangeKind {
+        match self {
+            StreetTypeRewrite::Expand => AddressChangeKind::StreetTypeExpanded,
+            StreetTypeRewrite::Abbreviate => AddressChangeKind::StreetTypeAbbreviated,
+        }
+    }
+}
+
+/// Types de voie (forme complète, abréviation) placés avant le nom de la voie
+/// (abréviations de la norme AFNOR NF Z10-011)
+const FRENCH_STREET_TYPES: &[(&str, &str)] = &[
+    ("AVENUE", "AV"), ("BOULEVARD", "BD"), ("PLACE", "PL"), ("CHEMIN", "CHE"), ("ROUTE", "RTE"),
+    ("IMPASSE", "IMP"), ("ALLÉE", "ALL"), ("FAUBOURG", "FG"), ("SQUARE", "SQ"), ("COURS", "CRS"),
+    ("QUARTIER", "QUA"), ("RÉSIDENCE", "RES"), ("LOTISSEMENT", "LOT"), ("PROMENADE", "PROM"), ("CARREFOUR", "CAR"),
+];
+
+/// Types de voie espagnols (codes de l'INE)
+const SPANISH_STREET_TYPES: &[(&str, &str)] = &[
+    ("CALLE", "CL"), ("AVENIDA", "AV"), ("PLAZA", "PL"), ("PASEO", "PS"), ("CARRETERA", "CTRA"),
+    ("CAMINO", "CM"), ("TRAVESÍA", "TR"), ("RONDA", "RD"),
+];
+
+/// Types de voie italiens
+const ITALIAN_STREET_TYPES: &[(&str, &str)] = &[
+    ("VIALE", "V.LE"), ("PIAZZA", "P.ZZA"), ("CORSO", "C.SO"), ("PIAZZALE", "P.LE"), ("LARGO", "L.GO"),
+    ("VICOLO", "V.LO"),
+];
+
+/// Types de voie anglais, placés après le nom de la voie (USPS Publication 28)
+const ENGLISH_STREET_TYPES: &[(&str, &str)] = &[
+    ("STREET", "ST"), ("AVENUE", "AVE"), ("BOULEVARD", "BLVD"), ("ROAD", "RD"), ("DRIVE", "DR"),
+    ("LANE", "LN"), ("COURT", "CT"), ("PLACE", "PL"), ("SQUARE", "SQ"), ("TERRACE", "TER"),
+    ("HIGHWAY", "HWY"), ("PARKWAY", "PKWY"), ("CIRCLE", "CIR"), ("CRESCENT", "CRES"), ("CLOSE", "CL"),
+    ("GARDENS", "GDNS"),
+];
+
+/// Position du type de voie par rapport au nom
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StreetTypePosition {
+    Before,  // « avenue Foch »
+    After,  // « Main Street »
+    Suffix,  // « Hauptstraße », « Berliner Straße »
+}
+
+/// Types de voie du pays, avec leur position
+fn street_types(country: &str) -> Option<(&'static [(&'static str, &'static str)], StreetTypePosition)> {
+    match country {
+        "FR" | "MC" | "BE" | "LU" => Some((FRENCH_STREET_TYPES, StreetTypePosition::Before)),
+        "ES" => Some((SPANISH_STREET_TYPES, StreetTypePosition::Before)),
+        "IT" => Some((ITALIAN_STREET_TYPES, StreetTypePosition::Before)),
+        "US" | "CA" | "PR" | "GB" | "IE" | "AU" | "NZ" => Some((ENGLISH_STREET_TYPES, StreetTypePosition::After)),
+        "DE" | "AT" | "CH" | "LI" => Some((&[], StreetTypePosition::Suffix)),
+        _ => None,
+    }
+}
+
+/// Champs écrits en majuscules selon les conventions postales du pays
+fn postal_uppercase_fields(country: &str) -> &'static [&'static str] {
+    match country {
+        // Norme AFNOR NF Z10-011, USPS Publication 28, Postes Canada
+        "FR" | "MC" | "US" | "CA" | "PR" => &["street1", "street2", "city", "state", "postal_code"],
+        // Localité en majuscules
+        "GB" | "IE" | "BE" | "LU" | "IT" => &["city", "postal_code"],
+        _ => &["postal_code"],
+    }
+}
+
+/// Réécrit le type de voie d'une ligne, en conservant la casse du mot remplacé
+fn rewrite_street_type(street: &str, country: &str, rewrite: StreetTypeRewrite) -> String {
+    let Some((table, position)) = street_types(country) else {
+        return street.to_string();
+    };
+    let mut words: Vec<String> = street.split(' ').map(str::to_string).collect();
+
+    if position == StreetTypePosition::Suffix {
+        for word in words.iter_mut() {
+            if let Some(replacement) = rewrite_german_suffix(word, rewrite) {
+                *word = replacement;
+            }
+        }
+        return words.join(" ");
+    }
+
+    let lookup = |word: &str| {
+        let key = transliterate(word).trim_end_matches('.').to_uppercase();
+        table.iter().find_map(|(full, abbreviation)| match rewrite {
+            StreetTypeRewrite::Expand if key == transliterate(abbreviation).trim_end_matches('.') => Some(*full),
+            StreetTypeRewrite::Abbreviate if key == transliterate(full) => Some(*abbreviation),
+            _ => None,
+        })
+    };
+
+    // Premier mot après le numéro, ou dernier mot (hors premier) pour les adresses anglaises
+    let index = match position {
+        StreetTypePosition::Before => words.iter().position(|word| !is_house_number_word(word)),
+        _ => (1..words.len()).rev().find(|index| lookup(&words[*index]).is_some()),
+    };
+    if let Some(index) = index
+        && let Some(replacement) = lookup(&words[index])
+    {
+        words[index] = match_case(&words[index], replacement);
+    }
+
+    words.join(" ")
+}
+
+/// Réécrit le suffixe « straße » d'un mot allemand
+fn rewrite_german_suffix(word: &str, rewrite: StreetTypeRewrite) -> Option<String> {
+    let lower = word.to_lowercase();
+    let (stem_length, replacement) = match rewrite {
+        StreetTypeRewrite::Abbreviate => {
+            let suffix = ["straße", "strasse"].into_iter().find(|suffix| lower.ends_with(suffix))?;
+            (lower.len() - suffix.len(), "str.")
+        }
+        StreetTypeRewrite::Expand => {
+            let suffix = ["str.", "str"].into_iter().find(|suffix| lower.ends_with(suffix))?;
+            (lower.len() - suffix.len(), "straße")
+        }
+    };
+
+    // Indices calculés sur la forme minuscule : ne s'applique qu'aux mots dont la casse ne change pas la longueur
+    if lower.len() != word.len() || !word.is_char_boundary(stem_length) {
+        return None;
+    }
+    let (stem, suffix) = word.split_at(stem_length);
+    Some(format!("{}{}", stem, match_case(suffix, replacement)))
+}
+
+/// Applique à un remplacement la casse du mot d'origine
+fn match_case(original: &str, replacement: &str) -> String {
+    let letters: Vec<char> = original.chars().filter(|c| c.is_alphabetic()).collect();
+    if letters.iter().all(|c| c.is_uppercase()) {
+        replacement.to_uppercase()
+    } else if letters.iter().all(|c| c.is_lowercase()) {
+        replacement.to_lowercase()
+    } else {
+        let lower = replacement.to_lowercase();
+        let mut chars = lower.chars();
+        chars.next().map(|first| first.to_uppercase().chain(chars).collect()).unwrap_or_default()
+    }
+}
+
+/// Mot faisant partie du numéro de voie (« 12 », « 12bis », « bis »)
+fn is_house_number_word(word: &str) -> bool {
+    word.starts_with(|c: char| c.is_ascii_digit())
+        || ["BIS", "TER", "QUATER"].contains(&word.trim_end_matches(',').to_uppercase().as_str())
+}
+
+/// Numéro en tête de ligne (« 12 bis rue... », « 221B Baker Street »)
+static LEADING_NUMBER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^(\d+(?:\s*-\s*\d+)?(?:\s*(?:bis|ter|quater)\b|[a-z]\b)?)[\s,]+(\D.*)$").expect("expression valide")
+});
+
+/// Numéro en fin de ligne (« Hauptstraße 5a », « Calle Mayor, 12 »)
+static TRAILING_NUMBER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^(.*\D)[\s,]+(\d+(?:\s*[-/]\s*\d+)?\s*[a-z]?)$").expect("expression valide")
+});
+
+/// Sépare le numéro de voie du nom de la voie, selon l'usage du pays
+pub fn split_house_number(street: &str, country: &str) -> StreetParts {
+    let street = street.trim();
+    let number_first = matches!(
+        country.trim().to_uppercase().as_str(),
+        "FR" | "MC" | "LU" | "US" | "CA" | "PR" | "GB" | "IE" | "AU" | "NZ"
+    );
+
+    let leading = || LEADING_NUMBER.captures(street).map(|captures| (captures[1].to_string(), captures[2].to_string()));
+    let trailing = || TRAILING_NUMBER.captures(street).map(|captures| (captures[2].to_string(), captures[1].to_string()));
+    let found = if number_first { leading().or_else(trailing) } else { trailing().or_else(leading) };
+
+    match found {
+        Some((number, name)) => StreetParts {
+            house_number: Some(normalize_house_number(&number)),
+            street: name.trim().trim_end_matches(',').trim().to_string(),
+        },
+        None => StreetParts { house_number: None, street: street.to_string() },
+    }
+}
+
+/// Écrit un numéro de voie sans espaces superflus, indice séparé (« 12bis » → « 12 bis »)
+fn normalize_house_number(number: &str) -> String {
+    let compact: String = number.split_whitespace().collect();
+    let digits = compact.trim_end_matches(|c: char| c.is_alphabetic());
+    let suffix = &compact[digits.len()..];
+
+    if suffix.chars().count() > 1 {
+        format!("{} {}", digits, suffix)
+    } else {
+        compact
+    }
+}
+
+/// Remplace accents et ligatures par des caractères ASCII
+///
+/// Les caractères sans équivalent (alphabets non latins) deviennent `?`.
+pub fn transliterate(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        if c.is_ascii() {
+            result.push(c);
+            continue;
+        }
+
+        let replacement = match c {
+            'ß' => "ss",
+            'ẞ' => "SS",
+            'Æ' => "AE",
+            'æ' => "ae",
+            'Œ' => "OE",
+            'œ' => "oe",
+            'Ø' => "O",
+            'ø' => "o",
+            'Ł' => "L",
+            'ł' => "l",
+            'Đ' => "D",
+            'đ' => "d",
+            'Þ' => "TH",
+            'þ' => "th",
+            '‘' | '’' | '´' => "'",
+            '“' | '”' | '«' | '»' => "\"",
+            '–' | '—' => "-",
+            'º' | '°' => "o",
+            'ª' => "a",
+            _ if c.is_whitespace() => " ",
+            _ => {
+                let decomposed: String = c.nfd().filter(char::is_ascii).collect();
+                result.push_str(if decomposed.is_empty() { "?" } else { &decomposed });
+                continue;
+            }
+        };
+        result.push_str(replacement);
+    }
+
+    result
+}
+
+/// Longueur d'un texte en caractères
+fn char_count(text: &str) -> usize {
+    text.chars().count()
+}
+
+/// Coupe un texte au dernier espace avant `max` caractères (ou à `max` s'il n'y en a pas)
+fn split_at_word(text: &str, max: usize) -> (&str, &str) {
+    let limit = text.char_indices().nth(max).map_or(text.len(), |(index, _)| index);
+    let cut = match text[..limit].rfind(' ') {
+        Some(space) if space > 0 && text[limit..].starts_with(' ') => limit,
+        Some(space) if space > 0 => space,
+        _ => limit,
+    };
+
+    (text[..cut].trim_end_matches([' ', ',']), text[cut..].trim_start_matches([' ', ',']))
+}
+
+/// Supprime les espaces en tête, en fin et en double
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ").replace(" ,", ",")
+}
+
+/// Champs de texte libre de l'adresse
+fn text_fields(address: &mut Address) -> Vec<(&'static str, &mut String)> {
+    let mut fields = vec![("name", &mut address.name)];
+    if let Some(company) = address.company.as_mut() {
+        fields.push(("company", company));
+    }
+    fields.extend(street_fields_of(&mut address.street1, &mut address.street2));
+    fields.push(("city", &mut address.city));
+    if let Some(state) = address.state.as_mut() {
+        fields.push(("state", state));
+    }
+    fields
+}
+
+/// Lignes de voie de l'adresse
+fn street_fields(address: &mut Address) -> Vec<(&'static str, &mut String)> {
+    street_fields_of(&mut address.street1, &mut address.street2)
+}
+
+fn street_fields_of<'a>(street1: &'a mut String, street2: &'a mut Option<String>) -> Vec<(&'static str, &'a mut String)> {
+    let mut fields = vec![("street1", street1)];
+    if let Some(street2) = street2.as_mut() {
+        fields.push(("street2", street2));
+    }
+    fields
+}
+
+/// Applique une transformation à un champ et consigne la modification
+fn update(
+    changes: &mut Vec<AddressChange>,
+    field: &str,
+    kind: AddressChangeKind,
+    value: &mut String,
+    transform: impl FnOnce(&str) -> String
+) {
+    let updated = transform(value);
+    if updated != *value {
+        push_change(changes, field, kind, value, &updated);
+        *value = updated;
+    }
+}
+
+/// Retire un champ facultatif vide
+fn drop_empty(changes: &mut Vec<AddressChange>, field: &str, value: &mut Option<String>) {
+    if let Some(text) = value.take_if(|text| text.trim().is_empty()) {
+        push_change(changes, field, AddressChangeKind::Whitespace, &text, "");
+    }
+}
+
+fn push_change(changes: &mut Vec<AddressChange>, field: &str, kind: AddressChangeKind, before: &str, after: &str) {
+    changes.push(AddressChange {
+        field: field.to_string(),
+        kind,
+        before: before.to_string(),
+        after: after.to_string(),
+    });
+}
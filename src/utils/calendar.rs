@@ -0,0 +1,356 @@
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, LazyLock, PoisonError, RwLock};
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc, Weekday};
+use chrono_tz::Tz;
+
+use crate::models::CarrierCode;
+
+/// Pays dont les jours fériés nationaux sont connus de la bibliothèque
+pub const SUPPORTED_COUNTRIES: [&str; 7] = ["FR", "DE", "BE", "IT", "ES", "GB", "US"];
+
+/// Jours fériés nationaux d'un pays pour une année
+type HolidayCache = HashMap<(String, i32), Arc<[Holiday]>>;
+
+/// Jours fériés nationaux déjà calculés, par pays et par année
+static HOLIDAYS: LazyLock<RwLock<HolidayCache>> = LazyLock::new(Default::default);
+
+/// Jour férié
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Holiday {
+    pub date: NaiveDate,
+    pub name: &'static str,
+}
+
+/// Dimanche de Pâques (calendrier grégorien, algorithme anonyme de Meeus)
+pub fn easter_sunday(year: i32) -> NaiveDate {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32).unwrap_or_default()
+}
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap_or_default()
+}
+
+/// `n`-ième jour de la semaine d'un mois (le dernier si `n` vaut 0)
+fn nth_weekday(year: i32, month: u32, weekday: Weekday, n: u8) -> NaiveDate {
+    if n == 0 {
+        let next_month = if month == 12 { date(year + 1, 1, 1) } else { date(year, month + 1, 1) };
+        let last = next_month - Duration::days(1);
+        let back = (7 + last.weekday().num_days_from_monday() - weekday.num_days_from_monday()) % 7;
+        return last - Duration::days(i64::from(back));
+    }
+
+    NaiveDate::from_weekday_of_month_opt(year, month, weekday, n).unwrap_or_default()
+}
+
+fn is_weekend(date: NaiveDate) -> bool {
+    matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+}
+
+/// Jour chômé aux États-Unis : veille d'un samedi férié, lendemain d'un dimanche férié
+fn us_observed(date: NaiveDate) -> NaiveDate {
+    match date.weekday() {
+        Weekday::Sat => date - Duration::days(1),
+        Weekday::Sun => date + Duration::days(1),
+        _ => date,
+    }
+}
+
+/// Jours fériés nationaux d'un pays pour une année, triés par date
+///
+/// Seuls les jours fériés nationaux sont connus (pas les fêtes régionales ni les
+/// jours exceptionnels) ; `BusinessCalendar::with_holidays` permet de les compléter.
+/// Un pays non pris en charge n'a aucun jour férié.
+pub fn public_holidays(country: &str, year: i32) -> Vec<Holiday> {
+    cached_holidays(country, year).to_vec()
+}
+
+/// Jours fériés d'un pays pour une année, calculés une seule fois
+fn cached_holidays(country: &str, year: i32) -> Arc<[Holiday]> {
+    let country = country.to_uppercase();
+    if !SUPPORTED_COUNTRIES.contains(&country.as_str()) {
+        return Arc::from([]);
+    }
+
+    let key = (country, year);
+    if let Some(holidays) = HOLIDAYS.read().unwrap_or_else(PoisonError::into_inner).get(&key) {
+        return holidays.clone();
+    }
+
+    let holidays: Arc<[Holiday]> = compute_holidays(&key.0, year).into();
+    HOLIDAYS.write().unwrap_or_else(PoisonError::into_inner).entry(key).or_insert(holidays).clone()
+}
+
+/// Calcule les jours fériés nationaux d'un pays (code ISO en majuscules) pour une année
+fn compute_holidays(country: &str, year: i32) -> Vec<Holiday> {
+    let easter = easter_sunday(year);
+    let easter_offset = |days: i64| easter + Duration::days(days);
+    let holiday = |date: NaiveDate, name: &'static str| Holiday { date, name };
+
+    let mut holidays = match country {
+        "FR" => vec![
+            holiday(date(year, 1, 1), "Jour de l'an"),
+            holiday(easter_offset(1), "Lundi de Pâques"),
+            holiday(date(year, 5, 1), "Fête du Travail"),
+            holiday(date(year, 5, 8), "Victoire 1945"),
+            holiday(easter_offset(39), "Ascension"),
+            holiday(easter_offset(50), "Lundi de Pentecôte"),
+            holiday(date(year, 7, 14), "Fête nationale"),
+            holiday(date(year, 8, 15), "Assomption"),
+            holiday(date(year, 11, 1), "Toussaint"),
+            holiday(date(year, 11, 11), "Armistice 1918"),
+            holiday(date(year, 12, 25), "Noël"),
+        ],
+        "DE" => vec![
+            holiday(date(year, 1, 1), "Neujahr"),
+            holiday(easter_offset(-2), "Karfreitag"),
+            holiday(easter_offset(1), "Ostermontag"),
+            holiday(date(year, 5, 1), "Tag der Arbeit"),
+            holiday(easter_offset(39), "Christi Himmelfahrt"),
+            holiday(easter_offset(50), "Pfingstmontag"),
+            holiday(date(year, 10, 3), "Tag der Deutschen Einheit"),
+            holiday(date(year, 12, 25), "Erster Weihnachtstag"),
+            holiday(date(year, 12, 26), "Zweiter Weihnachtstag"),
+        ],
+        "BE" => vec![
+            holiday(date(year, 1, 1), "Nouvel An"),
+            holiday(easter_offset(1), "Lundi de Pâques"),
+            holiday(date(year, 5, 1), "Fête du Travail"),
+            holiday(easter_offset(39), "Ascension"),
+            holiday(easter_offset(50), "Lundi de Pentecôte"),
+            holiday(date(year, 7, 21), "Fête nationale"),
+            holiday(date(year, 8, 15), "Assomption"),
+            holiday(date(year, 11, 1), "Toussaint"),
+            holiday(date(year, 11, 11), "Armistice"),
+            holiday(date(year, 12, 25), "Noël"),
+        ],
+        "IT" => vec![
+            holiday(date(year, 1, 1), "Capodanno"),
+            holiday(date(year, 1, 6), "Epifania"),
+            holiday(easter_offset(1), "Lunedì dell'Angelo"),
+            holiday(date(year, 4, 25), "Festa della Liberazione"),
+            holiday(date(year, 5, 1), "Festa del Lavoro"),
+            holiday(date(year, 6, 2), "Festa della Repubblica"),
+            holiday(date(year, 8, 15), "Ferragosto"),
+            holiday(date(year, 11, 1), "Ognissanti"),
+            holiday(date(year, 12, 8), "Immacolata Concezione"),
+            holiday(date(year, 12, 25), "Natale"),
+            holiday(date(year, 12, 26), "Santo Stefano"),
+        ],
+        "ES" => vec![
+            holiday(date(year, 1, 1), "Año Nuevo"),
+            holiday(date(year, 1, 6), "Epifanía del Señor"),
+            holiday(easter_offset(-2), "Viernes Santo"),
+            holiday(date(year, 5, 1), "Fiesta del Trabajo"),
+            holiday(date(year, 8, 15), "Asunción de la Virgen"),
+            holiday(date(year, 10, 12), "Fiesta Nacional de España"),
+            holiday(date(year, 11, 1), "Todos los Santos"),
+            holiday(date(year, 12, 6), "Día de la Constitución"),
+            holiday(date(year, 12, 8), "Inmaculada Concepción"),
+            holiday(date(year, 12, 25), "Navidad"),
+        ],
+        // Angleterre et pays de Galles : jours fériés reportés au premier jour ouvré libre
+        "GB" => {
+            let mut holidays = vec![
+                holiday(easter_offset(-2), "Good Friday"),
+                holiday(easter_offset(1), "Easter Monday"),
+                holiday(nth_weekday(year, 5, Weekday::Mon, 1), "Early May bank holiday"),
+                holiday(nth_weekday(year, 5, Weekday::Mon, 0), "Spring bank holiday"),
+                holiday(nth_weekday(year, 8, Weekday::Mon, 0), "Summer bank holiday"),
+            ];
+            for (fixed, name) in [(date(year, 1, 1), "New Year's Day"), (date(year, 12, 25), "Christmas Day"), (date(year, 12, 26), "Boxing Day")] {
+                let mut observed = fixed;
+                while is_weekend(observed) || holidays.iter().any(|holiday| holiday.date == observed) {
+                    observed += Duration::days(1);
+                }
+                holidays.push(holiday(observed, name));
+            }
+            holidays
+        }
+        // Jours fériés fédéraux, chômés la veille ou le lendemain s'ils tombent un week-end
+        "US" => {
+            let mut holidays = vec![
+                holiday(us_observed(date(year, 1, 1)), "New Year's Day"),
+                holiday(nth_weekday(year, 1, Weekday::Mon, 3), "Martin Luther King Jr. Day"),
+                holiday(nth_weekday(year, 2, Weekday::Mon, 3), "Washington's Birthday"),
+                holiday(nth_weekday(year, 5, Weekday::Mon, 0), "Memorial Day"),
+                holiday(us_observed(date(year, 7, 4)), "Independence Day"),
+                holiday(nth_weekday(year, 9, Weekday::Mon, 1), "Labor Day"),
+                holiday(nth_weekday(year, 10, Weekday::Mon, 2), "Columbus Day"),
+                holiday(us_observed(date(year, 11, 11)), "Veterans Day"),
+                holiday(nth_weekday(year, 11, Weekday::Thu, 4), "Thanksgiving Day"),
+                holiday(us_observed(date(year, 12, 25)), "Christmas Day"),
+            ];
+            if year >= 2021 {
+                holidays.push(holiday(us_observed(date(year, 6, 19)), "Juneteenth"));
+            }
+            // Un 1er janvier tombant un samedi est chômé le 31 décembre de l'année précédente
+            let next_new_year = us_observed(date(year + 1, 1, 1));
+            if next_new_year.year() == year {
+                holidays.push(holiday(next_new_year, "New Year's Day"));
+            }
+            holidays.retain(|holiday| holiday.date.year() == year);
+            holidays
+        }
+        _ => Vec::new(),
+    };
+
+    holidays.sort_by_key(|holiday| holiday.date);
+    holidays
+}
+
+/// Jour férié national d'un pays à une date, s'il y en a un
+pub fn holiday_on(country: &str, date: NaiveDate) -> Option<Holiday> {
+    cached_holidays(country, date.year()).iter().find(|holiday| holiday.date == date).cloned()
+}
+
+/// Indique si une date est un jour férié national d'un pays
+pub fn is_public_holiday(country: &str, date: NaiveDate) -> bool {
+    holiday_on(country, date).is_some()
+}
+
+/// Fuseau horaire de référence d'un pays, celui de la capitale pour un pays qui en a plusieurs
+///
+/// Un pays non pris en charge est à l'heure de Paris.
+pub fn country_timezone(country: &str) -> Tz {
+    match country.to_uppercase().as_str() {
+        "DE" => chrono_tz::Europe::Berlin,
+        "BE" => chrono_tz::Europe::Brussels,
+        "IT" => chrono_tz::Europe::Rome,
+        "ES" => chrono_tz::Europe::Madrid,
+        "GB" => chrono_tz::Europe::London,
+        "US" => chrono_tz::America::New_York,
+        _ => chrono_tz::Europe::Paris,
+    }
+}
+
+/// Jours fériés augmentés des jours ajoutés et des jours non opérés par chaque transporteur
+///
+/// Par défaut, les transporteurs opèrent du lundi au vendredi, hors jours fériés du pays.
+#[derive(Debug, Clone, Default)]
+pub struct BusinessCalendar {
+    holidays: HashMap<String, BTreeSet<NaiveDate>>,  // Jours fériés ajoutés, par code pays ISO
+    closures: HashMap<CarrierCode, BTreeSet<NaiveDate>>,  // Jours non opérés ponctuels d'un transporteur
+    operating_days: HashMap<CarrierCode, Vec<Weekday>>,  // Jours de la semaine opérés, si différents du lundi-vendredi
+    timezones: HashMap<String, Tz>,  // Fuseaux horaires, si différents de celui de la capitale
+}
+
+impl BusinessCalendar {
+    /// Crée un calendrier limité aux jours fériés nationaux
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ajoute des jours fériés pour un pays (fêtes régionales, jours exceptionnels)
+    pub fn with_holidays(mut self, country: &str, dates: impl IntoIterator<Item = NaiveDate>) -> Self {
+        self.holidays.entry(country.to_uppercase()).or_default().extend(dates);
+        self
+    }
+
+    /// Ajoute des jours où un transporteur n'opère pas (fermeture, grève)
+    pub fn with_closures(mut self, carrier: CarrierCode, dates: impl IntoIterator<Item = NaiveDate>) -> Self {
+        self.closures.entry(carrier).or_default().extend(dates);
+        self
+    }
+
+    /// Définit les jours de la semaine où un transporteur opère
+    pub fn with_operating_days(mut self, carrier: CarrierCode, weekdays: &[Weekday]) -> Self {
+        self.operating_days.insert(carrier, weekdays.to_vec());
+        self
+    }
+
+    /// Définit le fuseau horaire d'un pays (territoires d'outre-mer, pays à plusieurs fuseaux)
+    pub fn with_timezone(mut self, country: &str, timezone: Tz) -> Self {
+        self.timezones.insert(country.to_uppercase(), timezone);
+        self
+    }
+
+    /// Fuseau horaire d'un pays
+    pub fn timezone(&self, country: &str) -> Tz {
+        self.timezones.get(&country.to_uppercase()).copied().unwrap_or_else(|| country_timezone(country))
+    }
+
+    /// Date locale d'un instant dans un pays
+    pub fn local_date(&self, country: &str, at: DateTime<Utc>) -> NaiveDate {
+        at.with_timezone(&self.timezone(country)).date_naive()
+    }
+
+    /// Indique si une date est fériée dans un pays
+    pub fn is_holiday(&self, country: &str, date: NaiveDate) -> bool {
+        is_public_holiday(country, date)
+            || self.holidays.get(&country.to_uppercase()).is_some_and(|dates| dates.contains(&date))
+    }
+
+    /// Indique si une date est ouvrée (du lundi au vendredi, hors jours fériés)
+    pub fn is_business_day(&self, country: &str, date: NaiveDate) -> bool {
+        !is_weekend(date) && !self.is_holiday(country, date)
+    }
+
+    /// Indique si un transporteur opère à une date dans un pays
+    pub fn is_operating_day(&self, carrier: CarrierCode, country: &str, date: NaiveDate) -> bool {
+        let weekday_operated = match self.operating_days.get(&carrier) {
+            Some(weekdays) => weekdays.contains(&date.weekday()),
+            None => !is_weekend(date),
+        };
+
+        weekday_operated
+            && !self.is_holiday(country, date)
+            && !self.is_closed(carrier, date)
+    }
+
+    /// Indique si un transporteur a déclaré une fermeture ponctuelle à une date
+    pub fn is_closed(&self, carrier: CarrierCode, date: NaiveDate) -> bool {
+        self.closures.get(&carrier).is_some_and(|dates| dates.contains(&date))
+    }
+
+    /// Première date d'expédition possible à partir d'une date (incluse)
+    pub fn next_ship_date(&self, carrier: CarrierCode, country: &str, from: NaiveDate) -> NaiveDate {
+        if self.is_operating_day(carrier, country, from) {
+            from
+        } else {
+            next_day(from, |date| self.is_operating_day(carrier, country, date))
+        }
+    }
+
+    /// Ajoute des jours ouvrés à une date
+    pub fn add_business_days(&self, country: &str, from: NaiveDate, days: u32) -> NaiveDate {
+        (0..days).fold(from, |date, _| next_day(date, |date| self.is_business_day(country, date)))
+    }
+
+    /// Ajoute des jours opérés par un transporteur à une date
+    pub fn add_operating_days(&self, carrier: CarrierCode, country: &str, from: NaiveDate, days: u32) -> NaiveDate {
+        (0..days).fold(from, |date, _| next_day(date, |date| self.is_operating_day(carrier, country, date)))
+    }
+
+    /// Nombre de jours ouvrés après `from` jusqu'à `to` inclus
+    pub fn business_days_between(&self, country: &str, from: NaiveDate, to: NaiveDate) -> u32 {
+        from.iter_days()
+            .skip(1)
+            .take_while(|date| *date <= to)
+            .filter(|date| self.is_business_day(country, *date))
+            .count() as u32
+    }
+}
+
+/// Premier jour suivant une date qui satisfait un critère (au plus un an plus tard)
+pub(crate) fn next_day(from: NaiveDate, accept: impl Fn(NaiveDate) -> bool) -> NaiveDate {
+    (1..=366)
+        .map(|offset| from + Duration::days(offset))
+        .find(|date| accept(*date))
+        .unwrap_or(from + Duration::days(1))
+}
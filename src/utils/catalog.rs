@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::{LazyLock, PoisonError, RwLock, RwLockReadGuard};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::errors::DeliveryError;
+use crate::i18n::Locale;
+
+/// Catalogue livré avec un document YAML intégré, remplaçable ou complétable
+///
+/// Tables de statuts, traductions et modèles de notification se chargent tous de
+/// la même façon : document intégré, document ou fichier fourni, et surcharges
+/// partielles qui ne remplacent que les entrées qu'elles contiennent.
+pub trait YamlCatalog: Sized {
+    /// Nom du catalogue dans les messages d'erreur
+    const NAME: &'static str;
+
+    /// Document YAML intégré à la bibliothèque
+    const EMBEDDED: &'static str;
+
+    /// Charge un catalogue depuis un document YAML
+    fn from_yaml(yaml: &str) -> Result<Self, DeliveryError>;
+
+    /// Ajoute ou remplace des entrées ; les autres sont conservées
+    fn with_overrides(self, overrides: Self) -> Self;
+
+    /// Catalogue par défaut de la bibliothèque
+    fn embedded() -> Self {
+        Self::from_yaml(Self::EMBEDDED).unwrap_or_else(|e| panic!("{}: document intégré invalide: {}", Self::NAME, e))
+    }
+
+    /// Charge un catalogue depuis un fichier YAML
+    fn from_yaml_file(path: &str) -> Result<Self, DeliveryError> {
+        let content = std::fs::read_to_string(path)?;
+        Self::from_yaml(&content)
+    }
+
+    /// Ajoute ou remplace des entrées depuis un document YAML
+    fn with_overrides_yaml(self, yaml: &str) -> Result<Self, DeliveryError> {
+        Ok(self.with_overrides(Self::from_yaml(yaml)?))
+    }
+
+    /// Ajoute ou remplace des entrées depuis un fichier YAML
+    fn with_overrides_file(self, path: &str) -> Result<Self, DeliveryError> {
+        Ok(self.with_overrides(Self::from_yaml_file(path)?))
+    }
+}
+
+/// Entrées d'un catalogue, rangées par groupe (transporteur, langue) puis par clé
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(transparent)]
+pub(crate) struct CatalogTables<G: Eq + Hash, V> {
+    tables: HashMap<G, HashMap<String, V>>,
+}
+
+impl<G: Eq + Hash, V> Default for CatalogTables<G, V> {
+    fn default() -> Self {
+        Self { tables: HashMap::new() }
+    }
+}
+
+impl<G: Eq + Hash, V> CatalogTables<G, V> {
+    /// Document `groupe: { clé: valeur }`, chaque clé passant par `key`
+    pub(crate) fn from_yaml(yaml: &str, name: &str, key: impl Fn(&str) -> String) -> Result<Self, DeliveryError>
+    where
+        G: DeserializeOwned,
+        V: DeserializeOwned,
+    {
+        let document: HashMap<G, HashMap<String, V>> = parse(yaml, name)?;
+
+        let tables = document.into_iter()
+            .map(|(group, entries)| (group, entries.into_iter().map(|(name, value)| (key(&name), value)).collect()))
+            .collect();
+
+        Ok(Self { tables })
+    }
+
+    /// Document `groupe: { section: { clé: valeur } }`, clés `section.clé`
+    pub(crate) fn from_sectioned_yaml(yaml: &str, name: &str) -> Result<Self, DeliveryError>
+    where
+        G: DeserializeOwned,
+        V: DeserializeOwned,
+    {
+        let document: HashMap<G, HashMap<String, HashMap<String, V>>> = parse(yaml, name)?;
+
+        let tables = document.into_iter()
+            .map(|(group, sections)| {
+                let entries = sections.into_iter()
+                    .flat_map(|(section, entries)| {
+                        entries.into_iter().map(move |(key, value)| (format!("{}.{}", section, key), value))
+                    })
+                    .collect();
+                (group, entries)
+            })
+            .collect();
+
+        Ok(Self { tables })
+    }
+
+    /// Ajoute ou remplace les entrées d'autres tables
+    pub(crate) fn extend(&mut self, other: Self) {
+        for (group, entries) in other.tables {
+            self.tables.entry(group).or_default().extend(entries);
+        }
+    }
+
+    /// Ajoute ou remplace une entrée
+    pub(crate) fn insert(&mut self, group: G, key: String, value: V) {
+        self.tables.entry(group).or_default().insert(key, value);
+    }
+
+    /// Entrée d'un groupe
+    pub(crate) fn get(&self, group: &G, key: &str) -> Option<&V> {
+        self.tables.get(group)?.get(key)
+    }
+
+    /// Entrées d'un groupe
+    pub(crate) fn group(&self, group: &G) -> Option<&HashMap<String, V>> {
+        self.tables.get(group)
+    }
+}
+
+impl<V> CatalogTables<Locale, V> {
+    /// Premier résultat obtenu dans la langue demandée, la langue de repli, puis le français
+    pub(crate) fn find_localized<'a, R>(
+        &'a self,
+        locale: Locale,
+        fallback: Locale,
+        mut find: impl FnMut(Locale, &'a HashMap<String, V>) -> Option<R>
+    ) -> Option<R> {
+        [locale, fallback, Locale::Fr].into_iter()
+            .find_map(|locale| find(locale, self.tables.get(&locale)?))
+    }
+}
+
+fn parse<T: DeserializeOwned>(yaml: &str, name: &str) -> Result<T, DeliveryError> {
+    serde_yaml::from_str(yaml)
+        .map_err(|e| DeliveryError::SerializationError(format!("{}: document YAML invalide: {}", name, e)))
+}
+
+/// Catalogue utilisé par défaut dans le processus, initialisé au document intégré
+pub(crate) struct ActiveCatalog<T: 'static>(LazyLock<RwLock<T>, fn() -> RwLock<T>>);
+
+impl<T: YamlCatalog + Clone> ActiveCatalog<T> {
+    pub(crate) const fn new() -> Self {
+        Self(LazyLock::new(embedded_lock::<T>))
+    }
+
+    /// Remplace le catalogue actif
+    pub(crate) fn set(&self, catalog: T) {
+        *self.0.write().unwrap_or_else(PoisonError::into_inner) = catalog;
+    }
+
+    /// Copie du catalogue actif
+    pub(crate) fn get(&self) -> T {
+        self.read().clone()
+    }
+
+    /// Accès en lecture au catalogue actif
+    pub(crate) fn read(&self) -> RwLockReadGuard<'_, T> {
+        self.0.read().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+fn embedded_lock<T: YamlCatalog>() -> RwLock<T> {
+    RwLock::new(T::embedded())
+}
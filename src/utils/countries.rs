@@ -0,0 +1,283 @@
+/// Le pays est membre de l'Union européenne
+pub(crate) const EU: u8 = 1;
+
+/// Le pays est membre de l'Espace économique européen
+pub(crate) const EEA: u8 = 1 << 1;
+
+/// Le pays fait partie de l'union douanière de l'UE (pas de déclaration en douane)
+pub(crate) const CUSTOMS_UNION: u8 = 1 << 2;
+
+/// Enregistrement brut d'un pays ISO 3166-1
+pub(crate) struct CountryRecord {
+    pub code: &'static str,
+    pub name: &'static str,
+    pub phone_code: &'static str,
+    pub postal_code_format: Option<&'static str>,
+    pub postal_code_required: bool,
+    pub flags: u8,
+}
+
+const fn country(
+    code: &'static str,
+    name: &'static str,
+    phone_code: &'static str,
+    postal_code_format: Option<&'static str>,
+    postal_code_required: bool,
+    flags: u8,
+) -> CountryRecord {
+    CountryRecord { code, name, phone_code, postal_code_format, postal_code_required, flags }
+}
+
+/// Tous les pays ISO 3166-1 alpha-2 : nom, indicatif téléphonique, format de code postal
+/// (appliqué au code normalisé en majuscules), caractère obligatoire du code postal et appartenances
+pub(crate) static COUNTRIES: [CountryRecord; 249] = [
+    country("AD", "Andorra", "376", Some(r"^AD\d{3}$"), true, 0),
+    country("AE", "United Arab Emirates", "971", None, false, 0),
+    country("AF", "Afghanistan", "93", Some(r"^\d{4}$"), true, 0),
+    country("AG", "Antigua and Barbuda", "1", None, false, 0),
+    country("AI", "Anguilla", "1", Some(r"^AI-?2640$"), true, 0),
+    country("AL", "Albania", "355", Some(r"^\d{4}$"), true, 0),
+    country("AM", "Armenia", "374", Some(r"^\d{4}$"), true, 0),
+    country("AO", "Angola", "244", None, false, 0),
+    country("AQ", "Antarctica", "672", None, false, 0),
+    country("AR", "Argentina", "54", Some(r"^([A-HJ-NP-Z])?\d{4}([A-Z]{3})?$"), true, 0),
+    country("AS", "American Samoa", "1", Some(r"^96799(-\d{4})?$"), true, 0),
+    country("AT", "Austria", "43", Some(r"^\d{4}$"), true, EU | EEA | CUSTOMS_UNION),
+    country("AU", "Australia", "61", Some(r"^\d{4}$"), true, 0),
+    country("AW", "Aruba", "297", None, false, 0),
+    country("AX", "Åland Islands", "358", Some(r"^(AX-)?22\d{3}$"), true, 0),
+    country("AZ", "Azerbaijan", "994", Some(r"^(AZ ?)?\d{4}$"), true, 0),
+    country("BA", "Bosnia and Herzegovina", "387", Some(r"^\d{5}$"), true, 0),
+    country("BB", "Barbados", "1", Some(r"^(BB)?\d{5}$"), false, 0),
+    country("BD", "Bangladesh", "880", Some(r"^\d{4}$"), true, 0),
+    country("BE", "Belgium", "32", Some(r"^\d{4}$"), true, EU | EEA | CUSTOMS_UNION),
+    country("BF", "Burkina Faso", "226", None, false, 0),
+    country("BG", "Bulgaria", "359", Some(r"^\d{4}$"), true, EU | EEA | CUSTOMS_UNION),
+    country("BH", "Bahrain", "973", Some(r"^\d{3,4}$"), false, 0),
+    country("BI", "Burundi", "257", None, false, 0),
+    country("BJ", "Benin", "229", None, false, 0),
+    country("BL", "Saint Barthélemy", "590", Some(r"^97133$"), true, 0),
+    country("BM", "Bermuda", "1", Some(r"^[A-Z]{2} ?[A-Z0-9]{2}$"), true, 0),
+    country("BN", "Brunei Darussalam", "673", Some(r"^[A-Z]{2} ?\d{4}$"), true, 0),
+    country("BO", "Bolivia", "591", None, false, 0),
+    country("BQ", "Bonaire, Sint Eustatius and Saba", "599", None, false, 0),
+    country("BR", "Brazil", "55", Some(r"^\d{5}-?\d{3}$"), true, 0),
+    country("BS", "Bahamas", "1", None, false, 0),
+    country("BT", "Bhutan", "975", Some(r"^\d{5}$"), true, 0),
+    country("BV", "Bouvet Island", "47", None, false, 0),
+    country("BW", "Botswana", "267", None, false, 0),
+    country("BY", "Belarus", "375", Some(r"^\d{6}$"), true, 0),
+    country("BZ", "Belize", "501", None, false, 0),
+    country("CA", "Canada", "1", Some(r"^[ABCEGHJ-NPRSTVXY]\d[ABCEGHJ-NPRSTV-Z] ?\d[ABCEGHJ-NPRSTV-Z]\d$"), true, 0),
+    country("CC", "Cocos (Keeling) Islands", "61", Some(r"^6799$"), true, 0),
+    country("CD", "Congo, Democratic Republic of the", "243", None, false, 0),
+    country("CF", "Central African Republic", "236", None, false, 0),
+    country("CG", "Congo", "242", None, false, 0),
+    country("CH", "Switzerland", "41", Some(r"^\d{4}$"), true, 0),
+    country("CI", "Côte d'Ivoire", "225", None, false, 0),
+    country("CK", "Cook Islands", "682", None, false, 0),
+    country("CL", "Chile", "56", Some(r"^\d{7}$"), true, 0),
+    country("CM", "Cameroon", "237", None, false, 0),
+    country("CN", "China", "86", Some(r"^\d{6}$"), true, 0),
+    country("CO", "Colombia", "57", Some(r"^\d{6}$"), true, 0),
+    country("CR", "Costa Rica", "506", Some(r"^\d{5}$"), true, 0),
+    country("CU", "Cuba", "53", Some(r"^\d{5}$"), true, 0),
+    country("CV", "Cabo Verde", "238", Some(r"^\d{4}$"), true, 0),
+    country("CW", "Curaçao", "599", None, false, 0),
+    country("CX", "Christmas Island", "61", Some(r"^6798$"), true, 0),
+    country("CY", "Cyprus", "357", Some(r"^\d{4}$"), true, EU | EEA | CUSTOMS_UNION),
+    country("CZ", "Czechia", "420", Some(r"^\d{3} ?\d{2}$"), true, EU | EEA | CUSTOMS_UNION),
+    country("DE", "Germany", "49", Some(r"^\d{5}$"), true, EU | EEA | CUSTOMS_UNION),
+    country("DJ", "Djibouti", "253", None, false, 0),
+    country("DK", "Denmark", "45", Some(r"^\d{4}$"), true, EU | EEA | CUSTOMS_UNION),
+    country("DM", "Dominica", "1", None, false, 0),
+    country("DO", "Dominican Republic", "1", Some(r"^\d{5}$"), true, 0),
+    country("DZ", "Algeria", "213", Some(r"^\d{5}$"), true, 0),
+    country("EC", "Ecuador", "593", Some(r"^\d{6}$"), true, 0),
+    country("EE", "Estonia", "372", Some(r"^\d{5}$"), true, EU | EEA | CUSTOMS_UNION),
+    country("EG", "Egypt", "20", Some(r"^\d{5}$"), true, 0),
+    country("EH", "Western Sahara", "212", None, false, 0),
+    country("ER", "Eritrea", "291", None, false, 0),
+    country("ES", "Spain", "34", Some(r"^\d{5}$"), true, EU | EEA | CUSTOMS_UNION),
+    country("ET", "Ethiopia", "251", Some(r"^\d{4}$"), true, 0),
+    country("FI", "Finland", "358", Some(r"^\d{5}$"), true, EU | EEA | CUSTOMS_UNION),
+    country("FJ", "Fiji", "679", None, false, 0),
+    country("FK", "Falkland Islands (Malvinas)", "500", Some(r"^FIQQ ?1ZZ$"), true, 0),
+    country("FM", "Micronesia", "691", Some(r"^9694[1-4](-\d{4})?$"), true, 0),
+    country("FO", "Faroe Islands", "298", Some(r"^\d{3}$"), true, 0),
+    country("FR", "France", "33", Some(r"^\d{5}$"), true, EU | EEA | CUSTOMS_UNION),
+    country("GA", "Gabon", "241", None, false, 0),
+    country("GB", "United Kingdom", "44", Some(r"^(GIR ?0AA|[A-Z]{1,2}\d[A-Z\d]? ?\d[A-Z]{2})$"), true, 0),
+    country("GD", "Grenada", "1", None, false, 0),
+    country("GE", "Georgia", "995", Some(r"^\d{4}$"), true, 0),
+    country("GF", "French Guiana", "594", Some(r"^973\d{2}$"), true, 0),
+    country("GG", "Guernsey", "44", Some(r"^GY\d[\dA-Z]? ?\d[A-Z]{2}$"), true, 0),
+    country("GH", "Ghana", "233", None, false, 0),
+    country("GI", "Gibraltar", "350", Some(r"^GX11 ?1AA$"), true, 0),
+    country("GL", "Greenland", "299", Some(r"^39\d{2}$"), true, 0),
+    country("GM", "Gambia", "220", None, false, 0),
+    country("GN", "Guinea", "224", Some(r"^\d{3}$"), true, 0),
+    country("GP", "Guadeloupe", "590", Some(r"^971\d{2}$"), true, 0),
+    country("GQ", "Equatorial Guinea", "240", None, false, 0),
+    country("GR", "Greece", "30", Some(r"^\d{3} ?\d{2}$"), true, EU | EEA | CUSTOMS_UNION),
+    country("GS", "South Georgia and the South Sandwich Islands", "500", Some(r"^SIQQ ?1ZZ$"), true, 0),
+    country("GT", "Guatemala", "502", Some(r"^\d{5}$"), true, 0),
+    country("GU", "Guam", "1", Some(r"^969\d{2}(-\d{4})?$"), true, 0),
+    country("GW", "Guinea-Bissau", "245", Some(r"^\d{4}$"), true, 0),
+    country("GY", "Guyana", "592", None, false, 0),
+    country("HK", "Hong Kong", "852", None, false, 0),
+    country("HM", "Heard Island and McDonald Islands", "672", Some(r"^\d{4}$"), true, 0),
+    country("HN", "Honduras", "504", Some(r"^\d{5}$"), true, 0),
+    country("HR", "Croatia", "385", Some(r"^\d{5}$"), true, EU | EEA | CUSTOMS_UNION),
+    country("HT", "Haiti", "509", Some(r"^\d{4}$"), true, 0),
+    country("HU", "Hungary", "36", Some(r"^\d{4}$"), true, EU | EEA | CUSTOMS_UNION),
+    country("ID", "Indonesia", "62", Some(r"^\d{5}$"), true, 0),
+    country("IE", "Ireland", "353", Some(r"^([AC-FHKNPRTV-Y]\d{2} ?[\dAC-FHKNPRTV-Y]{4}|D6W ?[\dAC-FHKNPRTV-Y]{4})$"), false, EU | EEA | CUSTOMS_UNION),
+    country("IL", "Israel", "972", Some(r"^\d{5}(\d{2})?$"), true, 0),
+    country("IM", "Isle of Man", "44", Some(r"^IM\d[\dA-Z]? ?\d[A-Z]{2}$"), true, 0),
+    country("IN", "India", "91", Some(r"^\d{6}$"), true, 0),
+    country("IO", "British Indian Ocean Territory", "246", Some(r"^BBND ?1ZZ$"), true, 0),
+    country("IQ", "Iraq", "964", Some(r"^\d{5}$"), true, 0),
+    country("IR", "Iran", "98", Some(r"^\d{5}-?\d{5}$"), true, 0),
+    country("IS", "Iceland", "354", Some(r"^\d{3}$"), true, EEA),
+    country("IT", "Italy", "39", Some(r"^\d{5}$"), true, EU | EEA | CUSTOMS_UNION),
+    country("JE", "Jersey", "44", Some(r"^JE\d[\dA-Z]? ?\d[A-Z]{2}$"), true, 0),
+    country("JM", "Jamaica", "1", None, false, 0),
+    country("JO", "Jordan", "962", Some(r"^\d{5}$"), true, 0),
+    country("JP", "Japan", "81", Some(r"^\d{3}-?\d{4}$"), true, 0),
+    country("KE", "Kenya", "254", Some(r"^\d{5}$"), true, 0),
+    country("KG", "Kyrgyzstan", "996", Some(r"^\d{6}$"), true, 0),
+    country("KH", "Cambodia", "855", Some(r"^\d{5,6}$"), true, 0),
+    country("KI", "Kiribati", "686", None, false, 0),
+    country("KM", "Comoros", "269", None, false, 0),
+    country("KN", "Saint Kitts and Nevis", "1", None, false, 0),
+    country("KP", "Korea, Democratic People's Republic of", "850", None, false, 0),
+    country("KR", "Korea, Republic of", "82", Some(r"^\d{5}$"), true, 0),
+    country("KW", "Kuwait", "965", Some(r"^\d{5}$"), true, 0),
+    country("KY", "Cayman Islands", "1", Some(r"^KY\d-\d{4}$"), true, 0),
+    country("KZ", "Kazakhstan", "7", Some(r"^\d{6}$"), true, 0),
+    country("LA", "Lao People's Democratic Republic", "856", Some(r"^\d{5}$"), true, 0),
+    country("LB", "Lebanon", "961", Some(r"^\d{4}( ?\d{4})?$"), false, 0),
+    country("LC", "Saint Lucia", "1", None, false, 0),
+    country("LI", "Liechtenstein", "423", Some(r"^(94(8[5-9]|9[0-8]))$"), true, EEA),
+    country("LK", "Sri Lanka", "94", Some(r"^\d{5}$"), true, 0),
+    country("LR", "Liberia", "231", Some(r"^\d{4}$"), true, 0),
+    country("LS", "Lesotho", "266", Some(r"^\d{3}$"), true, 0),
+    country("LT", "Lithuania", "370", Some(r"^(LT-)?\d{5}$"), true, EU | EEA | CUSTOMS_UNION),
+    country("LU", "Luxembourg", "352", Some(r"^(L-)?\d{4}$"), true, EU | EEA | CUSTOMS_UNION),
+    country("LV", "Latvia", "371", Some(r"^(LV-)?\d{4}$"), true, EU | EEA | CUSTOMS_UNION),
+    country("LY", "Libya", "218", None, false, 0),
+    country("MA", "Morocco", "212", Some(r"^\d{5}$"), true, 0),
+    country("MC", "Monaco", "377", Some(r"^980\d{2}$"), true, CUSTOMS_UNION),
+    country("MD", "Moldova", "373", Some(r"^(MD-?)?\d{4}$"), true, 0),
+    country("ME", "Montenegro", "382", Some(r"^8\d{4}$"), true, 0),
+    country("MF", "Saint Martin (French part)", "590", Some(r"^97150$"), true, 0),
+    country("MG", "Madagascar", "261", Some(r"^\d{3}$"), true, 0),
+    country("MH", "Marshall Islands", "692", Some(r"^969[67]\d(-\d{4})?$"), true, 0),
+    country("MK", "North Macedonia", "389", Some(r"^\d{4}$"), true, 0),
+    country("ML", "Mali", "223", None, false, 0),
+    country("MM", "Myanmar", "95", Some(r"^\d{5}$"), true, 0),
+    country("MN", "Mongolia", "976", Some(r"^\d{5}$"), true, 0),
+    country("MO", "Macao", "853", None, false, 0),
+    country("MP", "Northern Mariana Islands", "1", Some(r"^9695[0-2](-\d{4})?$"), true, 0),
+    country("MQ", "Martinique", "596", Some(r"^972\d{2}$"), true, 0),
+    country("MR", "Mauritania", "222", None, false, 0),
+    country("MS", "Montserrat", "1", Some(r"^MSR ?\d{4}$"), true, 0),
+    country("MT", "Malta", "356", Some(r"^[A-Z]{3} ?\d{2,4}$"), true, EU | EEA | CUSTOMS_UNION),
+    country("MU", "Mauritius", "230", Some(r"^\d{5}$"), true, 0),
+    country("MV", "Maldives", "960", Some(r"^\d{5}$"), true, 0),
+    country("MW", "Malawi", "265", None, false, 0),
+    country("MX", "Mexico", "52", Some(r"^\d{5}$"), true, 0),
+    country("MY", "Malaysia", "60", Some(r"^\d{5}$"), true, 0),
+    country("MZ", "Mozambique", "258", Some(r"^\d{4}$"), true, 0),
+    country("NA", "Namibia", "264", None, false, 0),
+    country("NC", "New Caledonia", "687", Some(r"^988\d{2}$"), true, 0),
+    country("NE", "Niger", "227", Some(r"^\d{4}$"), true, 0),
+    country("NF", "Norfolk Island", "672", Some(r"^2899$"), true, 0),
+    country("NG", "Nigeria", "234", Some(r"^\d{6}$"), false, 0),
+    country("NI", "Nicaragua", "505", Some(r"^\d{5}$"), true, 0),
+    country("NL", "Netherlands", "31", Some(r"^\d{4} ?[A-Z]{2}$"), true, EU | EEA | CUSTOMS_UNION),
+    country("NO", "Norway", "47", Some(r"^\d{4}$"), true, EEA),
+    country("NP", "Nepal", "977", Some(r"^\d{5}$"), true, 0),
+    country("NR", "Nauru", "674", None, false, 0),
+    country("NU", "Niue", "683", None, false, 0),
+    country("NZ", "New Zealand", "64", Some(r"^\d{4}$"), true, 0),
+    country("OM", "Oman", "968", Some(r"^(PC ?)?\d{3}$"), true, 0),
+    country("PA", "Panama", "507", Some(r"^\d{4}$"), false, 0),
+    country("PE", "Peru", "51", Some(r"^\d{5}$"), true, 0),
+    country("PF", "French Polynesia", "689", Some(r"^987\d{2}$"), true, 0),
+    country("PG", "Papua New Guinea", "675", Some(r"^\d{3}$"), true, 0),
+    country("PH", "Philippines", "63", Some(r"^\d{4}$"), true, 0),
+    country("PK", "Pakistan", "92", Some(r"^\d{5}$"), true, 0),
+    country("PL", "Poland", "48", Some(r"^\d{2}-?\d{3}$"), true, EU | EEA | CUSTOMS_UNION),
+    country("PM", "Saint Pierre and Miquelon", "508", Some(r"^97500$"), true, 0),
+    country("PN", "Pitcairn", "64", Some(r"^PCRN ?1ZZ$"), true, 0),
+    country("PR", "Puerto Rico", "1", Some(r"^00[679]\d{2}(-\d{4})?$"), true, 0),
+    country("PS", "Palestine, State of", "970", None, false, 0),
+    country("PT", "Portugal", "351", Some(r"^\d{4}-?\d{3}$"), true, EU | EEA | CUSTOMS_UNION),
+    country("PW", "Palau", "680", Some(r"^96940(-\d{4})?$"), true, 0),
+    country("PY", "Paraguay", "595", Some(r"^\d{4}$"), true, 0),
+    country("QA", "Qatar", "974", None, false, 0),
+    country("RE", "Réunion", "262", Some(r"^974\d{2}$"), true, 0),
+    country("RO", "Romania", "40", Some(r"^\d{6}$"), true, EU | EEA | CUSTOMS_UNION),
+    country("RS", "Serbia", "381", Some(r"^\d{5}$"), true, 0),
+    country("RU", "Russian Federation", "7", Some(r"^\d{6}$"), true, 0),
+    country("RW", "Rwanda", "250", None, false, 0),
+    country("SA", "Saudi Arabia", "966", Some(r"^\d{5}(-\d{4})?$"), true, 0),
+    country("SB", "Solomon Islands", "677", None, false, 0),
+    country("SC", "Seychelles", "248", None, false, 0),
+    country("SD", "Sudan", "249", Some(r"^\d{5}$"), true, 0),
+    country("SE", "Sweden", "46", Some(r"^\d{3} ?\d{2}$"), true, EU | EEA | CUSTOMS_UNION),
+    country("SG", "Singapore", "65", Some(r"^\d{6}$"), true, 0),
+    country("SH", "Saint Helena, Ascension and Tristan da Cunha", "290", Some(r"^((ASCN|STHL|TDCU) ?1ZZ)$"), true, 0),
+    country("SI", "Slovenia", "386", Some(r"^(SI-)?\d{4}$"), true, EU | EEA | CUSTOMS_UNION),
+    country("SJ", "Svalbard and Jan Mayen", "47", Some(r"^\d{4}$"), true, 0),
+    country("SK", "Slovakia", "421", Some(r"^\d{3} ?\d{2}$"), true, EU | EEA | CUSTOMS_UNION),
+    country("SL", "Sierra Leone", "232", None, false, 0),
+    country("SM", "San Marino", "378", Some(r"^4789\d$"), true, CUSTOMS_UNION),
+    country("SN", "Senegal", "221", Some(r"^\d{5}$"), true, 0),
+    country("SO", "Somalia", "252", Some(r"^[A-Z]{2} ?\d{5}$"), false, 0),
+    country("SR", "Suriname", "597", None, false, 0),
+    country("SS", "South Sudan", "211", None, false, 0),
+    country("ST", "Sao Tome and Principe", "239", None, false, 0),
+    country("SV", "El Salvador", "503", Some(r"^\d{4}$"), false, 0),
+    country("SX", "Sint Maarten (Dutch part)", "1", None, false, 0),
+    country("SY", "Syrian Arab Republic", "963", None, false, 0),
+    country("SZ", "Eswatini", "268", Some(r"^[HLMS]\d{3}$"), true, 0),
+    country("TC", "Turks and Caicos Islands", "1", Some(r"^TKCA ?1ZZ$"), true, 0),
+    country("TD", "Chad", "235", None, false, 0),
+    country("TF", "French Southern Territories", "262", None, false, 0),
+    country("TG", "Togo", "228", None, false, 0),
+    country("TH", "Thailand", "66", Some(r"^\d{5}$"), true, 0),
+    country("TJ", "Tajikistan", "992", Some(r"^\d{6}$"), true, 0),
+    country("TK", "Tokelau", "690", None, false, 0),
+    country("TL", "Timor-Leste", "670", None, false, 0),
+    country("TM", "Turkmenistan", "993", Some(r"^\d{6}$"), true, 0),
+    country("TN", "Tunisia", "216", Some(r"^\d{4}$"), true, 0),
+    country("TO", "Tonga", "676", None, false, 0),
+    country("TR", "Türkiye", "90", Some(r"^\d{5}$"), true, 0),
+    country("TT", "Trinidad and Tobago", "1", Some(r"^\d{6}$"), false, 0),
+    country("TV", "Tuvalu", "688", None, false, 0),
+    country("TW", "Taiwan", "886", Some(r"^\d{3}(\d{2,3})?$"), true, 0),
+    country("TZ", "Tanzania", "255", Some(r"^\d{4,5}$"), false, 0),
+    country("UA", "Ukraine", "380", Some(r"^\d{5}$"), true, 0),
+    country("UG", "Uganda", "256", None, false, 0),
+    country("UM", "United States Minor Outlying Islands", "1", Some(r"^96898$"), true, 0),
+    country("US", "United States", "1", Some(r"^\d{5}(-\d{4})?$"), true, 0),
+    country("UY", "Uruguay", "598", Some(r"^\d{5}$"), true, 0),
+    country("UZ", "Uzbekistan", "998", Some(r"^\d{6}$"), true, 0),
+    country("VA", "Holy See", "39", Some(r"^00120$"), true, 0),
+    country("VC", "Saint Vincent and the Grenadines", "1", Some(r"^VC\d{4}$"), true, 0),
+    country("VE", "Venezuela", "58", Some(r"^\d{4}$"), true, 0),
+    country("VG", "Virgin Islands (British)", "1", Some(r"^VG\d{4}$"), true, 0),
+    country("VI", "Virgin Islands (U.S.)", "1", Some(r"^008\d{2}(-\d{4})?$"), true, 0),
+    country("VN", "Viet Nam", "84", Some(r"^\d{6}$"), true, 0),
+    country("VU", "Vanuatu", "678", None, false, 0),
+    country("WF", "Wallis and Futuna", "681", Some(r"^986\d{2}$"), true, 0),
+    country("WS", "Samoa", "685", None, false, 0),
+    country("YE", "Yemen", "967", None, false, 0),
+    country("YT", "Mayotte", "262", Some(r"^976\d{2}$"), true, 0),
+    country("ZA", "South Africa", "27", Some(r"^\d{4}$"), true, 0),
+    country("ZM", "Zambia", "260", Some(r"^\d{5}$"), true, 0),
+    country("ZW", "Zimbabwe", "263", None, false, 0),
+];
@@ -1,4 +1,6 @@
-use crate::models::{Address, ShipmentStatus};
+use crate::models::{Address, CarrierCode, ShipmentStatus, TrackingSubStatus};
+use crate::utils::address::AddressNormalizer;
+use crate::utils::{geo, status_mapping};
 
 /// Formate une adresse pour l'affichage
 pub fn format_address(address: &Address) -> String {
@@ -20,28 +22,84 @@ pub fn format_address(address: &Address) -> String {
         }
     }
 
-    let mut city_line = String::new();
-
-    if let Some(state) = &address.state {
-        city_line = format!("{} {} {}", address.postal_code, address.city, state);
-    } else {
-        city_line = format!("{} {}", address.postal_code, address.city);
+    let postal_code = geo::format_postal_code(&address.country, &address.postal_code);
+    let country = address.country.trim().to_uppercase();
+
+    match country.as_str() {
+        // Ville, état puis code postal sur la même ligne
+        "US" | "CA" | "AU" | "PR" | "GU" | "VI" | "AS" | "MP" => {
+            let mut city_line = address.city.clone();
+            if let Some(state) = address.state.as_ref().filter(|state| !state.trim().is_empty()) {
+                city_line = format!("{} {}", city_line, state);
+            }
+            lines.push(format!("{} {}", city_line, postal_code).trim().to_string());
+        }
+        // Ville puis code postal sur une ligne séparée
+        "GB" | "IE" | "GG" | "JE" | "IM" | "GI" | "FK" => {
+            lines.push(address.city.clone());
+            if !postal_code.is_empty() {
+                lines.push(postal_code);
+            }
+        }
+        _ => {
+            let city_line = match &address.state {
+                Some(state) => format!("{} {} {}", postal_code, address.city, state),
+                None => format!("{} {}", postal_code, address.city),
+            };
+            lines.push(city_line.trim().to_string());
+        }
     }
 
-    lines.push(city_line);
     lines.push(address.country.clone());
 
     lines.join("\n")
 }
 
+/// Formate une adresse selon les conventions postales du pays de destination
+/// (casse, espaces, code postal)
+///
+/// Voir `utils::address::AddressNormalizer` pour les règles appliquées.
+pub fn format_postal_address(address: &Address) -> String {
+    AddressNormalizer::new().format(address)
+}
+
 /// Formate un numéro de téléphone en format international
 pub fn format_phone(phone: &str, country: &str) -> String {
     match country {
         "FR" => format_phone_france(phone),
         "US" => format_phone_usa(phone),
-        // Ajouter d'autres pays au besoin
-        _ => phone.to_string(),
+        _ => format_phone_international(phone, country),
+    }
+}
+
+/// Formate un numéro de téléphone avec l'indicatif du pays issu de la table des pays
+fn format_phone_international(phone: &str, country: &str) -> String {
+    let Some(info) = geo::country_info(country) else {
+        return phone.to_string();
+    };
+
+    let trimmed = phone.trim();
+    let digits: String = trimmed.chars().filter(|c| c.is_ascii_digit()).collect();
+
+    if digits.is_empty() {
+        return phone.to_string();
+    }
+
+    if trimmed.starts_with('+') {
+        return format!("+{}", digits);
+    }
+
+    if let Some(international) = digits.strip_prefix("00") {
+        return format!("+{}", international);
     }
+
+    // Le 0 initial est conservé en Italie, à Saint-Marin et au Vatican
+    let national = match country.trim().to_uppercase().as_str() {
+        "IT" | "SM" | "VA" => digits.as_str(),
+        _ => digits.trim_start_matches('0'),
+    };
+
+    format!("+{} {}", info.phone_code, national)
 }
 
 /// Formate un numéro de téléphone français
@@ -101,88 +159,13 @@ fn format_phone_usa(phone: &str) -> String {
 }
 
 /// Standardise les codes de statut spécifiques aux transporteurs
-pub fn normalize_status(carrier_status: &str, carrier_code: crate::models::CarrierCode) -> ShipmentStatus {
-    match carrier_code {
-        crate::models::CarrierCode::Colissimo => normalize_status_colissimo(carrier_status),
-        crate::models::CarrierCode::Chronopost => normalize_status_chronopost(carrier_status),
-        crate::models::CarrierCode::FedEx => normalize_status_fedex(carrier_status),
-        crate::models::CarrierCode::UPS => normalize_status_ups(carrier_status),
-        crate::models::CarrierCode::DHL => normalize_status_dhl(carrier_status),
-    }
-}
-
-fn normalize_status_colissimo(status: &str) -> ShipmentStatus {
-    match status {
-        // Codes de base
-        "PRC" => ShipmentStatus::Pickup,
-        "PCH" => ShipmentStatus::Pickup,
-        "PDR" => ShipmentStatus::InTransit,
-        "PRES" => ShipmentStatus::OutForDelivery,
-        "LIV" => ShipmentStatus::Delivered,
-        "LIVRI" => ShipmentStatus::Delivered,
-        "ANOML" => ShipmentStatus::Exception,
-        "RET" => ShipmentStatus::Returned,
-
-        // Codes supplémentaires identifiés dans l'API réelle
-        "PCHMQT" => ShipmentStatus::Created,         // Colis en préparation
-        "LIVCFM" => ShipmentStatus::Delivered,       // Livraison confirmée
-        "AARBPR" => ShipmentStatus::OutForDelivery,  // Disponible en point de retrait
-        "PRELIV" => ShipmentStatus::OutForDelivery,  // En préparation pour la livraison
-        "PCHTRI" => ShipmentStatus::InTransit,       // En transit sur la plateforme
-
-        // Code par défaut
-        _ => ShipmentStatus::Unknown,
-    }
-}
-
-fn normalize_status_chronopost(status: &str) -> ShipmentStatus {
-    match status {
-        "A" => ShipmentStatus::Created,
-        "B" => ShipmentStatus::Pickup,
-        "C" | "D" | "E" => ShipmentStatus::InTransit,
-        "F" => ShipmentStatus::OutForDelivery,
-        "G" => ShipmentStatus::Delivered,
-        "H" => ShipmentStatus::Exception,
-        "I" => ShipmentStatus::Returned,
-        _ => ShipmentStatus::Unknown,
-    }
-}
-
-fn normalize_status_fedex(status: &str) -> ShipmentStatus {
-    match status {
-        "AA" => ShipmentStatus::Created,
-        "PU" => ShipmentStatus::Pickup,
-        "IT" => ShipmentStatus::InTransit,
-        "OD" => ShipmentStatus::OutForDelivery,
-        "DL" => ShipmentStatus::Delivered,
-        "DE" => ShipmentStatus::Exception,
-        "RT" => ShipmentStatus::Returned,
-        _ => ShipmentStatus::Unknown,
-    }
+///
+/// Les correspondances proviennent des tables de `utils::status_mapping`.
+pub fn normalize_status(carrier_status: &str, carrier_code: CarrierCode) -> ShipmentStatus {
+    status_mapping::resolve_status(carrier_code, carrier_status)
 }
 
-fn normalize_status_ups(status: &str) -> ShipmentStatus {
-    match status {
-        "M" => ShipmentStatus::Created,
-        "P" => ShipmentStatus::Pickup,
-        "I" => ShipmentStatus::InTransit,
-        "O" => ShipmentStatus::OutForDelivery,
-        "D" => ShipmentStatus::Delivered,
-        "X" => ShipmentStatus::Exception,
-        "R" => ShipmentStatus::Returned,
-        _ => ShipmentStatus::Unknown,
-    }
-}
-
-fn normalize_status_dhl(status: &str) -> ShipmentStatus {
-    match status {
-        "SD" => ShipmentStatus::Created,
-        "PU" => ShipmentStatus::Pickup,
-        "IT" => ShipmentStatus::InTransit,
-        "OD" => ShipmentStatus::OutForDelivery,
-        "DL" => ShipmentStatus::Delivered,
-        "EX" => ShipmentStatus::Exception,
-        "RT" => ShipmentStatus::Returned,
-        _ => ShipmentStatus::Unknown,
-    }
+/// Standardise un code de statut transporteur en sous-statut détaillé
+pub fn normalize_sub_status(carrier_status: &str, carrier_code: CarrierCode) -> TrackingSubStatus {
+    status_mapping::resolve_sub_status(carrier_code, carrier_status)
 }
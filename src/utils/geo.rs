@@ -1,4 +1,9 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::utils::countries::{COUNTRIES, CUSTOMS_UNION, EEA, EU};
 
 /// Structure pour contenir les informations sur un pays
 #[derive(Debug, Clone)]
@@ -7,18 +12,124 @@ pub struct CountryInfo {
     pub name: String,
     pub postal_code_format: Option<String>,
     pub phone_code: String,
+    pub postal_code_required: bool,
+    pub eu_member: bool,
+    pub eea_member: bool,
+    pub customs_union: bool,
+}
+
+/// Pays indexés par code ISO, construits une seule fois
+static COUNTRY_INDEX: LazyLock<BTreeMap<&'static str, CountryInfo>> = LazyLock::new(|| {
+    COUNTRIES
+        .iter()
+        .map(|record| {
+            let info = CountryInfo {
+                code: record.code.to_string(),
+                name: record.name.to_string(),
+                postal_code_format: record.postal_code_format.map(|format| format.to_string()),
+                phone_code: record.phone_code.to_string(),
+                postal_code_required: record.postal_code_required,
+                eu_member: record.flags & EU != 0,
+                eea_member: record.flags & EEA != 0,
+                customs_union: record.flags & CUSTOMS_UNION != 0,
+            };
+            (record.code, info)
+        })
+        .collect()
+});
+
+/// Formats de code postal compilés, construits une seule fois
+static POSTAL_CODE_PATTERNS: LazyLock<BTreeMap<&'static str, Regex>> = LazyLock::new(|| {
+    COUNTRIES
+        .iter()
+        .filter_map(|record| {
+            let format = record.postal_code_format?;
+            let regex = Regex::new(format).expect("format de code postal invalide dans la table des pays");
+            Some((record.code, regex))
+        })
+        .collect()
+});
+
+/// Obtient les informations d'un pays à partir de son code ISO à 2 lettres
+pub fn country_info(code: &str) -> Option<&'static CountryInfo> {
+    COUNTRY_INDEX.get(code.trim().to_uppercase().as_str())
+}
+
+/// Liste tous les pays connus, triés par code ISO
+pub fn all_countries() -> impl Iterator<Item = &'static CountryInfo> {
+    COUNTRY_INDEX.values()
 }
 
 /// Obtient le nom d'un pays à partir de son code ISO à 2 lettres
 pub fn country_name_from_code(code: &str) -> Option<String> {
-    let countries = get_countries();
-    countries.get(code).map(|country| country.name.clone())
+    country_info(code).map(|country| country.name.clone())
 }
 
 /// Vérifie si un code pays est valide
 pub fn is_valid_country_code(code: &str) -> bool {
-    let countries = get_countries();
-    countries.contains_key(code)
+    country_info(code).is_some()
+}
+
+/// Normalise un code postal pour la comparaison (majuscules, espaces simples)
+pub fn normalize_postal_code(postal_code: &str) -> String {
+    postal_code
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_uppercase()
+}
+
+/// Vérifie qu'un code postal respecte le format du pays
+///
+/// Les espaces et tirets parasites corrigés par `format_postal_code` sont admis
+/// (`75 001` en France). Retourne `true` pour les pays sans code postal ou sans
+/// format connu.
+pub fn is_valid_postal_code(country: &str, postal_code: &str) -> bool {
+    matches_postal_format(country, &format_postal_code(country, postal_code))
+}
+
+/// Vérifie qu'un code postal déjà normalisé respecte exactement le format du pays
+fn matches_postal_format(country: &str, postal_code: &str) -> bool {
+    match POSTAL_CODE_PATTERNS.get(country.trim().to_uppercase().as_str()) {
+        Some(pattern) => pattern.is_match(postal_code),
+        None => true,
+    }
+}
+
+/// Indique si le code postal est obligatoire pour un pays (vrai pour les pays inconnus)
+pub fn is_postal_code_required(country: &str) -> bool {
+    country_info(country).is_none_or(|info| info.postal_code_required)
+}
+
+/// Met un code postal valide sous sa forme d'écriture officielle (ex: `SW1A 1AA`, `75 001` → `75001`)
+pub fn format_postal_code(country: &str, postal_code: &str) -> String {
+    let normalized = normalize_postal_code(postal_code);
+    let compact: String = normalized.chars().filter(|c| !c.is_whitespace() && *c != '-').collect();
+    let country = country.trim().to_uppercase();
+
+    let split = |at: usize, separator: &str| {
+        if compact.len() > at && compact.is_char_boundary(at) {
+            format!("{}{}{}", &compact[..at], separator, &compact[at..])
+        } else {
+            compact.clone()
+        }
+    };
+
+    match country.as_str() {
+        // Code intérieur sur les 3 derniers caractères
+        "GB" | "GG" | "JE" | "IM" => split(compact.len().saturating_sub(3), " "),
+        "CA" | "IE" => split(3, " "),
+        "NL" => split(4, " "),
+        "CZ" | "SK" | "SE" | "GR" => split(3, " "),
+        "PL" => split(2, "-"),
+        "PT" => split(4, "-"),
+        "BR" => split(5, "-"),
+        "JP" => split(3, "-"),
+        _ if matches_postal_format(&country, &normalized) => normalized,
+        // Espaces ou tirets parasites dans un code à chiffres fixes (`75 001`, `10-115`)
+        _ if matches_postal_format(&country, &compact) => compact,
+        _ => postal_code.trim().to_string(),
+    }
 }
 
 /// Calcule la distance approximative entre deux points géographiques (en km)
@@ -46,105 +157,22 @@ pub fn is_domestic_shipping(sender_country: &str, recipient_country: &str) -> bo
 
 /// Vérifie si une livraison est européenne (UE)
 pub fn is_eu_shipping(sender_country: &str, recipient_country: &str) -> bool {
-    let eu_countries = get_eu_countries();
-    eu_countries.contains(&sender_country.to_uppercase().as_str()) &&
-    eu_countries.contains(&recipient_country.to_uppercase().as_str())
+    let is_eu = |code: &str| country_info(code).is_some_and(|country| country.eu_member);
+    is_eu(sender_country) && is_eu(recipient_country)
 }
 
-/// Obtient la liste des pays de l'UE
-fn get_eu_countries() -> Vec<&'static str> {
-    vec![
-        "AT", "BE", "BG", "HR", "CY", "CZ", "DK", "EE", "FI", "FR",
-        "DE", "GR", "HU", "IE", "IT", "LV", "LT", "LU", "MT", "NL",
-        "PL", "PT", "RO", "SK", "SI", "ES", "SE"
-    ]
+/// Vérifie si une livraison reste dans l'Espace économique européen
+pub fn is_eea_shipping(sender_country: &str, recipient_country: &str) -> bool {
+    let is_eea = |code: &str| country_info(code).is_some_and(|country| country.eea_member);
+    is_eea(sender_country) && is_eea(recipient_country)
 }
 
-/// Obtient la carte des informations sur les pays
-fn get_countries() -> HashMap<&'static str, CountryInfo> {
-    let mut countries = HashMap::new();
-
-    countries.insert(
-        "FR",
-        CountryInfo {
-            code: "FR".to_string(),
-            name: "France".to_string(),
-            postal_code_format: Some(r"^\d{5}$".to_string()),
-            phone_code: "33".to_string(),
-        },
-    );
-
-    countries.insert(
-        "US",
-        CountryInfo {
-            code: "US".to_string(),
-            name: "United States".to_string(),
-            postal_code_format: Some(r"^\d{5}(-\d{4})?$".to_string()),
-            phone_code: "1".to_string(),
-        },
-    );
-
-    countries.insert(
-        "GB",
-        CountryInfo {
-            code: "GB".to_string(),
-            name: "United Kingdom".to_string(),
-            postal_code_format: Some(r"^[A-Z]{1,2}\d[A-Z\d]? \d[A-Z]{2}$".to_string()),
-            phone_code: "44".to_string(),
-        },
-    );
-
-    countries.insert(
-        "DE",
-        CountryInfo {
-            code: "DE".to_string(),
-            name: "Germany".to_string(),
-            postal_code_format: Some(r"^\d{5}$".to_string()),
-            phone_code: "49".to_string(),
-        },
-    );
-
-    countries.insert(
-        "IT",
-        CountryInfo {
-            code: "IT".to_string(),
-            name: "Italy".to_string(),
-            postal_code_format: Some(r"^\d{5}$".to_string()),
-            phone_code: "39".to_string(),
-        },
-    );
-
-    countries.insert(
-        "ES",
-        CountryInfo {
-            code: "ES".to_string(),
-            name: "Spain".to_string(),
-            postal_code_format: Some(r"^\d{5}$".to_string()),
-            phone_code: "34".to_string(),
-        },
-    );
-
-    countries.insert(
-        "BE",
-        CountryInfo {
-            code: "BE".to_string(),
-            name: "Belgium".to_string(),
-            postal_code_format: Some(r"^\d{4}$".to_string()),
-            phone_code: "32".to_string(),
-        },
-    );
-
-    countries.insert(
-        "CA",
-        CountryInfo {
-            code: "CA".to_string(),
-            name: "Canada".to_string(),
-            postal_code_format: Some(r"^[A-Z]\d[A-Z] \d[A-Z]\d$".to_string()),
-            phone_code: "1".to_string(),
-        },
-    );
-
-    // Ajoutez d'autres pays selon les besoins...
-
-    countries
+/// Vérifie si une livraison nécessite une déclaration en douane
+pub fn requires_customs_declaration(sender_country: &str, recipient_country: &str) -> bool {
+    if sender_country.trim().eq_ignore_ascii_case(recipient_country.trim()) {
+        return false;
+    }
+
+    let in_union = |code: &str| country_info(code).is_some_and(|country| country.customs_union);
+    !(in_union(sender_country) && in_union(recipient_country))
 }
@@ -1,31 +1,23 @@
 pub mod validation;
 pub mod formatting;
 pub mod geo;
+pub mod weight;
+pub mod status_mapping;
+pub mod catalog;
+pub mod calendar;
+pub mod tracking;
+pub mod address;
+mod countries;
 
 /// Utilitaires généraux
 pub mod general {
     use crate::models::CarrierCode;
 
     /// Détecte le transporteur probable à partir d'un numéro de suivi
+    ///
+    /// Voir `utils::tracking::detect_carriers` pour l'ensemble des candidats.
     pub fn detect_carrier_from_tracking(tracking: &str) -> Option<CarrierCode> {
-        // Exemples de formats de numéros de suivi (simplifiés)
-        if tracking.len() == 13 && tracking.starts_with("1Z") {
-            return Some(CarrierCode::UPS);
-        }
-
-        if tracking.len() == 12 && tracking.chars().all(|c| c.is_numeric()) {
-            return Some(CarrierCode::FedEx);
-        }
-
-        if tracking.len() == 13 && tracking.ends_with("FR") {
-            return Some(CarrierCode::Colissimo);
-        }
-
-        if tracking.len() == 10 && tracking.starts_with("CH") {
-            return Some(CarrierCode::Chronopost);
-        }
-
-        None
+        crate::utils::tracking::detect_carrier(tracking)
     }
 }
 
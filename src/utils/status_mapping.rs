@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex, PoisonError};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::errors::DeliveryError;
+use crate::models::{CarrierCode, ShipmentStatus, TrackingSubStatus};
+use crate::utils::catalog::{ActiveCatalog, CatalogTables, YamlCatalog};
+
+/// Tables de correspondance des codes de statut des transporteurs
+///
+/// Chaque code brut est associé à un sous-statut, dont découle le statut normalisé.
+/// Document YAML : une table par transporteur, `Colissimo: { LIV: Delivered, ... }`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct StatusMapping {
+    tables: CatalogTables<CarrierCode, TrackingSubStatus>,
+}
+
+/// Les codes sont comparés sans tenir compte de la casse ni des espaces
+fn normalize_code(code: &str) -> String {
+    code.trim().to_uppercase()
+}
+
+impl YamlCatalog for StatusMapping {
+    const NAME: &'static str = "Table de statuts";
+    const EMBEDDED: &'static str = include_str!("status_codes.yaml");
+
+    fn from_yaml(yaml: &str) -> Result<Self, DeliveryError> {
+        Ok(Self { tables: CatalogTables::from_yaml(yaml, Self::NAME, normalize_code)? })
+    }
+
+    fn with_overrides(mut self, overrides: StatusMapping) -> Self {
+        self.tables.extend(overrides.tables);
+        self
+    }
+}
+
+impl StatusMapping {
+    /// Crée des tables vides
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ajoute ou remplace un code
+    pub fn with_code(mut self, carrier: CarrierCode, code: &str, sub_status: TrackingSubStatus) -> Self {
+        self.tables.insert(carrier, normalize_code(code), sub_status);
+        self
+    }
+
+    /// Sous-statut associé à un code, s'il figure dans les tables
+    pub fn lookup(&self, carrier: CarrierCode, code: &str) -> Option<TrackingSubStatus> {
+        self.tables.get(&carrier, &normalize_code(code)).copied()
+    }
+
+    /// Codes connus d'un transporteur, triés
+    pub fn codes(&self, carrier: CarrierCode) -> Vec<(String, TrackingSubStatus)> {
+        let mut codes: Vec<(String, TrackingSubStatus)> = self.tables.group(&carrier)
+            .map(|codes| codes.iter().map(|(code, sub_status)| (code.clone(), *sub_status)).collect())
+            .unwrap_or_default();
+
+        codes.sort_by(|a, b| a.0.cmp(&b.0));
+        codes
+    }
+
+    /// Document YAML des tables, pour servir de base à un fichier de surcharge
+    pub fn to_yaml(&self) -> Result<String, DeliveryError> {
+        serde_yaml::to_string(self)
+            .map_err(|e| DeliveryError::SerializationError(format!("Table de statuts YAML: {}", e)))
+    }
+}
+
+/// Tables utilisées par la normalisation (par défaut, les tables intégrées)
+static ACTIVE_MAPPING: ActiveCatalog<StatusMapping> = ActiveCatalog::new();
+
+/// Remplace les tables utilisées par la normalisation
+///
+/// Pour ne surcharger que quelques codes :
+/// `set_status_mapping(StatusMapping::embedded().with_overrides_file("statuts.yaml")?)`.
+pub fn set_status_mapping(mapping: StatusMapping) {
+    ACTIVE_MAPPING.set(mapping);
+}
+
+/// Copie des tables utilisées par la normalisation
+pub fn status_mapping() -> StatusMapping {
+    ACTIVE_MAPPING.get()
+}
+
+/// Code de statut transporteur rencontré sans correspondance dans les tables
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnmappedStatus {
+    pub carrier: CarrierCode,
+    pub raw_status: String,
+    pub occurrences: u64,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+}
+
+/// Nombre maximal de codes non reconnus conservés ; le moins récemment vu est oublié au-delà
+const MAX_UNMAPPED_STATUSES: usize = 500;
+
+/// Codes non reconnus depuis le démarrage, par transporteur et code normalisé
+static UNMAPPED_STATUSES: LazyLock<Mutex<HashMap<(CarrierCode, String), UnmappedStatus>>> = LazyLock::new(Default::default);
+
+/// Sous-statut d'un code transporteur d'après les tables actives
+///
+/// Les codes sans correspondance sont journalisés et conservés pour
+/// `unmapped_status_codes`, afin de compléter les tables.
+pub fn resolve_sub_status(carrier: CarrierCode, raw_status: &str) -> TrackingSubStatus {
+    let sub_status = ACTIVE_MAPPING.read().lookup(carrier, raw_status);
+
+    sub_status.unwrap_or_else(|| {
+        report_unmapped(carrier, raw_status);
+        TrackingSubStatus::Unknown
+    })
+}
+
+/// Statut normalisé d'un code transporteur d'après les tables actives
+pub fn resolve_status(carrier: CarrierCode, raw_status: &str) -> ShipmentStatus {
+    resolve_sub_status(carrier, raw_status).status()
+}
+
+/// Codes de statut rencontrés sans correspondance, du plus fréquent au moins fréquent
+pub fn unmapped_status_codes() -> Vec<UnmappedStatus> {
+    let unmapped = UNMAPPED_STATUSES.lock().unwrap_or_else(PoisonError::into_inner);
+    let mut codes: Vec<UnmappedStatus> = unmapped.values().cloned().collect();
+
+    codes.sort_by(|a, b| b.occurrences.cmp(&a.occurrences).then_with(|| a.first_seen.cmp(&b.first_seen)));
+    codes
+}
+
+/// Oublie les codes non reconnus déjà signalés
+pub fn clear_unmapped_status_codes() {
+    UNMAPPED_STATUSES.lock().unwrap_or_else(PoisonError::into_inner).clear();
+}
+
+fn report_unmapped(carrier: CarrierCode, raw_status: &str) {
+    let now = Utc::now();
+    let mut unmapped = UNMAPPED_STATUSES.lock().unwrap_or_else(PoisonError::into_inner);
+    let key = (carrier, normalize_code(raw_status));
+
+    if !unmapped.contains_key(&key) && unmapped.len() >= MAX_UNMAPPED_STATUSES {
+        let oldest = unmapped.iter()
+            .min_by_key(|(_, status)| status.last_seen)
+            .map(|(key, _)| key.clone());
+        if let Some(oldest) = oldest {
+            unmapped.remove(&oldest);
+        }
+    }
+
+    let entry = unmapped.entry(key).or_insert_with(|| {
+        log::warn!("Code de statut {} non reconnu: {:?}", carrier, raw_status);
+        UnmappedStatus { carrier, raw_status: raw_status.to_string(), occurrences: 0, first_seen: now, last_seen: now }
+    });
+    entry.occurrences += 1;
+    entry.last_seen = now;
+}
@@ -0,0 +1,231 @@
+use crate::models::CarrierCode;
+
+/// Format de numéro de suivi reconnu
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TrackingFormat {
+    Ups1Z,  // 1Z + 15 caractères + clé mod 10
+    FedExExpress,  // 12 chiffres, clé mod 11
+    FedExGround,  // 15 chiffres, clé mod 10
+    FedExGround96,  // 22 chiffres commençant par 96
+    FedExSmartPost,  // 22 chiffres commençant par 92, remis par l'USPS
+    Sscc,  // 20 chiffres commençant par 00 (SSCC GS1)
+    UpuS10,  // Format international UPU S10 : 2 lettres, 8 chiffres, clé mod 11, pays
+    ColissimoDomestic,  // 1 chiffre, 1 lettre, 11 chiffres
+    ChronopostLegacy,  // CH + 8 caractères
+    DhlExpress,  // 10 chiffres, clé mod 7
+    DhlPaket,  // 12 chiffres, clé pondérée 4/9
+    DhlParcel,  // JJD + chiffres
+    DhlEcommerce,  // GM + chiffres
+}
+
+/// Transporteur possible pour un numéro de suivi
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackingCandidate {
+    pub carrier: CarrierCode,
+    pub format: TrackingFormat,
+    pub confidence: f64,  // 0 à 1
+    pub checksum_verified: bool,  // Clé de contrôle vérifiée (sinon structure seule)
+}
+
+/// Supprime espaces et tirets et passe en majuscules
+pub fn normalize_tracking_number(tracking: &str) -> String {
+    tracking.chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .collect::<String>()
+        .to_uppercase()
+}
+
+fn digits(value: &str) -> Option<Vec<u32>> {
+    value.chars().map(|c| c.to_digit(10)).collect()
+}
+
+/// Clé GS1 (SSCC, FedEx Ground) : pondération 3/1 depuis la droite
+fn gs1_check_digit(payload: &[u32]) -> u32 {
+    let sum: u32 = payload.iter().rev().enumerate()
+        .map(|(index, digit)| if index % 2 == 0 { digit * 3 } else { *digit })
+        .sum();
+    (10 - sum % 10) % 10
+}
+
+/// Vérifie une chaîne de chiffres dont le dernier est une clé GS1
+fn gs1_valid(value: &str) -> bool {
+    digits(value).is_some_and(|digits| {
+        digits.split_last().is_some_and(|(check, payload)| gs1_check_digit(payload) == *check)
+    })
+}
+
+/// UPS 1Z : les lettres valent (code ASCII - 63) mod 10, rangs pairs doublés
+fn ups_valid(tracking: &str) -> bool {
+    let bytes = tracking.as_bytes();
+    if bytes.len() != 18 || !tracking.starts_with("1Z") || !bytes[2..].iter().all(u8::is_ascii_alphanumeric) {
+        return false;
+    }
+
+    let sum: u32 = bytes[2..17].iter().enumerate()
+        .map(|(index, byte)| {
+            let value = if byte.is_ascii_digit() { u32::from(byte - b'0') } else { u32::from(byte - 63) % 10 };
+            if index % 2 == 1 { value * 2 } else { value }
+        })
+        .sum();
+
+    bytes[17].is_ascii_digit() && (10 - sum % 10) % 10 == u32::from(bytes[17] - b'0')
+}
+
+/// FedEx Express 12 chiffres : pondération 3/1/7, reste de la division par 11
+fn fedex_express_valid(tracking: &str) -> bool {
+    let Some(digits) = digits(tracking).filter(|digits| digits.len() == 12) else {
+        return false;
+    };
+
+    let sum: u32 = digits[..11].iter().zip([3, 1, 7].iter().cycle()).map(|(digit, weight)| digit * weight).sum();
+    sum % 11 % 10 == digits[11]
+}
+
+/// UPU S10 : pondération 8/6/4/2/3/5/9/7, clé 11 - reste (10 → 0, 11 → 5)
+fn s10_valid(tracking: &str) -> bool {
+    let bytes = tracking.as_bytes();
+    if bytes.len() != 13
+        || !bytes[..2].iter().all(u8::is_ascii_uppercase)
+        || !bytes[11..].iter().all(u8::is_ascii_uppercase) {
+        return false;
+    }
+    let Some(digits) = digits(&tracking[2..11]) else {
+        return false;
+    };
+
+    let sum: u32 = digits[..8].iter().zip([8, 6, 4, 2, 3, 5, 9, 7]).map(|(digit, weight)| digit * weight).sum();
+    let check = match 11 - sum % 11 {
+        10 => 0,
+        11 => 5,
+        check => check,
+    };
+    check == digits[8]
+}
+
+/// DHL Express 10 chiffres : les 9 premiers modulo 7
+fn dhl_express_valid(tracking: &str) -> bool {
+    tracking.len() == 10
+        && tracking.bytes().all(|byte| byte.is_ascii_digit())
+        && tracking[..9].parse::<u64>().is_ok_and(|number| number % 7 == u64::from(tracking.as_bytes()[9] - b'0'))
+}
+
+/// DHL Paket 12 chiffres : pondération 4/9 depuis la gauche
+fn dhl_paket_valid(tracking: &str) -> bool {
+    let Some(digits) = digits(tracking).filter(|digits| digits.len() == 12) else {
+        return false;
+    };
+
+    let sum: u32 = digits[..11].iter().zip([4, 9].iter().cycle()).map(|(digit, weight)| digit * weight).sum();
+    (10 - sum % 10) % 10 == digits[11]
+}
+
+/// Indique si un numéro a la structure d'un format à clé de contrôle (UPS 1Z,
+/// UPU S10, formats numériques) sans qu'aucune clé ne soit correcte
+pub(crate) fn has_invalid_check_digit(tracking: &str) -> bool {
+    let tracking = normalize_tracking_number(tracking);
+    let bytes = tracking.as_bytes();
+
+    let ups = bytes.len() == 18 && tracking.starts_with("1Z") && bytes[2..].iter().all(u8::is_ascii_alphanumeric);
+    let s10 = bytes.len() == 13
+        && bytes[..2].iter().all(u8::is_ascii_uppercase)
+        && bytes[2..11].iter().all(u8::is_ascii_digit)
+        && bytes[11..].iter().all(u8::is_ascii_uppercase);
+    let numeric = bytes.iter().all(u8::is_ascii_digit) && [10, 12, 15, 20, 22].contains(&bytes.len());
+
+    (ups || s10 || numeric)
+        && !s10_valid(&tracking)
+        && !detect_carriers(&tracking).iter().any(|candidate| candidate.checksum_verified)
+}
+
+/// Transporteurs possibles pour un numéro de suivi, du plus probable au moins probable
+///
+/// Les formats à clé de contrôle ne sont retenus que si la clé est correcte ; un
+/// numéro invalide ou inconnu ne donne aucun candidat.
+pub fn detect_carriers(tracking: &str) -> Vec<TrackingCandidate> {
+    let tracking = normalize_tracking_number(tracking);
+    let all_digits = !tracking.is_empty() && tracking.bytes().all(|byte| byte.is_ascii_digit());
+    let mut candidates = Vec::new();
+    let mut push = |carrier, format, confidence, checksum_verified| {
+        candidates.push(TrackingCandidate { carrier, format, confidence, checksum_verified });
+    };
+
+    if ups_valid(&tracking) {
+        push(CarrierCode::UPS, TrackingFormat::Ups1Z, 0.99, true);
+    }
+
+    if s10_valid(&tracking) {
+        // L'opérateur est désigné par le code pays, le service par la première lettre
+        match (&tracking[11..], tracking.as_bytes()[0]) {
+            ("FR", b'E' | b'X') => push(CarrierCode::Chronopost, TrackingFormat::UpuS10, 0.9, true),
+            ("FR", _) => push(CarrierCode::Colissimo, TrackingFormat::UpuS10, 0.9, true),
+            ("DE", _) => push(CarrierCode::DHL, TrackingFormat::UpuS10, 0.85, true),
+            _ => {}
+        }
+    }
+
+    if tracking.len() == 13 {
+        let bytes = tracking.as_bytes();
+        if bytes[0].is_ascii_digit() && bytes[1].is_ascii_uppercase() && bytes[2..].iter().all(u8::is_ascii_digit) {
+            push(CarrierCode::Colissimo, TrackingFormat::ColissimoDomestic, 0.8, false);
+        }
+    }
+
+    if tracking.len() == 10 && tracking.starts_with("CH") && tracking[2..].bytes().all(|byte| byte.is_ascii_alphanumeric()) {
+        push(CarrierCode::Chronopost, TrackingFormat::ChronopostLegacy, 0.5, false);
+    }
+
+    if all_digits {
+        match tracking.len() {
+            10 if dhl_express_valid(&tracking) => push(CarrierCode::DHL, TrackingFormat::DhlExpress, 0.9, true),
+            12 => {
+                if fedex_express_valid(&tracking) {
+                    push(CarrierCode::FedEx, TrackingFormat::FedExExpress, 0.85, true);
+                }
+                if dhl_paket_valid(&tracking) {
+                    push(CarrierCode::DHL, TrackingFormat::DhlPaket, 0.6, true);
+                }
+            }
+            15 if gs1_valid(&tracking) => push(CarrierCode::FedEx, TrackingFormat::FedExGround, 0.85, true),
+            20 if tracking.starts_with("00") && gs1_valid(&tracking[2..]) => {
+                // SSCC : chiffre d'extension puis préfixe GS1 340-440 pour l'Allemagne, utilisé par DHL Paket
+                let german = tracking[3..6].parse::<u32>().is_ok_and(|prefix| (400..=440).contains(&prefix) || prefix == 340);
+                push(CarrierCode::DHL, TrackingFormat::Sscc, if german { 0.8 } else { 0.5 }, true);
+                push(CarrierCode::FedEx, TrackingFormat::Sscc, if german { 0.4 } else { 0.5 }, true);
+            }
+            22 if tracking.starts_with("96") && gs1_valid(&tracking[7..]) => {
+                push(CarrierCode::FedEx, TrackingFormat::FedExGround96, 0.9, true);
+            }
+            22 if tracking.starts_with("92") && gs1_valid(&tracking) => {
+                push(CarrierCode::FedEx, TrackingFormat::FedExSmartPost, 0.6, true);
+            }
+            _ => {}
+        }
+    }
+
+    if tracking.starts_with("JJD") && tracking.len() >= 18 && tracking[3..].bytes().all(|byte| byte.is_ascii_digit()) {
+        push(CarrierCode::DHL, TrackingFormat::DhlParcel, 0.85, false);
+    }
+
+    if tracking.starts_with("GM") && (18..=24).contains(&tracking.len()) && tracking[2..].bytes().all(|byte| byte.is_ascii_digit()) {
+        push(CarrierCode::DHL, TrackingFormat::DhlEcommerce, 0.8, false);
+    }
+
+    candidates.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+    candidates
+}
+
+/// Transporteur le plus probable pour un numéro de suivi
+pub fn detect_carrier(tracking: &str) -> Option<CarrierCode> {
+    detect_carriers(tracking).first().map(|candidate| candidate.carrier)
+}
+
+/// Indique si un numéro de suivi est valide, pour un transporteur donné ou pour l'un d'eux
+///
+/// Sans transporteur, un numéro UPU S10 bien formé est valide même si son pays
+/// d'origine ne correspond à aucun transporteur géré.
+pub fn is_valid_tracking_number(tracking: &str, carrier: Option<CarrierCode>) -> bool {
+    match carrier {
+        Some(carrier) => detect_carriers(tracking).iter().any(|candidate| candidate.carrier == carrier),
+        None => s10_valid(&normalize_tracking_number(tracking)) || !detect_carriers(tracking).is_empty(),
+    }
+}
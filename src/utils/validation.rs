@@ -1,150 +1,403 @@
-use crate::models::{Address, Parcel};
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::constraints::ConstraintViolation;
+use crate::models::{Address, CarrierCode, Parcel, PickupRequest, Rate};
 use crate::errors::DeliveryError;
+use crate::utils::calendar::BusinessCalendar;
+use crate::utils::{geo, tracking};
 
-/// Valide une adresse postale
-pub fn validate_address(address: &Address) -> Result<(), DeliveryError> {
-    // Vérifie que les champs obligatoires sont remplis
-    if address.name.trim().is_empty() {
-        return Err(DeliveryError::InvalidAddress("Le nom est obligatoire".to_string()));
+/// Gravité d'un problème de validation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// Problème non bloquant, à signaler à l'utilisateur
+    Warning,
+    /// Problème bloquant l'expédition
+    Error,
+}
+
+/// Problème de validation rattaché à un champ
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidationIssue {
+    /// Chemin du champ concerné (ex: `recipient.postal_code`)
+    pub field: String,
+    /// Code machine du problème (ex: `required`, `invalid_format`)
+    pub code: String,
+    pub severity: Severity,
+    /// Message lisible dans la langue par défaut de la bibliothèque
+    pub message: String,
+    /// Valeurs utilisées dans le message, pour le traduire
+    #[serde(default)]
+    pub params: BTreeMap<String, String>,
+}
+
+impl ValidationIssue {
+    /// Crée un problème bloquant
+    pub fn error(field: &str, code: &str, message: &str) -> Self {
+        Self {
+            field: field.to_string(),
+            code: code.to_string(),
+            severity: Severity::Error,
+            message: message.to_string(),
+            params: BTreeMap::new(),
+        }
     }
 
-    if address.street1.trim().is_empty() {
-        return Err(DeliveryError::InvalidAddress("L'adresse est obligatoire".to_string()));
+    /// Crée un problème non bloquant
+    pub fn warning(field: &str, code: &str, message: &str) -> Self {
+        Self {
+            severity: Severity::Warning,
+            ..Self::error(field, code, message)
+        }
     }
 
-    if address.city.trim().is_empty() {
-        return Err(DeliveryError::InvalidAddress("La ville est obligatoire".to_string()));
+    /// Ajoute une valeur utilisée dans le message
+    pub fn with_param(mut self, name: &str, value: impl ToString) -> Self {
+        self.params.insert(name.to_string(), value.to_string());
+        self
     }
+}
 
-    if address.postal_code.trim().is_empty() {
-        return Err(DeliveryError::InvalidAddress("Le code postal est obligatoire".to_string()));
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
     }
+}
 
-    if address.country.trim().is_empty() {
-        return Err(DeliveryError::InvalidAddress("Le pays est obligatoire".to_string()));
+impl From<&ConstraintViolation> for ValidationIssue {
+    fn from(violation: &ConstraintViolation) -> Self {
+        let mut issue = ValidationIssue::error(&violation.field, &violation.code.to_string(), &violation.message)
+            .with_param("carrier", violation.carrier);
+
+        if let Some(limit) = violation.limit {
+            issue = issue.with_param("limit", limit);
+        }
+        if let Some(actual) = violation.actual {
+            issue = issue.with_param("actual", actual);
+        }
+
+        issue
     }
+}
 
-    // Règles de validation spécifiques par pays
-    validate_country_specific(address)
+/// Rapport regroupant tous les problèmes de validation constatés
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ValidationReport {
+    pub issues: Vec<ValidationIssue>,
 }
 
-/// Validations spécifiques par pays
-fn validate_country_specific(address: &Address) -> Result<(), DeliveryError> {
-    match address.country.as_str() {
-        "FR" => validate_address_france(address),
-        "US" => validate_address_usa(address),
-        // Ajouter d'autres pays au besoin
-        _ => Ok(()),  // Pas de validation spécifique pour les autres pays
+impl ValidationReport {
+    /// Crée un rapport vide
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ajoute un problème au rapport
+    pub fn push(&mut self, issue: ValidationIssue) {
+        self.issues.push(issue);
+    }
+
+    /// Ajoute les problèmes d'un autre rapport en préfixant leurs champs (ex: `recipient`)
+    pub fn merge_prefixed(&mut self, prefix: &str, other: ValidationReport) {
+        self.issues.extend(other.issues.into_iter().map(|mut issue| {
+            issue.field = format!("{}.{}", prefix, issue.field);
+            issue
+        }));
+    }
+
+    /// Ajoute les problèmes d'un autre rapport tels quels
+    pub fn merge(&mut self, other: ValidationReport) {
+        self.issues.extend(other.issues);
+    }
+
+    /// Construit un rapport à partir de violations de contraintes transporteur
+    pub fn from_violations(violations: &[ConstraintViolation]) -> Self {
+        Self {
+            issues: violations.iter().map(ValidationIssue::from).collect(),
+        }
+    }
+
+    /// Indique si le rapport ne contient aucun problème bloquant
+    pub fn is_valid(&self) -> bool {
+        self.errors().next().is_none()
+    }
+
+    /// Indique si le rapport est entièrement vide
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Problèmes bloquants
+    pub fn errors(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues.iter().filter(|issue| issue.severity == Severity::Error)
+    }
+
+    /// Problèmes non bloquants
+    pub fn warnings(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues.iter().filter(|issue| issue.severity == Severity::Warning)
+    }
+
+    /// Problèmes concernant un champ donné
+    pub fn issues_for<'a>(&'a self, field: &'a str) -> impl Iterator<Item = &'a ValidationIssue> {
+        self.issues.iter().filter(move |issue| issue.field == field)
+    }
+
+    /// Convertit le rapport en erreur s'il contient des problèmes bloquants,
+    /// sinon le retourne avec ses éventuels avertissements
+    pub fn into_result(self) -> Result<Self, DeliveryError> {
+        if self.is_valid() {
+            Ok(self)
+        } else {
+            Err(DeliveryError::Validation(self))
+        }
     }
 }
 
-/// Validations spécifiques pour la France
-fn validate_address_france(address: &Address) -> Result<(), DeliveryError> {
-    // Valide le format du code postal français (5 chiffres)
-    if !address.postal_code.chars().all(char::is_numeric) || address.postal_code.len() != 5 {
-        return Err(DeliveryError::InvalidAddress(
-            "Le code postal français doit contenir 5 chiffres".to_string()
-        ));
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let messages: Vec<String> = self.issues.iter().map(|issue| issue.to_string()).collect();
+        write!(f, "{}", messages.join("; "))
     }
+}
 
-    // Vérifie que le numéro de téléphone est au format français si présent
-    if let Some(phone) = &address.phone {
-        if !phone.starts_with("+33") && !phone.starts_with("0") {
-            return Err(DeliveryError::InvalidAddress(
-                "Le numéro de téléphone français doit commencer par +33 ou 0".to_string()
-            ));
+/// Valide une adresse postale et retourne tous les problèmes constatés
+///
+/// Les champs du rapport sont relatifs à l'adresse (ex: `postal_code`).
+pub fn validate_address(address: &Address) -> ValidationReport {
+    let mut report = ValidationReport::new();
+
+    // Vérifie que les champs obligatoires sont remplis
+    let required = [
+        ("name", &address.name, "Le nom est obligatoire"),
+        ("street1", &address.street1, "L'adresse est obligatoire"),
+        ("city", &address.city, "La ville est obligatoire"),
+        ("country", &address.country, "Le pays est obligatoire"),
+    ];
+
+    for (field, value, message) in required {
+        if value.trim().is_empty() {
+            report.push(ValidationIssue::error(field, "required", message));
         }
     }
 
-    Ok(())
+    if address.country.trim().is_empty() {
+        return report;
+    }
+
+    // Règles communes à tous les pays, issues de la table des pays
+    validate_country_rules(address, &mut report);
+
+    // Règles de validation spécifiques par pays
+    validate_country_specific(address, &mut report);
+
+    report
 }
 
-/// Validations spécifiques pour les États-Unis
-fn validate_address_usa(address: &Address) -> Result<(), DeliveryError> {
-    // Vérifie que l'état est présent (obligatoire aux USA)
-    if address.state.is_none() || address.state.as_ref().unwrap().trim().is_empty() {
-        return Err(DeliveryError::InvalidAddress(
-            "L'état est obligatoire pour les adresses aux États-Unis".to_string()
-        ));
+/// Vérifie le code pays et le code postal à partir de la table des pays
+fn validate_country_rules(address: &Address, report: &mut ValidationReport) {
+    let Some(country) = geo::country_info(&address.country) else {
+        report.push(
+            ValidationIssue::error("country", "invalid_country", "Le code pays doit être un code ISO 3166-1 à 2 lettres")
+                .with_param("country", &address.country)
+        );
+        return;
+    };
+
+    let postal = address.postal_code.trim();
+
+    if postal.is_empty() {
+        if country.postal_code_required {
+            report.push(
+                ValidationIssue::error("postal_code", "required", "Le code postal est obligatoire")
+                    .with_param("country", &country.name)
+            );
+        }
+        return;
     }
 
-    // Valide le format du code postal américain (5 chiffres ou 5+4)
-    let postal = &address.postal_code;
-    let is_valid = (postal.len() == 5 && postal.chars().all(char::is_numeric)) ||
-                   (postal.len() == 10 && postal.chars().enumerate().all(|(i, c)| {
-                       (i != 5 && c.is_numeric()) || (i == 5 && c == '-')
-                   }));
+    if !geo::is_valid_postal_code(&country.code, postal) {
+        report.push(
+            ValidationIssue::error(
+                "postal_code",
+                "invalid_format",
+                &format!("Le code postal n'est pas au format attendu pour le pays {}", country.name),
+            )
+            .with_param("country", &country.name)
+            .with_param("format", country.postal_code_format.as_deref().unwrap_or_default())
+        );
+    }
+}
+
+/// Validations spécifiques par pays
+fn validate_country_specific(address: &Address, report: &mut ValidationReport) {
+    match address.country.trim().to_uppercase().as_str() {
+        "FR" => validate_address_france(address, report),
+        "US" => validate_address_usa(address, report),
+        // Ajouter d'autres pays au besoin
+        _ => {}  // Pas de validation spécifique pour les autres pays
+    }
+}
 
-    if !is_valid {
-        return Err(DeliveryError::InvalidAddress(
-            "Le code postal américain doit être au format 12345 ou 12345-6789".to_string()
+/// Validations spécifiques pour la France
+fn validate_address_france(address: &Address, report: &mut ValidationReport) {
+    // Vérifie que le numéro de téléphone est au format français si présent
+    if let Some(phone) = &address.phone
+        && !phone.starts_with("+33") && !phone.starts_with("0") {
+        report.push(ValidationIssue::error(
+            "phone", "invalid_format", "Le numéro de téléphone français doit commencer par +33 ou 0"
         ));
     }
+}
 
-    Ok(())
+/// Validations spécifiques pour les États-Unis
+fn validate_address_usa(address: &Address, report: &mut ValidationReport) {
+    // Vérifie que l'état est présent (obligatoire aux USA)
+    if address.state.as_ref().is_none_or(|state| state.trim().is_empty()) {
+        report.push(ValidationIssue::error(
+            "state", "required", "L'état est obligatoire pour les adresses aux États-Unis"
+        ));
+    }
 }
 
-/// Valide un colis pour l'expédition
-pub fn validate_parcel(parcel: &Parcel) -> Result<(), DeliveryError> {
+/// Valide un colis pour l'expédition et retourne tous les problèmes constatés
+pub fn validate_parcel(parcel: &Parcel) -> ValidationReport {
+    let mut report = ValidationReport::new();
+
     // Vérifie les dimensions et le poids
     if parcel.weight <= 0.0 {
-        return Err(DeliveryError::InvalidParcel(
-            "Le poids du colis doit être supérieur à 0".to_string()
-        ));
+        report.push(ValidationIssue::error("weight", "not_positive", "Le poids du colis doit être supérieur à 0"));
     }
 
-    if parcel.length <= 0.0 || parcel.width <= 0.0 || parcel.height <= 0.0 {
-        return Err(DeliveryError::InvalidParcel(
-            "Les dimensions du colis doivent être supérieures à 0".to_string()
-        ));
+    let dimensions = [("length", parcel.length), ("width", parcel.width), ("height", parcel.height)];
+    for (field, value) in dimensions {
+        if value <= 0.0 {
+            report.push(ValidationIssue::error(
+                field, "not_positive", "Les dimensions du colis doivent être supérieures à 0"
+            ));
+        }
     }
 
     // Valide les adresses
-    validate_address(&parcel.sender)?;
-    validate_address(&parcel.recipient)?;
+    report.merge_prefixed("sender", validate_address(&parcel.sender));
+    report.merge_prefixed("recipient", validate_address(&parcel.recipient));
 
     // Vérifie que l'expéditeur et le destinataire sont différents
     if parcel.sender.postal_code == parcel.recipient.postal_code &&
        parcel.sender.street1 == parcel.recipient.street1 &&
        parcel.sender.name == parcel.recipient.name {
-        return Err(DeliveryError::InvalidParcel(
-            "L'expéditeur et le destinataire ne peuvent pas être identiques".to_string()
+        report.push(ValidationIssue::error(
+            "recipient", "identical_addresses", "L'expéditeur et le destinataire ne peuvent pas être identiques"
         ));
     }
 
-    Ok(())
+    report
 }
 
-/// Valide un numéro de suivi
-pub fn validate_tracking_number(tracking: &str, carrier_code: Option<crate::models::CarrierCode>) -> bool {
-    if tracking.trim().is_empty() {
-        return false;
+/// Valide une demande d'enlèvement et retourne tous les problèmes constatés
+///
+/// Le jour d'enlèvement est vérifié avec le calendrier donné (jours fériés et
+/// jours opérés par le transporteur).
+pub fn validate_pickup_request(request: &PickupRequest, calendar: &BusinessCalendar, carrier: CarrierCode) -> ValidationReport {
+    let mut report = ValidationReport::new();
+
+    report.merge_prefixed("address", validate_address(&request.address));
+
+    if request.close_at <= request.ready_at {
+        report.push(ValidationIssue::error(
+            "close_at", "invalid_window", "L'heure de fermeture doit suivre l'heure de mise à disposition"
+        ));
+    } else if request.close_at <= chrono::Utc::now() {
+        report.push(ValidationIssue::error("close_at", "in_past", "Le créneau d'enlèvement est déjà passé"));
     }
 
-    match carrier_code {
-        Some(crate::models::CarrierCode::Colissimo) => {
-            // Exemple: 13 caractères se terminant par FR
-            tracking.len() == 13 && tracking.ends_with("FR")
-        }
-        Some(crate::models::CarrierCode::Chronopost) => {
-            // Exemple: 10 caractères commençant par CH
-            tracking.len() == 10 && tracking.starts_with("CH")
-        }
-        Some(crate::models::CarrierCode::FedEx) => {
-            // Exemple: 12 chiffres
-            tracking.len() == 12 && tracking.chars().all(|c| c.is_numeric())
-        }
-        Some(crate::models::CarrierCode::UPS) => {
-            // Exemple: 1Z suivi de caractères alphanumériques
-            tracking.len() == 18 && tracking.starts_with("1Z")
-        }
-        Some(crate::models::CarrierCode::DHL) => {
-            // Exemple: 10 chiffres
-            tracking.len() == 10 && tracking.chars().all(|c| c.is_numeric())
-        }
-        None => {
-            // Validation basique sans spécifier le transporteur
-            !tracking.is_empty() && tracking.len() >= 8 && tracking.len() <= 30
-        }
+    let ready_date = calendar.local_date(&request.address.country, request.ready_at);
+    if !calendar.is_operating_day(carrier, &request.address.country, ready_date) {
+        report.push(
+            ValidationIssue::warning("ready_at", "non_business_day", "L'enlèvement est demandé un jour non ouvré")
+                .with_param("date", ready_date)
+                .with_param("carrier", carrier)
+        );
     }
+
+    if request.package_count == 0 {
+        report.push(ValidationIssue::error("package_count", "not_positive", "Au moins un colis doit être enlevé"));
+    }
+
+    if request.total_weight <= 0.0 {
+        report.push(ValidationIssue::error("total_weight", "not_positive", "Le poids total doit être supérieur à 0"));
+    }
+
+    if request.tracking_numbers.len() > request.package_count as usize {
+        report.push(
+            ValidationIssue::error(
+                "tracking_numbers", "too_many", "Plus de numéros de suivi que de colis à enlever"
+            )
+            .with_param("count", request.tracking_numbers.len())
+            .with_param("package_count", request.package_count)
+        );
+    }
+
+    report
+}
+
+/// Vérifie que le poids facturable annoncé par un tarif correspond aux dimensions du colis
+///
+/// Le poids attendu est calculé avec les diviseurs donnés, ceux du contrat de
+/// l'expéditeur s'il en a négocié.
+pub fn validate_rate_weight(parcel: &Parcel, rate: &Rate, rules: &crate::utils::weight::VolumetricRules) -> ValidationReport {
+    let mut report = ValidationReport::new();
+    let expected = rules.billable_weight(parcel, rate.carrier, &rate.service_code);
+
+    // Tolérance de 10 g pour les arrondis propres à chaque API
+    if let Some(quoted) = rate.billable_weight
+        && quoted + 0.01 < expected.billable {
+        report.push(
+            ValidationIssue::error(
+                "rate.billable_weight",
+                "billable_weight_mismatch",
+                &format!(
+                    "Le poids facturable du tarif ({:.2} kg) est inférieur au poids facturable calculé ({:.2} kg)",
+                    quoted, expected.billable
+                ),
+            )
+            .with_param("quoted", format!("{:.2}", quoted))
+            .with_param("expected", format!("{:.2}", expected.billable))
+        );
+    }
+
+    report
+}
+
+/// Valide un numéro de suivi (format et clé de contrôle), pour un transporteur donné ou pour l'un d'eux
+///
+/// Un numéro qui a la structure d'un format à clé de contrôle mais dont la clé est
+/// fausse est signalé avec le paramètre `check_digit` à `invalid`.
+pub fn validate_tracking_number(tracking_number: &str, carrier_code: Option<CarrierCode>) -> ValidationReport {
+    let mut report = ValidationReport::new();
+    if tracking::is_valid_tracking_number(tracking_number, carrier_code) {
+        return report;
+    }
+
+    let normalized = tracking::normalize_tracking_number(tracking_number);
+    let check_digit_failed = tracking::has_invalid_check_digit(&normalized);
+    let message = match carrier_code {
+        _ if check_digit_failed => format!("Le numéro de suivi {} n'est pas valide: clé de contrôle incorrecte", normalized),
+        Some(carrier) => format!("Le numéro de suivi {} n'est pas un numéro {}", normalized, carrier),
+        None => format!("Le numéro de suivi {} n'est pas valide: format inconnu", normalized),
+    };
+
+    let mut issue = ValidationIssue::error("tracking_number", "invalid_format", &message)
+        .with_param("tracking_number", &normalized);
+    if check_digit_failed {
+        issue = issue.with_param("check_digit", "invalid");
+    }
+    if let Some(carrier) = carrier_code {
+        issue = issue.with_param("carrier", carrier);
+    }
+    report.push(issue);
+
+    report
 }
@@ -0,0 +1,209 @@
+use std::sync::LazyLock;
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::{CarrierCode, Parcel};
+
+/// Nombre de kilogrammes dans une livre
+const KG_PER_LB: f64 = 0.453_592_37;
+
+/// Nombre de centimètres dans un pouce
+const CM_PER_INCH: f64 = 2.54;
+
+/// Système d'unités dans lequel un diviseur volumétrique est exprimé
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DimensionUnit {
+    /// Diviseur en cm³/kg (ex: 5000, 6000)
+    Metric,
+    /// Diviseur en in³/lb (ex: 139, 166)
+    Imperial,
+}
+
+/// Diviseur volumétrique appliqué par un transporteur
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct VolumetricDivisor {
+    pub divisor: f64,
+    pub unit: DimensionUnit,
+    /// Pas d'arrondi supérieur du poids facturable, dans l'unité de poids du diviseur (kg ou lb)
+    pub rounding_step: f64,
+}
+
+impl VolumetricDivisor {
+    /// Crée un diviseur métrique (cm³/kg) sans arrondi
+    pub const fn metric(divisor: f64) -> Self {
+        Self { divisor, unit: DimensionUnit::Metric, rounding_step: 0.0 }
+    }
+
+    /// Crée un diviseur impérial (in³/lb) sans arrondi
+    pub const fn imperial(divisor: f64) -> Self {
+        Self { divisor, unit: DimensionUnit::Imperial, rounding_step: 0.0 }
+    }
+
+    /// Définit le pas d'arrondi supérieur du poids facturable
+    pub const fn with_rounding(mut self, step: f64) -> Self {
+        self.rounding_step = step;
+        self
+    }
+
+    /// Calcule le poids volumétrique en kg pour des dimensions en cm
+    pub fn volumetric_weight(&self, length: f64, width: f64, height: f64) -> f64 {
+        match self.unit {
+            DimensionUnit::Metric => length * width * height / self.divisor,
+            DimensionUnit::Imperial => {
+                let cubic_inches = (length / CM_PER_INCH) * (width / CM_PER_INCH) * (height / CM_PER_INCH);
+                cubic_inches / self.divisor * KG_PER_LB
+            }
+        }
+    }
+
+    /// Arrondit un poids en kg au pas supérieur défini par le transporteur
+    pub fn round_up(&self, weight_kg: f64) -> f64 {
+        if self.rounding_step <= 0.0 {
+            return weight_kg;
+        }
+
+        let (weight, factor) = match self.unit {
+            DimensionUnit::Metric => (weight_kg, 1.0),
+            DimensionUnit::Imperial => (weight_kg / KG_PER_LB, KG_PER_LB),
+        };
+
+        // Tolérance pour éviter qu'une erreur d'arrondi flottant ne fasse passer au pas suivant
+        let steps = (weight / self.rounding_step - 1e-9).ceil().max(0.0);
+        steps * self.rounding_step * factor
+    }
+}
+
+/// Détail du calcul du poids facturable d'un colis
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BillableWeight {
+    /// Poids réel en kg
+    pub actual: f64,
+    /// Poids volumétrique en kg (absent si le transporteur facture au poids réel)
+    pub volumetric: Option<f64>,
+    /// Poids retenu pour la facturation en kg
+    pub billable: f64,
+    /// Diviseur utilisé pour le calcul
+    pub divisor: Option<VolumetricDivisor>,
+}
+
+impl BillableWeight {
+    /// Indique si le poids volumétrique l'emporte sur le poids réel
+    pub fn is_volumetric(&self) -> bool {
+        self.volumetric.is_some_and(|volumetric| volumetric > self.actual)
+    }
+}
+
+/// Règle associant un diviseur à un transporteur et, optionnellement, à un service
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VolumetricRule {
+    pub carrier: CarrierCode,
+    /// Code de service concerné (None = tous les services du transporteur)
+    pub service_code: Option<String>,
+    /// Diviseur à appliquer (None = facturation au poids réel uniquement)
+    pub divisor: Option<VolumetricDivisor>,
+}
+
+/// Table des diviseurs volumétriques par transporteur et par service
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VolumetricRules {
+    rules: Vec<VolumetricRule>,
+}
+
+impl VolumetricRules {
+    /// Crée une table vide (aucun poids volumétrique appliqué)
+    pub fn empty() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    /// Ajoute ou remplace une règle (ex: diviseur négocié sur un compte)
+    pub fn with_rule(mut self, carrier: CarrierCode, service_code: Option<&str>, divisor: Option<VolumetricDivisor>) -> Self {
+        self.rules.retain(|rule| !(rule.carrier == carrier && rule.service_code.as_deref() == service_code));
+        self.rules.push(VolumetricRule {
+            carrier,
+            service_code: service_code.map(|code| code.to_string()),
+            divisor,
+        });
+        self
+    }
+
+    /// Obtient le diviseur applicable à un service, la règle du service primant sur celle du transporteur
+    pub fn divisor_for(&self, carrier: CarrierCode, service_code: &str) -> Option<VolumetricDivisor> {
+        let service_rule = self.rules.iter()
+            .find(|rule| rule.carrier == carrier && rule.service_code.as_deref() == Some(service_code));
+
+        let carrier_rule = || self.rules.iter()
+            .find(|rule| rule.carrier == carrier && rule.service_code.is_none());
+
+        service_rule.or_else(carrier_rule).and_then(|rule| rule.divisor)
+    }
+
+    /// Calcule le poids facturable d'un colis pour un service donné
+    pub fn billable_weight(&self, parcel: &Parcel, carrier: CarrierCode, service_code: &str) -> BillableWeight {
+        match self.divisor_for(carrier, service_code) {
+            Some(divisor) => billable_weight(parcel, &divisor),
+            None => BillableWeight {
+                actual: parcel.weight,
+                volumetric: None,
+                billable: parcel.weight,
+                divisor: None,
+            },
+        }
+    }
+}
+
+impl Default for VolumetricRules {
+    /// Diviseurs publics des transporteurs supportés
+    fn default() -> Self {
+        let us_domestic = VolumetricDivisor::imperial(139.0).with_rounding(1.0);
+        let express = VolumetricDivisor::metric(5000.0).with_rounding(0.5);
+
+        let mut rules = Self::empty()
+            // Colissimo facture au poids réel
+            .with_rule(CarrierCode::Colissimo, None, None)
+            .with_rule(CarrierCode::Chronopost, None, Some(VolumetricDivisor::metric(5000.0)))
+            .with_rule(CarrierCode::FedEx, None, Some(express))
+            .with_rule(CarrierCode::UPS, None, Some(express))
+            .with_rule(CarrierCode::DHL, None, Some(express))
+            .with_rule(CarrierCode::DHL, Some("W"), Some(VolumetricDivisor::metric(6000.0).with_rounding(0.5)));
+
+        // Services domestiques américains, facturés en pouces et en livres
+        for service in ["FEDEX_GROUND", "GROUND_HOME_DELIVERY", "FEDEX_EXPRESS_SAVER", "FEDEX_2_DAY", "STANDARD_OVERNIGHT", "PRIORITY_OVERNIGHT"] {
+            rules = rules.with_rule(CarrierCode::FedEx, Some(service), Some(us_domestic));
+        }
+        for service in ["01", "02", "03", "12", "13", "14", "59"] {
+            rules = rules.with_rule(CarrierCode::UPS, Some(service), Some(us_domestic));
+        }
+
+        rules
+    }
+}
+
+/// Calcule le poids volumétrique d'un colis en kg
+pub fn volumetric_weight(parcel: &Parcel, divisor: &VolumetricDivisor) -> f64 {
+    divisor.volumetric_weight(parcel.length, parcel.width, parcel.height)
+}
+
+/// Calcule le poids facturable d'un colis : max(poids réel, poids volumétrique), arrondi selon le transporteur
+pub fn billable_weight(parcel: &Parcel, divisor: &VolumetricDivisor) -> BillableWeight {
+    let volumetric = volumetric_weight(parcel, divisor);
+
+    BillableWeight {
+        actual: parcel.weight,
+        volumetric: Some(volumetric),
+        billable: divisor.round_up(parcel.weight.max(volumetric)),
+        divisor: Some(*divisor),
+    }
+}
+
+/// Diviseurs publics des transporteurs
+static PUBLIC_RULES: LazyLock<VolumetricRules> = LazyLock::new(VolumetricRules::default);
+
+/// Diviseurs publics des transporteurs, construits une seule fois
+pub fn public_rules() -> &'static VolumetricRules {
+    &PUBLIC_RULES
+}
+
+/// Calcule le poids facturable d'un colis avec les diviseurs publics du transporteur
+pub fn billable_weight_for(parcel: &Parcel, carrier: CarrierCode, service_code: &str) -> BillableWeight {
+    PUBLIC_RULES.billable_weight(parcel, carrier, service_code)
+}
@@ -0,0 +1,305 @@
+use std::collections::{BTreeSet, HashMap};
+use std::io::Read;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::DeliveryError;
+use crate::utils::address::transliterate;
+use crate::verification::AddressUsage;
+
+/// Nombre de caractères des préfixes indexés (département, district postal...)
+const PREFIX_LENGTH: usize = 2;
+
+/// Commune ou localité desservie par un code postal
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PostalEntry {
+    pub country: String,  // Code ISO 3166-1
+    pub postal_code: String,
+    pub city: String,  // Nom de la commune
+    #[serde(default)]
+    pub delivery_label: Option<String>,  // Libellé d'acheminement, s'il diffère du nom de la commune
+    #[serde(default)]
+    pub locality: Option<String>,  // Lieu-dit ou ancienne commune (ligne 5)
+    #[serde(default)]
+    pub commune_code: Option<String>,  // Code INSEE ou équivalent
+    #[serde(default)]
+    pub usage: Option<AddressUsage>,  // Usage connu du code postal
+}
+
+impl PostalEntry {
+    /// Crée une entrée à partir du code postal et de la commune
+    pub fn new(country: &str, postal_code: &str, city: &str) -> Self {
+        Self {
+            country: country.trim().to_uppercase(),
+            postal_code: postal_code.trim().to_string(),
+            city: city.trim().to_string(),
+            delivery_label: None,
+            locality: None,
+            commune_code: None,
+            usage: None,
+        }
+    }
+
+    /// Définit le libellé d'acheminement
+    pub fn with_delivery_label(mut self, label: &str) -> Self {
+        self.delivery_label = Some(label.trim().to_string()).filter(|label| !label.is_empty());
+        self
+    }
+
+    /// Définit le lieu-dit
+    pub fn with_locality(mut self, locality: &str) -> Self {
+        self.locality = Some(locality.trim().to_string()).filter(|locality| !locality.is_empty());
+        self
+    }
+
+    /// Définit l'usage du code postal
+    pub fn with_usage(mut self, usage: AddressUsage) -> Self {
+        self.usage = Some(usage);
+        self
+    }
+
+    /// Localité à écrire sur l'adresse : libellé d'acheminement, sinon nom de la commune
+    pub fn mailing_city(&self) -> &str {
+        self.delivery_label.as_deref().unwrap_or(&self.city)
+    }
+
+    /// Usage du code postal : déclaré, ou professionnel pour un code CEDEX
+    /// (réservé aux entreprises et administrations)
+    pub fn usage(&self) -> AddressUsage {
+        match self.usage {
+            Some(usage) => usage,
+            None if self.names().any(|name| place_key(name).split(' ').any(|word| word == "CEDEX")) => AddressUsage::Commercial,
+            None => AddressUsage::Unknown,
+        }
+    }
+
+    /// Noms sous lesquels la localité peut être écrite
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.city.as_str())
+            .chain(self.delivery_label.as_deref())
+            .chain(self.locality.as_deref())
+    }
+}
+
+/// Référentiel hors ligne des codes postaux et des communes
+///
+/// Se charge depuis un CSV générique (`country,postal_code,city,delivery_label,locality,commune_code,usage`,
+/// seules les trois premières colonnes étant obligatoires) ou depuis la base officielle
+/// des codes postaux de La Poste.
+#[derive(Debug, Clone, Default)]
+pub struct PostalDataset {
+    entries: Vec<PostalEntry>,
+    by_country: HashMap<String, Vec<usize>>,  // Pays → entrées
+    by_postal_code: HashMap<(String, String), Vec<usize>>,  // (pays, code postal compact) → entrées
+    by_postal_prefix: HashMap<(String, String), Vec<usize>>,  // (pays, début du code postal) → entrées
+    by_name: HashMap<(String, String), Vec<usize>>,  // (pays, nom normalisé) → entrées
+    by_name_prefix: HashMap<(String, String), Vec<usize>>,  // (pays, début du nom normalisé) → entrées
+}
+
+impl PostalDataset {
+    /// Crée un référentiel vide
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Crée un référentiel à partir d'entrées
+    pub fn from_entries(entries: impl IntoIterator<Item = PostalEntry>) -> Self {
+        let mut dataset = Self::new();
+        for entry in entries {
+            dataset.push(entry);
+        }
+        dataset
+    }
+
+    /// Ajoute une entrée au référentiel
+    pub fn with_entry(mut self, entry: PostalEntry) -> Self {
+        self.push(entry);
+        self
+    }
+
+    /// Ajoute une entrée au référentiel
+    pub fn push(&mut self, mut entry: PostalEntry) {
+        entry.country = entry.country.trim().to_uppercase();
+        let index = self.entries.len();
+        let postal_code = postal_key(&entry.postal_code);
+
+        self.by_country.entry(entry.country.clone()).or_default().push(index);
+        self.by_postal_prefix.entry((entry.country.clone(), prefix(&postal_code))).or_default().push(index);
+        self.by_postal_code.entry((entry.country.clone(), postal_code)).or_default().push(index);
+
+        let keys: BTreeSet<String> = entry.names().map(place_key).filter(|key| !key.is_empty()).collect();
+        let prefixes: BTreeSet<String> = keys.iter().map(|key| prefix(key)).collect();
+        for key in keys {
+            self.by_name.entry((entry.country.clone(), key)).or_default().push(index);
+        }
+        for name_prefix in prefixes {
+            self.by_name_prefix.entry((entry.country.clone(), name_prefix)).or_default().push(index);
+        }
+
+        self.entries.push(entry);
+    }
+
+    /// Charge un CSV générique (en-têtes obligatoires, séparateur `,`)
+    pub fn from_csv<R: Read>(reader: R) -> Result<Self, DeliveryError> {
+        let mut csv_reader = csv::ReaderBuilder::new().trim(csv::Trim::All).from_reader(reader);
+        let mut dataset = Self::new();
+
+        for record in csv_reader.deserialize::<PostalEntry>() {
+            let entry = record
+                .map_err(|e| DeliveryError::SerializationError(format!("Référentiel postal CSV invalide: {}", e)))?;
+            dataset.push(entry);
+        }
+
+        Ok(dataset)
+    }
+
+    /// Charge un CSV générique depuis un fichier
+    pub fn from_csv_file(path: impl AsRef<Path>) -> Result<Self, DeliveryError> {
+        Self::from_csv(std::fs::File::open(path)?)
+    }
+
+    /// Charge la base officielle des codes postaux de La Poste
+    ///
+    /// Colonnes attendues, séparées par `;` : code commune INSEE, nom de la commune,
+    /// code postal, libellé d'acheminement et, facultativement, ligne 5. Les fichiers
+    /// en UTF-8 comme en Latin-1 sont acceptés.
+    pub fn from_laposte_csv<R: Read>(reader: R) -> Result<Self, DeliveryError> {
+        let mut csv_reader = csv::ReaderBuilder::new()
+            .delimiter(b';')
+            .flexible(true)
+            .trim(csv::Trim::All)
+            .from_reader(reader);
+        let mut dataset = Self::new();
+
+        for (line, record) in csv_reader.byte_records().enumerate() {
+            let record = record
+                .map_err(|e| DeliveryError::SerializationError(format!("Base La Poste invalide: {}", e)))?;
+            let field = |index: usize| record.get(index).map(decode).unwrap_or_default();
+
+            let (postal_code, city) = (field(2), field(1));
+            if postal_code.is_empty() || city.is_empty() {
+                return Err(DeliveryError::SerializationError(format!(
+                    "Base La Poste invalide: code postal ou commune manquant ligne {}", line + 2
+                )));
+            }
+
+            let mut entry = PostalEntry::new("FR", &postal_code, &city)
+                .with_delivery_label(&field(3))
+                .with_locality(&field(4));
+            entry.commune_code = Some(field(0)).filter(|code| !code.is_empty());
+            if entry.delivery_label.as_deref().is_some_and(|label| place_key(label) == place_key(&entry.city)) {
+                entry.delivery_label = None;
+            }
+            dataset.push(entry);
+        }
+
+        Ok(dataset)
+    }
+
+    /// Charge la base officielle des codes postaux de La Poste depuis un fichier
+    pub fn from_laposte_file(path: impl AsRef<Path>) -> Result<Self, DeliveryError> {
+        Self::from_laposte_csv(std::fs::File::open(path)?)
+    }
+
+    /// Toutes les entrées, dans l'ordre de chargement
+    pub fn entries(&self) -> &[PostalEntry] {
+        &self.entries
+    }
+
+    /// Nombre d'entrées
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Indique si le référentiel est vide
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Pays couverts par le référentiel
+    pub fn countries(&self) -> BTreeSet<&str> {
+        self.by_country.keys().map(String::as_str).collect()
+    }
+
+    /// Indique si le référentiel couvre un pays
+    pub fn covers(&self, country: &str) -> bool {
+        self.by_country.contains_key(&country.trim().to_uppercase())
+    }
+
+    /// Entrées d'un pays
+    pub fn entries_for<'a>(&'a self, country: &str) -> impl Iterator<Item = &'a PostalEntry> + 'a {
+        self.by_country.get(&country.trim().to_uppercase())
+            .into_iter()
+            .flatten()
+            .map(|index| &self.entries[*index])
+    }
+
+    /// Communes desservies par un code postal
+    pub fn by_postal_code(&self, country: &str, postal_code: &str) -> Vec<&PostalEntry> {
+        self.lookup(&self.by_postal_code, country, postal_key(postal_code))
+    }
+
+    /// Codes postaux d'une commune (nom, libellé d'acheminement ou lieu-dit)
+    pub fn by_city(&self, country: &str, city: &str) -> Vec<&PostalEntry> {
+        self.lookup(&self.by_name, country, place_key(city))
+    }
+
+    /// Entrées proches d'une adresse : même début de code postal ou de nom de localité
+    pub fn candidates(&self, country: &str, postal_code: &str, city: &str) -> Vec<&PostalEntry> {
+        let country = country.trim().to_uppercase();
+        let postal_prefix = (country.clone(), prefix(&postal_key(postal_code)));
+        let name_prefix = (country, prefix(&place_key(city)));
+
+        let indices: BTreeSet<usize> = [self.by_postal_prefix.get(&postal_prefix), self.by_name_prefix.get(&name_prefix)]
+            .into_iter()
+            .flatten()
+            .flatten()
+            .copied()
+            .collect();
+        indices.into_iter().map(|index| &self.entries[index]).collect()
+    }
+
+    fn lookup(&self, index: &HashMap<(String, String), Vec<usize>>, country: &str, key: String) -> Vec<&PostalEntry> {
+        index.get(&(country.trim().to_uppercase(), key))
+            .map(|indices| indices.iter().map(|index| &self.entries[*index]).collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Début d'une clé de code postal ou de nom
+fn prefix(key: &str) -> String {
+    key.chars().take(PREFIX_LENGTH).collect()
+}
+
+/// Décode un champ UTF-8, ou Latin-1 à défaut
+fn decode(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.trim().to_string(),
+        Err(_) => bytes.iter().map(|byte| *byte as char).collect::<String>().trim().to_string(),
+    }
+}
+
+/// Code postal sans espaces ni tirets, en majuscules
+pub(crate) fn postal_key(postal_code: &str) -> String {
+    postal_code.chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .flat_map(char::to_uppercase)
+        .collect()
+}
+
+/// Nom de localité comparable : sans accents ni ponctuation, en majuscules,
+/// « SAINT » et « SAINTE » abrégés
+pub(crate) fn place_key(name: &str) -> String {
+    transliterate(name)
+        .to_uppercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| match word {
+            "SAINT" => "ST",
+            "SAINTE" => "STE",
+            other => other,
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,328 @@
+pub mod dataset;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::DeliveryError;
+use crate::models::Address;
+use crate::utils::validation::{ValidationIssue, ValidationReport};
+pub use dataset::{PostalDataset, PostalEntry};
+use dataset::{place_key, postal_key};
+
+/// Usage d'une adresse
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AddressUsage {
+    Residential,  // Particulier : ni raison sociale, ni CEDEX, ni boîte postale
+    Commercial,  // Entreprise ou administration
+    #[default]
+    Unknown,  // Pays non couvert : usage non déterminé
+}
+
+/// Résultat de la vérification du couple code postal / localité
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationStatus {
+    Verified,  // Le code postal dessert la localité
+    Mismatch,  // Code postal et localité existent, mais ne vont pas ensemble
+    UnknownPostalCode,  // Code postal absent du référentiel
+    UnknownCity,  // Localité inconnue pour ce code postal comme ailleurs
+    Unsupported,  // Pays non couvert par le référentiel
+}
+
+/// Correction proposée pour une adresse
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AddressSuggestion {
+    pub postal_code: String,
+    pub city: String,  // Localité à écrire sur l'adresse
+    pub score: f64,  // 0 à 1, 1 = concordance parfaite
+    pub usage: AddressUsage,
+}
+
+/// Résultat de la vérification d'une adresse
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AddressVerification {
+    pub status: VerificationStatus,
+    pub matched: Option<PostalEntry>,  // Entrée du référentiel correspondant à l'adresse
+    pub suggestions: Vec<AddressSuggestion>,  // Par score décroissant
+    pub usage: AddressUsage,
+    pub report: ValidationReport,  // Problèmes constatés, traduisibles
+}
+
+impl AddressVerification {
+    /// Vérification impossible faute de données pour le pays
+    pub fn unsupported() -> Self {
+        Self {
+            status: VerificationStatus::Unsupported,
+            matched: None,
+            suggestions: Vec::new(),
+            usage: AddressUsage::Unknown,
+            report: ValidationReport::new(),
+        }
+    }
+
+    /// Indique si le code postal dessert la localité
+    pub fn is_verified(&self) -> bool {
+        self.status == VerificationStatus::Verified
+    }
+
+    /// Correction la plus probable
+    pub fn best_suggestion(&self) -> Option<&AddressSuggestion> {
+        self.suggestions.first()
+    }
+
+    /// Adresse corrigée avec la meilleure suggestion, si elle atteint le score minimal
+    pub fn corrected(&self, address: &Address, min_score: f64) -> Option<Address> {
+        let suggestion = self.best_suggestion().filter(|suggestion| suggestion.score >= min_score)?;
+        let mut corrected = address.clone();
+        corrected.postal_code = suggestion.postal_code.clone();
+        corrected.city = suggestion.city.clone();
+        Some(corrected)
+    }
+}
+
+/// Trait pour les services de vérification d'adresse
+#[async_trait]
+pub trait AddressVerifier: Send + Sync {
+    /// Vérifie la cohérence d'une adresse et propose des corrections
+    async fn verify(&self, address: &Address) -> Result<AddressVerification, DeliveryError>;
+
+    /// Version synchrone (bloquante) de verify
+    fn verify_blocking(&self, address: &Address) -> Result<AddressVerification, DeliveryError>;
+
+    /// Indique si le service couvre un pays
+    fn supports(&self, country: &str) -> bool;
+}
+
+/// Score minimal par défaut d'une suggestion
+pub const DEFAULT_MIN_SCORE: f64 = 0.5;
+
+/// Nombre de suggestions retournées par défaut
+pub const DEFAULT_MAX_SUGGESTIONS: usize = 5;
+
+/// Vérification d'adresse à partir d'un référentiel postal hors ligne
+///
+/// Le score d'une suggestion pondère à parts égales la ressemblance de la
+/// localité et celle du code postal : une localité bien écrite sous un autre
+/// code postal et un code postal exact sous une autre localité valent chacun
+/// au moins 0,5.
+#[derive(Debug, Clone)]
+pub struct DatasetVerifier {
+    dataset: Arc<PostalDataset>,
+    min_score: f64,
+    max_suggestions: usize,
+}
+
+impl DatasetVerifier {
+    /// Crée un service de vérification à partir d'un référentiel
+    pub fn new(dataset: PostalDataset) -> Self {
+        Self {
+            dataset: Arc::new(dataset),
+            min_score: DEFAULT_MIN_SCORE,
+            max_suggestions: DEFAULT_MAX_SUGGESTIONS,
+        }
+    }
+
+    /// Définit le score minimal d'une suggestion
+    pub fn with_min_score(mut self, min_score: f64) -> Self {
+        self.min_score = min_score.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Définit le nombre maximal de suggestions
+    pub fn with_max_suggestions(mut self, max_suggestions: usize) -> Self {
+        self.max_suggestions = max_suggestions;
+        self
+    }
+
+    /// Référentiel utilisé
+    pub fn dataset(&self) -> &PostalDataset {
+        &self.dataset
+    }
+
+    /// Vérifie une adresse contre le référentiel
+    pub fn check(&self, address: &Address) -> AddressVerification {
+        let country = address.country.trim().to_uppercase();
+        if !self.dataset.covers(&country) {
+            return AddressVerification::unsupported();
+        }
+
+        let city = place_key(&address.city);
+        let served = self.dataset.by_postal_code(&country, &address.postal_code);
+        let matched = served.iter()
+            .find(|entry| entry.names().any(|name| place_key(name) == city))
+            .map(|entry| (*entry).clone());
+
+        let status = match &matched {
+            Some(_) => VerificationStatus::Verified,
+            None if served.is_empty() => VerificationStatus::UnknownPostalCode,
+            None if !self.dataset.by_city(&country, &address.city).is_empty() => VerificationStatus::Mismatch,
+            None => VerificationStatus::UnknownCity,
+        };
+
+        let mut report = ValidationReport::new();
+        let postal_code = address.postal_code.trim();
+        let city_name = address.city.trim();
+        match status {
+            VerificationStatus::Mismatch => report.push(
+                ValidationIssue::error(
+                    "postal_code",
+                    "postal_code_city_mismatch",
+                    &format!("Le code postal {} ne correspond pas à la ville {}", postal_code, city_name),
+                )
+                .with_param("postal_code", postal_code)
+                .with_param("city", city_name)
+            ),
+            VerificationStatus::UnknownPostalCode => report.push(
+                ValidationIssue::error(
+                    "postal_code",
+                    "unknown_postal_code",
+                    &format!("Le code postal {} n'existe pas", postal_code),
+                )
+                .with_param("postal_code", postal_code)
+            ),
+            VerificationStatus::UnknownCity => report.push(
+                ValidationIssue::warning(
+                    "city",
+                    "unknown_city",
+                    &format!("La ville {} n'est pas desservie par le code postal {}", city_name, postal_code),
+                )
+                .with_param("postal_code", postal_code)
+                .with_param("city", city_name)
+            ),
+            VerificationStatus::Verified | VerificationStatus::Unsupported => {}
+        }
+
+        let suggestions = match status {
+            VerificationStatus::Verified => Vec::new(),
+            _ => self.suggestions(&country, &address.postal_code, &city),
+        };
+        let usage = address_usage(address, matched.as_ref().map_or(AddressUsage::Unknown, PostalEntry::usage));
+
+        AddressVerification { status, matched, suggestions, usage, report }
+    }
+
+    /// Entrées les plus proches de l'adresse, par score décroissant
+    ///
+    /// Seules les entrées partageant le début du code postal ou du nom de la
+    /// localité sont comparées.
+    fn suggestions(&self, country: &str, postal_code: &str, city: &str) -> Vec<AddressSuggestion> {
+        let candidates = self.dataset.candidates(country, postal_code, city);
+        let postal_code = postal_key(postal_code);
+        let mut best: HashMap<(String, String), AddressSuggestion> = HashMap::new();
+
+        for entry in candidates {
+            let city_score = entry.names()
+                .map(|name| similarity(city, &place_key(name)))
+                .fold(0.0, f64::max);
+            let score = (city_score + postal_similarity(&postal_code, &postal_key(&entry.postal_code))) / 2.0;
+            if score < self.min_score {
+                continue;
+            }
+
+            let suggestion = AddressSuggestion {
+                postal_code: entry.postal_code.clone(),
+                city: entry.mailing_city().to_string(),
+                score: (score * 100.0).round() / 100.0,
+                usage: entry.usage(),
+            };
+            best.entry((suggestion.postal_code.clone(), suggestion.city.clone()))
+                .and_modify(|existing| if suggestion.score > existing.score { *existing = suggestion.clone() })
+                .or_insert(suggestion);
+        }
+
+        let mut suggestions: Vec<AddressSuggestion> = best.into_values().collect();
+        suggestions.sort_by(|a, b| {
+            b.score.total_cmp(&a.score)
+                .then_with(|| a.postal_code.cmp(&b.postal_code))
+                .then_with(|| a.city.cmp(&b.city))
+        });
+        suggestions.truncate(self.max_suggestions);
+        suggestions
+    }
+}
+
+#[async_trait]
+impl AddressVerifier for DatasetVerifier {
+    async fn verify(&self, address: &Address) -> Result<AddressVerification, DeliveryError> {
+        self.verify_blocking(address)
+    }
+
+    fn verify_blocking(&self, address: &Address) -> Result<AddressVerification, DeliveryError> {
+        Ok(self.check(address))
+    }
+
+    fn supports(&self, country: &str) -> bool {
+        self.dataset.covers(country)
+    }
+}
+
+/// Usage d'une adresse : celui du code postal s'il est connu, sinon professionnel
+/// pour une raison sociale, une mention CEDEX ou une boîte postale (BP, CS),
+/// particulier à défaut
+fn address_usage(address: &Address, postal_usage: AddressUsage) -> AddressUsage {
+    if postal_usage != AddressUsage::Unknown {
+        return postal_usage;
+    }
+
+    let has_company = address.company.as_deref().is_some_and(|company| !company.trim().is_empty());
+    let cedex = place_key(&address.city).split(' ').any(|word| word == "CEDEX");
+    let post_box = std::iter::once(address.street1.as_str())
+        .chain(address.street2.as_deref())
+        .any(|street| {
+            let street = place_key(street);
+            street.split(' ').any(|word| matches!(word, "BP" | "TSA"))
+                || street.starts_with("CS ")
+                || street.contains("BOITE POSTALE")
+        });
+
+    if has_company || cedex || post_box {
+        AddressUsage::Commercial
+    } else {
+        AddressUsage::Residential
+    }
+}
+
+/// Ressemblance de deux codes postaux : 1 s'ils sont identiques, sinon part du
+/// préfixe commun (département, district...) réduite de moitié
+fn postal_similarity(a: &str, b: &str) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+    let length = a.chars().count().max(b.chars().count());
+    if length == 0 {
+        return 0.0;
+    }
+    let common = a.chars().zip(b.chars()).take_while(|(x, y)| x == y).count();
+    common as f64 / length as f64 / 2.0
+}
+
+/// Ressemblance de deux noms normalisés, d'après la distance de Levenshtein (0 à 1)
+fn similarity(a: &str, b: &str) -> f64 {
+    let length = a.chars().count().max(b.chars().count());
+    if length == 0 {
+        return 0.0;
+    }
+    1.0 - levenshtein(a, b) as f64 / length as f64
+}
+
+/// Nombre minimal d'insertions, suppressions et substitutions pour passer d'un texte à l'autre
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, ca) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
@@ -0,0 +1,61 @@
+#![cfg(feature = "mock")]
+
+use zyou_delivery::carriers::mock::MockCarrier;
+use zyou_delivery::lifecycle::LifecycleState;
+use zyou_delivery::{CarrierCode, DeliveryError, LabelOptions, Parcel, ShippingManager};
+
+fn setup() -> (ShippingManager, Parcel) {
+    let mut manager = ShippingManager::new();
+    manager.add_carrier(Box::new(MockCarrier::new(CarrierCode::Colissimo)));
+
+    let parcel = Parcel::new()
+        .with_weight(1.5)
+        .with_dimensions(30.0, 20.0, 10.0)
+        .with_sender("Boutique", "1 rue de la Paix", "75002", "Paris", "FR")
+        .with_recipient("Jean Martin", "10 avenue Foch", "69006", "Lyon", "FR");
+
+    (manager, parcel)
+}
+
+fn label_twice(manager: &ShippingManager, parcel: &Parcel, between: impl FnOnce(&str)) -> String {
+    let rate = manager.get_rates_blocking(&CarrierCode::Colissimo, parcel).unwrap().remove(0);
+    let label = manager.generate_label_blocking(&CarrierCode::Colissimo, parcel, &rate, &LabelOptions::default()).unwrap();
+    between(&label.tracking_number);
+
+    let again = manager.generate_label_blocking(&CarrierCode::Colissimo, parcel, &rate, &LabelOptions::default()).unwrap();
+    assert_eq!(again.tracking_number, label.tracking_number);
+    label.tracking_number
+}
+
+#[test]
+fn regenerated_label_keeps_shipment_history() {
+    let (manager, parcel) = setup();
+    let mut first = None;
+    let tracking_number = label_twice(&manager, &parcel, |tracking_number| {
+        first = manager.store().load_shipment(tracking_number).unwrap();
+    });
+    let first = first.unwrap();
+
+    let record = manager.store().load_shipment(&tracking_number).unwrap().unwrap();
+    assert_eq!(record.created_at, first.created_at);
+    assert_eq!(record.lifecycle.state, LifecycleState::Labelled);
+    assert_eq!(record.lifecycle.audit, first.lifecycle.audit);
+    assert_eq!(manager.manifests().pending(CarrierCode::Colissimo, None).len(), 1);
+}
+
+#[test]
+fn regenerated_label_does_not_revive_cancelled_shipment() {
+    let (manager, parcel) = setup();
+    let rate = manager.get_rates_blocking(&CarrierCode::Colissimo, &parcel).unwrap().remove(0);
+    let label = manager.generate_label_blocking(&CarrierCode::Colissimo, &parcel, &rate, &LabelOptions::default()).unwrap();
+    let tracking_number = label.tracking_number;
+    manager.cancel_shipment(&tracking_number, "Commande annulée").unwrap();
+
+    let again = manager.generate_label_blocking(&CarrierCode::Colissimo, &parcel, &rate, &LabelOptions::default());
+    assert!(matches!(again, Err(DeliveryError::InvalidTransition(_))));
+
+    let record = manager.store().load_shipment(&tracking_number).unwrap().unwrap();
+    assert_eq!(record.lifecycle.state, LifecycleState::Cancelled);
+    assert_eq!(record.lifecycle.audit.last().unwrap().to, LifecycleState::Cancelled);
+    assert!(manager.manifests().pending(CarrierCode::Colissimo, None).is_empty());
+}
@@ -0,0 +1,75 @@
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::thread;
+
+use chrono::Utc;
+use zyou_delivery::printing::{LabelPrinter, PrinterProfile, PrinterState};
+use zyou_delivery::{CarrierCode, LabelFormat, ShippingLabel};
+
+const ZPL: &str = "^XA^FO50,50^A0N,40,40^FDColis 6A12345678901^FS^XZ";
+
+/// Imprimante simulée : renvoie `response` après avoir lu `expected` octets,
+/// puis rend tout ce qu'elle a reçu
+fn fake_printer(expected: usize, response: &'static [u8]) -> (u16, thread::JoinHandle<Vec<u8>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let handle = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut received = Vec::new();
+        let mut buffer = [0u8; 512];
+        while received.len() < expected {
+            match stream.read(&mut buffer).unwrap() {
+                0 => break,
+                read => received.extend_from_slice(&buffer[..read]),
+            }
+        }
+        stream.write_all(response).unwrap();
+        received
+    });
+
+    (port, handle)
+}
+
+fn zpl_label() -> ShippingLabel {
+    ShippingLabel {
+        carrier: CarrierCode::Colissimo,
+        tracking_number: "6A12345678901".to_string(),
+        label_format: LabelFormat::ZPL,
+        label_data: ZPL.as_bytes().to_vec(),
+        created_at: Utc::now(),
+        expires_at: None,
+        dimensions: None,
+        copies: 1,
+    }
+}
+
+#[test]
+fn raw_printer_receives_zpl_label() {
+    let (port, printer) = fake_printer(ZPL.len(), b"");
+    let profile = PrinterProfile::raw("Zebra", "127.0.0.1").with_port(port);
+
+    let job = LabelPrinter::new(profile).print_blocking(&zpl_label()).unwrap();
+
+    assert_eq!(printer.join().unwrap(), ZPL.as_bytes());
+    assert_eq!(job.bytes_sent, ZPL.len());
+    assert_eq!(job.tracking_number, "6A12345678901");
+}
+
+#[test]
+fn raw_printer_status_maps_host_status_response() {
+    // Plus de papier, 2 étiquettes en mémoire tampon
+    let response = b"\x02030,1,0,1245,002,0,0,0,000,0,0,0\x03\r\n\
+        \x02000,0,0,0,1,2,6,0,00000000,1,000\x03\r\n\
+        \x021234,0\x03\r\n";
+    let (port, printer) = fake_printer(3, response);
+    let profile = PrinterProfile::raw("Zebra", "127.0.0.1").with_port(port);
+
+    let status = LabelPrinter::new(profile).status_blocking().unwrap();
+
+    assert_eq!(printer.join().unwrap(), b"~HS");
+    assert_eq!(status.state, PrinterState::Stopped);
+    assert_eq!(status.reasons, vec!["media-empty".to_string()]);
+    assert_eq!(status.queued_jobs, Some(2));
+    assert!(!status.is_ready());
+}